@@ -6,6 +6,7 @@ use lazy_static::lazy_static;
 #[cfg(feature = "extensions")]
 use crate::commands::extensions;
 use crate::commands::parse;
+use crate::format::OutputFormat;
 
 const VERSION: &str = git_version!(args = ["--dirty=-modified", "--tags"], cargo_suffix = "+");
 
@@ -46,11 +47,23 @@ pub fn app() -> Command {
                 .help("Ignore all configuration files")
                 .conflicts_with("config")
                 .action(ArgAction::SetTrue),
+            Arg::new("profile")
+                .long("profile")
+                .value_name("PROFILE")
+                .help("Select a configuration profile, overriding PHYLUM_PROFILE")
+                .conflicts_with("no-config"),
             Arg::new("timeout")
                 .short('t')
                 .long("timeout")
                 .value_name("TIMEOUT")
                 .help("Set the timeout (in seconds) for requests to the Phylum api"),
+            Arg::new("retries")
+                .long("retries")
+                .value_name("COUNT")
+                .value_parser(clap::value_parser!(u32))
+                .help(
+                    "Maximum number of retries for failed requests to the Phylum api (default: 3)",
+                ),
             Arg::new("no-check-certificate")
                 .action(ArgAction::SetTrue)
                 .long("no-check-certificate")
@@ -74,6 +87,30 @@ pub fn app() -> Command {
                 .help("Reduce the level of verbosity (the maximum is -qq)")
                 .action(ArgAction::Count)
                 .conflicts_with("verbose"),
+            Arg::new("log-format")
+                .long("log-format")
+                .value_name("FORMAT")
+                .value_parser(["text", "json"])
+                .global(true)
+                .help("Format of log messages (default: text)"),
+            Arg::new("log-file")
+                .long("log-file")
+                .value_name("FILE")
+                .value_hint(ValueHint::FilePath)
+                .global(true)
+                .help("Append log messages to a file instead of stderr"),
+            Arg::new("progress")
+                .long("progress")
+                .value_name("MODE")
+                .value_parser(["auto", "text", "json", "none"])
+                .global(true)
+                .help("Progress reporting on stderr (default: auto)"),
+            Arg::new("error-format")
+                .long("error-format")
+                .value_name("FORMAT")
+                .value_parser(["text", "json"])
+                .global(true)
+                .help("Format used to report failures on stderr (default: text)"),
         ]);
 
     app = add_subcommands(app);
@@ -98,6 +135,12 @@ pub fn add_subcommands(command: Command) -> Command {
                     .short('j')
                     .long("json")
                     .help("Produce output in json format (default: false)"),
+                Arg::new("format")
+                    .long("format")
+                    .value_name("FORMAT")
+                    .value_parser(PossibleValuesParser::new(OutputFormat::NAMES))
+                    .conflicts_with("json")
+                    .help("Output format for analysis results (default: pretty)"),
                 Arg::new("project")
                     .short('p')
                     .long("project")
@@ -288,6 +331,20 @@ pub fn add_subcommands(command: Command) -> Command {
                                 .short('n')
                                 .long("token-name")
                                 .help("Unique name for the new token that will be created"),
+                        )
+                        .arg(
+                            Arg::new("device")
+                                .action(ArgAction::SetTrue)
+                                .long("device")
+                                .conflicts_with_all(["reauth", "token-name"])
+                                .help("Login on a different device, without a local browser"),
+                        )
+                        .arg(
+                            Arg::new("ci")
+                                .action(ArgAction::SetTrue)
+                                .long("ci")
+                                .conflicts_with_all(["reauth", "token-name", "device"])
+                                .help("Verify the CI platform's identity token exchange"),
                         ),
                 )
                 .subcommand(
@@ -376,32 +433,91 @@ pub fn add_subcommands(command: Command) -> Command {
         .subcommand(
             Command::new("analyze")
                 .about("Submit a request for analysis to the processing system")
+                .after_long_help(
+                    "Dependency confusion with the `internal_packages` of the `.phylum_project` \
+                     is only detected within the analyzed lockfiles. Public registries are not \
+                     queried, so internal packages whose name is also published publicly are only \
+                     reported once a lockfile resolves them from a public registry.",
+                )
+                .args(analyze_args())
+                .arg(
+                    Arg::new("baseline")
+                        .long("baseline")
+                        .value_name("FILE")
+                        .value_hint(ValueHint::FilePath)
+                        .help("Only fail on policy rejections missing from this baseline file"),
+                ),
+        )
+        .subcommand(
+            Command::new("baseline")
+                .about("Manage baselines of accepted policy rejections")
+                .arg_required_else_help(true)
+                .subcommand_required(true)
+                .subcommand(
+                    Command::new("update")
+                        .about("Analyze dependency files and replace the baseline's rejections")
+                        .args(analyze_args())
+                        .arg(
+                            Arg::new("baseline")
+                                .long("baseline")
+                                .value_name("FILE")
+                                .value_hint(ValueHint::FilePath)
+                                .default_value("phylum-baseline.json")
+                                .help("Baseline file to update (default: phylum-baseline.json)"),
+                        ),
+                ),
+        )
+        .subcommand(
+            Command::new("check-lock")
+                .about("Check lockfiles for drift from their manifests")
                 .args(&[
-                    Arg::new("label")
-                        .short('l')
-                        .long("label")
-                        .value_name("LABEL")
-                        .help("Specify a label to use for analysis"),
+                    Arg::new("manifest")
+                        .value_name("MANIFEST")
+                        .value_hint(ValueHint::FilePath)
+                        .help("Path to the manifest to check (default: all supported manifests)")
+                        .action(ArgAction::Append),
                     Arg::new("json")
                         .action(ArgAction::SetTrue)
                         .short('j')
                         .long("json")
                         .help("Produce output in json format (default: false)"),
-                    Arg::new("project")
-                        .short('p')
-                        .long("project")
-                        .value_name("PROJECT_NAME")
-                        .help("Specify a project to use for analysis"),
-                    Arg::new("group")
-                        .short('g')
-                        .long("group")
-                        .value_name("GROUP_NAME")
-                        .help("Specify a group to use for analysis")
-                        .requires("project"),
+                ]),
+        )
+        .subcommand(
+            Command::new("suppressions")
+                .about("Manage local suppressions of policy rejections")
+                .arg_required_else_help(true)
+                .subcommand_required(true)
+                .subcommand(
+                    Command::new("audit").about("List expired and expiring suppressions").args(&[
+                        Arg::new("file").value_name("FILE").value_hint(ValueHint::FilePath).help(
+                            "Path to the suppressions file (default: project's \
+                             .phylum-suppressions.toml)",
+                        ),
+                        Arg::new("within")
+                            .long("within")
+                            .value_name("DAYS")
+                            .value_parser(clap::value_parser!(u32))
+                            .default_value("30")
+                            .help(
+                                "Report suppressions expiring within this many days (default: 30)",
+                            ),
+                        Arg::new("json")
+                            .action(ArgAction::SetTrue)
+                            .short('j')
+                            .long("json")
+                            .help("Produce output in json format (default: false)"),
+                    ]),
+                ),
+        )
+        .subcommand(
+            Command::new("verify-installed")
+                .about("Verify installed packages against lockfiles")
+                .args(&[
                     Arg::new("depfile")
                         .value_name("DEPENDENCY_FILE")
                         .value_hint(ValueHint::FilePath)
-                        .help("Path to the dependency file to submit")
+                        .help("Path to the lockfile to verify against")
                         .action(ArgAction::Append),
                     Arg::new("type")
                         .short('t')
@@ -410,21 +526,44 @@ pub fn add_subcommands(command: Command) -> Command {
                         .requires("depfile")
                         .help("Dependency file type used for all lockfiles (default: auto)")
                         .value_parser(PossibleValuesParser::new(parse::lockfile_types(true))),
-                    Arg::new("base")
-                        .short('b')
-                        .long("base")
-                        .value_name("FILE")
-                        .value_hint(ValueHint::FilePath)
-                        .help("Previous list of dependencies for analyzing the delta")
-                        .hide(true),
-                    Arg::new("skip-sandbox")
+                    Arg::new("path")
+                        .long("path")
+                        .value_name("DIR")
+                        .value_hint(ValueHint::DirPath)
+                        .help("Directory with installed packages (default: current directory)"),
+                    Arg::new("json")
                         .action(ArgAction::SetTrue)
-                        .long("skip-sandbox")
-                        .help("Run lockfile generation without sandbox protection"),
-                    Arg::new("no-generation")
+                        .short('j')
+                        .long("json")
+                        .help("Produce output in json format (default: false)"),
+                ]),
+        )
+        .subcommand(
+            Command::new("lint")
+                .about("Detect unpinned and mutable dependency sources in lockfiles")
+                .args(&[
+                    Arg::new("depfile")
+                        .value_name("DEPENDENCY_FILE")
+                        .value_hint(ValueHint::FilePath)
+                        .help("Path to the lockfile to lint")
+                        .action(ArgAction::Append),
+                    Arg::new("type")
+                        .short('t')
+                        .long("type")
+                        .value_name("TYPE")
+                        .requires("depfile")
+                        .help("Dependency file type used for all lockfiles (default: auto)")
+                        .value_parser(PossibleValuesParser::new(parse::lockfile_types(true))),
+                    Arg::new("strictness")
+                        .long("strictness")
+                        .value_name("LEVEL")
+                        .help("Exit with a non-zero code for findings of at least this severity")
+                        .value_parser(["info", "warning", "error"]),
+                    Arg::new("json")
                         .action(ArgAction::SetTrue)
-                        .long("no-generation")
-                        .help("Disable generation of lockfiles from manifests"),
+                        .short('j')
+                        .long("json")
+                        .help("Produce output in json format (default: false)"),
                 ]),
         )
         .subcommand(Command::new("version").about("Display application version"))
@@ -602,6 +741,112 @@ pub fn add_subcommands(command: Command) -> Command {
                 .subcommand(
                     Command::new("unlink").about("Clear the configured default organization"),
                 ),
+        )
+        .subcommand(
+            Command::new("config")
+                .about("Manage the CLI configuration")
+                .arg_required_else_help(true)
+                .subcommand_required(true)
+                .subcommand(
+                    Command::new("get").about("Print the effective value of a setting").args(&[
+                        Arg::new("key")
+                            .value_name("KEY")
+                            .help("Name of the setting, like `connection.uri`")
+                            .required(true),
+                    ]),
+                )
+                .subcommand(
+                    Command::new("set").about("Update a setting in the configuration file").args(
+                        &[
+                            Arg::new("key")
+                                .value_name("KEY")
+                                .help("Name of the setting, like `connection.uri`")
+                                .required(true),
+                            Arg::new("value")
+                                .value_name("VALUE")
+                                .help("New value of the setting")
+                                .required(true),
+                        ],
+                    ),
+                )
+                .subcommand(
+                    Command::new("unset")
+                        .about("Reset a setting in the configuration file to its default value")
+                        .args(&[Arg::new("key")
+                            .value_name("KEY")
+                            .help("Name of the setting, like `connection.uri`")
+                            .required(true)]),
+                )
+                .subcommand(
+                    Command::new("list").about("List the effective value of all settings").args(&[
+                        Arg::new("show-origin")
+                            .action(ArgAction::SetTrue)
+                            .long("show-origin")
+                            .help("Show where each value came from"),
+                        Arg::new("json")
+                            .action(ArgAction::SetTrue)
+                            .short('j')
+                            .long("json")
+                            .help("Produce output in json format (default: false)"),
+                    ]),
+                )
+                .subcommand(Command::new("path").about("Print the configuration file path"))
+                .subcommand(
+                    Command::new("profile")
+                        .about("Manage configuration profiles")
+                        .arg_required_else_help(true)
+                        .subcommand_required(true)
+                        .subcommand(
+                            Command::new("list").about("List all configuration profiles").args(&[
+                                Arg::new("json")
+                                    .action(ArgAction::SetTrue)
+                                    .short('j')
+                                    .long("json")
+                                    .help("Produce output in json format (default: false)"),
+                            ]),
+                        )
+                        .subcommand(
+                            Command::new("use").about("Select the profile used by default").args(
+                                &[Arg::new("name")
+                                    .value_name("NAME")
+                                    .help("Name of the profile")
+                                    .required(true)],
+                            ),
+                        )
+                        .subcommand(
+                            Command::new("add").about("Create a new profile").args(&[
+                                Arg::new("name")
+                                    .value_name("NAME")
+                                    .help("Name of the profile")
+                                    .required(true),
+                                Arg::new("uri")
+                                    .long("uri")
+                                    .value_name("URI")
+                                    .help("Phylum API URI (default: https://api.phylum.io)"),
+                                Arg::new("ca-bundle")
+                                    .long("ca-bundle")
+                                    .value_name("FILE")
+                                    .value_hint(ValueHint::FilePath)
+                                    .help("PEM file with additional trusted root certificates"),
+                                Arg::new("proxy")
+                                    .long("proxy")
+                                    .value_name("URL")
+                                    .help("Proxy URL for all requests"),
+                                Arg::new("no-check-certificate")
+                                    .action(ArgAction::SetTrue)
+                                    .long("no-check-certificate")
+                                    .help("Don't validate the server certificate"),
+                            ]),
+                        )
+                        .subcommand(
+                            Command::new("remove").alias("rm").about("Delete a profile").args(&[
+                                Arg::new("name")
+                                    .value_name("NAME")
+                                    .help("Name of the profile")
+                                    .required(true),
+                            ]),
+                        ),
+                ),
         );
 
     #[cfg(feature = "extensions")]
@@ -718,6 +963,75 @@ pub fn add_subcommands(command: Command) -> Command {
     app
 }
 
+/// Arguments shared by all commands submitting an analysis.
+fn analyze_args() -> Vec<Arg> {
+    vec![
+        Arg::new("label")
+            .short('l')
+            .long("label")
+            .value_name("LABEL")
+            .help("Specify a label to use for analysis (default: current branch)"),
+        Arg::new("json")
+            .action(ArgAction::SetTrue)
+            .short('j')
+            .long("json")
+            .help("Produce output in json format (default: false)"),
+        Arg::new("format")
+            .long("format")
+            .value_name("FORMAT")
+            .value_parser(PossibleValuesParser::new(OutputFormat::NAMES))
+            .conflicts_with("json")
+            .help("Output format for analysis results (default: pretty)"),
+        Arg::new("project")
+            .short('p')
+            .long("project")
+            .value_name("PROJECT_NAME")
+            .help("Specify a project to use for analysis"),
+        Arg::new("group")
+            .short('g')
+            .long("group")
+            .value_name("GROUP_NAME")
+            .help("Specify a group to use for analysis")
+            .requires("project"),
+        Arg::new("depfile")
+            .value_name("DEPENDENCY_FILE")
+            .value_hint(ValueHint::FilePath)
+            .help("Path to the dependency file to submit")
+            .action(ArgAction::Append),
+        Arg::new("type")
+            .short('t')
+            .long("type")
+            .value_name("TYPE")
+            .requires("depfile")
+            .help("Dependency file type used for all lockfiles (default: auto)")
+            .value_parser(PossibleValuesParser::new(parse::lockfile_types(true))),
+        Arg::new("base")
+            .short('b')
+            .long("base")
+            .value_name("FILE")
+            .value_hint(ValueHint::FilePath)
+            .help("Previous list of dependencies for analyzing the delta")
+            .hide(true),
+        Arg::new("skip-sandbox")
+            .action(ArgAction::SetTrue)
+            .long("skip-sandbox")
+            .help("Run lockfile generation without sandbox protection"),
+        Arg::new("no-generation")
+            .action(ArgAction::SetTrue)
+            .long("no-generation")
+            .help("Disable generation of lockfiles from manifests"),
+        Arg::new("no-annotations")
+            .action(ArgAction::SetTrue)
+            .long("no-annotations")
+            .help("Disable native annotations when running in CI"),
+        Arg::new("code-quality-report")
+            .long("code-quality-report")
+            .value_name("FILE")
+            .value_hint(ValueHint::FilePath)
+            .help("Write a GitLab code quality report to this file"),
+    ]
+}
+
 /// Check if a non-extension subcommand exists.
 pub fn is_builtin_subcommand(name: &str) -> bool {
     add_subcommands(Command::new("phylum"))
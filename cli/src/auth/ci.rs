@@ -0,0 +1,210 @@
+//! CI workload identity authentication.
+//!
+//! Instead of storing an API key as CI secret, the identity token issued by
+//! the CI platform is exchanged for a short-lived access token (RFC 8693).
+//! The access token is only kept in memory and never written to disk.
+//!
+//! The identity token is read from the first available source:
+//!  - `PHYLUM_CI_ID_TOKEN` containing the token itself
+//!  - `PHYLUM_CI_ID_TOKEN_FILE` containing the path to a token file
+//!  - GitHub Actions' `ACTIONS_ID_TOKEN_REQUEST_URL` and
+//!    `ACTIONS_ID_TOKEN_REQUEST_TOKEN`
+
+use std::time::Duration;
+use std::{env, fs};
+
+use anyhow::{anyhow, Context, Result};
+use chrono::Utc;
+use phylum_api::Token;
+use phylum_types::types::auth::AccessToken;
+use reqwest::{Client, Url};
+use serde::Deserialize;
+
+use super::jwt;
+use super::oidc::{fetch_oidc_server_settings, OIDC_CLIENT_ID};
+use crate::config::ConnectionInfo;
+use crate::http;
+
+/// Grant type for exchanging the identity token.
+const TOKEN_EXCHANGE_GRANT_TYPE: &str = "urn:ietf:params:oauth:grant-type:token-exchange";
+
+/// Token type of the CI identity token.
+const JWT_TOKEN_TYPE: &str = "urn:ietf:params:oauth:token-type:jwt";
+
+/// Token type requested from the token exchange.
+const ACCESS_TOKEN_TYPE: &str = "urn:ietf:params:oauth:token-type:access_token";
+
+/// GitHub Actions identity token response.
+#[derive(Deserialize)]
+struct GitHubTokenResponse {
+    value: String,
+}
+
+/// Token exchange response.
+#[derive(Deserialize)]
+struct TokenExchangeResponse {
+    access_token: AccessToken,
+    /// Lifetime of the access token in seconds.
+    expires_in: Option<i64>,
+}
+
+/// Get an access token for the CI identity token.
+pub async fn access_token(ignore_certs: bool, connection: &ConnectionInfo) -> Result<Token> {
+    let client = http::api_client_builder(connection, ignore_certs)?.build()?;
+    let id_token = identity_token(&client, &connection.uri).await?;
+
+    let oidc_settings = fetch_oidc_server_settings(ignore_certs, connection).await?;
+    exchange_token(&client, oidc_settings.token_endpoint, &id_token)
+        .await
+        .context("CI identity token exchange failed")
+}
+
+/// Read the CI platform's identity token.
+async fn identity_token(client: &Client, audience: &str) -> Result<String> {
+    if let Some(token) = env::var("PHYLUM_CI_ID_TOKEN").ok().filter(|token| !token.is_empty()) {
+        return Ok(token);
+    }
+
+    if let Some(path) = env::var_os("PHYLUM_CI_ID_TOKEN_FILE") {
+        let token = fs::read_to_string(&path)
+            .with_context(|| format!("Could not read identity token from {path:?}"))?;
+        return Ok(token.trim().into());
+    }
+
+    let request_url = env::var("ACTIONS_ID_TOKEN_REQUEST_URL");
+    let request_token = env::var("ACTIONS_ID_TOKEN_REQUEST_TOKEN");
+    if let (Ok(request_url), Ok(request_token)) = (request_url, request_token) {
+        let request_url = Url::parse(&request_url)?;
+        return github_token(client, request_url, &request_token, audience).await;
+    }
+
+    Err(anyhow!(
+        "No CI identity token found; set `PHYLUM_CI_ID_TOKEN`, `PHYLUM_CI_ID_TOKEN_FILE` or grant \
+         the `id-token: write` permission in GitHub Actions"
+    ))
+}
+
+/// Request an identity token from GitHub Actions.
+async fn github_token(
+    client: &Client,
+    mut request_url: Url,
+    request_token: &str,
+    audience: &str,
+) -> Result<String> {
+    request_url.query_pairs_mut().append_pair("audience", audience);
+
+    let response = client
+        .get(request_url)
+        .header("Accept", "application/json")
+        .bearer_auth(request_token)
+        .timeout(Duration::from_secs(5))
+        .send()
+        .await?;
+
+    if let Err(error) = response.error_for_status_ref() {
+        Err(anyhow!(response.text().await?)).context(error)
+    } else {
+        Ok(response.json::<GitHubTokenResponse>().await?.value)
+    }
+}
+
+/// Exchange the identity token for an access token.
+async fn exchange_token(client: &Client, token_url: Url, id_token: &str) -> Result<Token> {
+    let body = [
+        ("client_id", OIDC_CLIENT_ID),
+        ("grant_type", TOKEN_EXCHANGE_GRANT_TYPE),
+        ("requested_token_type", ACCESS_TOKEN_TYPE),
+        ("subject_token", id_token),
+        ("subject_token_type", JWT_TOKEN_TYPE),
+    ];
+    let response = client
+        .post(token_url)
+        .header("Accept", "application/json")
+        .timeout(Duration::from_secs(5))
+        .form(&body)
+        .send()
+        .await?;
+
+    if let Err(error) = response.error_for_status_ref() {
+        return Err(anyhow!(response.text().await?)).context(error);
+    }
+
+    let response = response.json::<TokenExchangeResponse>().await?;
+    let expires_at = match response.expires_in {
+        Some(expires_in) => Some(Utc::now() + chrono::Duration::seconds(expires_in)),
+        None => jwt::expiry(response.access_token.as_str()).ok().flatten(),
+    };
+
+    Ok(Token { access_token: response.access_token, expires_at })
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+    use wiremock::matchers::{body_string_contains, header, method, path, query_param};
+    use wiremock::{Mock, ResponseTemplate};
+
+    use super::*;
+    use crate::test::mockito::*;
+
+    #[tokio::test]
+    async fn github_token_is_requested() -> Result<()> {
+        let mock_server = build_mock_server().await;
+        Mock::given(method("GET"))
+            .and(path("github-token"))
+            .and(query_param("audience", "https://api.phylum.io"))
+            .and(header("Authorization", "Bearer REQUEST_TOKEN"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "value": "ID" })))
+            .mount(&mock_server)
+            .await;
+
+        let client = Client::new();
+        let url = Url::parse(&format!("{}/github-token", mock_server.uri()))?;
+        let token = github_token(&client, url, "REQUEST_TOKEN", "https://api.phylum.io").await?;
+        assert_eq!(token, "ID");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn identity_token_is_exchanged() -> Result<()> {
+        let mock_server = build_mock_server().await;
+        Mock::given(method("POST"))
+            .and(path(TOKEN_URI))
+            .and(body_string_contains("subject_token=ID_TOKEN"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "access_token": DUMMY_ACCESS_TOKEN,
+                "issued_token_type": ACCESS_TOKEN_TYPE,
+                "token_type": "Bearer",
+                "expires_in": 300,
+            })))
+            .with_priority(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = Client::new();
+        let token_url = Url::parse(&mock_server.uri())?.join(TOKEN_URI)?;
+        let token = exchange_token(&client, token_url, "ID_TOKEN").await?;
+        assert_eq!(token.access_token.as_str(), DUMMY_ACCESS_TOKEN);
+        assert!(token.is_fresh());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn rejected_exchange_fails() {
+        let mock_server = build_mock_server().await;
+        Mock::given(method("POST"))
+            .and(path(TOKEN_URI))
+            .respond_with(
+                ResponseTemplate::new(400).set_body_json(json!({ "error": "invalid_grant" })),
+            )
+            .with_priority(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = Client::new();
+        let token_url = Url::parse(&mock_server.uri()).unwrap().join(TOKEN_URI).unwrap();
+        assert!(exchange_token(&client, token_url, "ID_TOKEN").await.is_err());
+    }
+}
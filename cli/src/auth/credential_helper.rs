@@ -0,0 +1,146 @@
+//! External credential helpers.
+//!
+//! Credential helpers are programs which store the refresh token outside of
+//! the configuration file. Like git credential helpers, they are invoked with
+//! `get`, `store` or `erase` as their last argument and exchange `key=value`
+//! lines over stdin and stdout:
+//!
+//! ```text
+//! $ printf 'uri=https://api.phylum.io\n' | my-helper get
+//! token=ph0_...
+//! ```
+//!
+//! The `uri` is always sent to identify the Phylum instance, while `store` also
+//! receives the `token`. Only `get` is expected to write anything to stdout.
+
+use std::io::{self, Write};
+use std::process::{Command, Stdio};
+
+use anyhow::{anyhow, Context, Result};
+use phylum_types::types::auth::RefreshToken;
+
+/// Get the refresh token from the credential helper.
+pub fn get(helper: &str, uri: &str) -> Result<Option<RefreshToken>> {
+    let output = run(helper, "get", &[("uri", uri)])?;
+
+    let token = output
+        .lines()
+        .filter_map(|line| line.split_once('='))
+        .find(|(key, _)| *key == "token")
+        .map(|(_, token)| token.trim())
+        .filter(|token| !token.is_empty())
+        .map(RefreshToken::new);
+
+    Ok(token)
+}
+
+/// Persist the refresh token with the credential helper.
+pub fn store(helper: &str, uri: &str, token: &RefreshToken) -> Result<()> {
+    run(helper, "store", &[("uri", uri), ("token", token.as_str())])?;
+    Ok(())
+}
+
+/// Remove the refresh token from the credential helper.
+pub fn erase(helper: &str, uri: &str) -> Result<()> {
+    run(helper, "erase", &[("uri", uri)])?;
+    Ok(())
+}
+
+/// Run a credential helper action, returning its stdout.
+fn run(helper: &str, action: &str, input: &[(&str, &str)]) -> Result<String> {
+    let mut child = shell_command(helper, action)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .with_context(|| format!("Could not run credential helper {helper:?}"))?;
+
+    // Write the request and close stdin to signal its end.
+    //
+    // Helpers are free to ignore their input and exit early, so a closed pipe
+    // is not an error. Only the exit status and output decide the result.
+    let mut stdin = child.stdin.take().expect("piped stdin");
+    let request: String = input.iter().map(|(key, value)| format!("{key}={value}\n")).collect();
+    match stdin.write_all(request.as_bytes()) {
+        Err(err) if err.kind() != io::ErrorKind::BrokenPipe => {
+            return Err(err)
+                .with_context(|| format!("Could not write to credential helper {helper:?}"));
+        },
+        _ => (),
+    }
+    drop(stdin);
+
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        return Err(anyhow!(
+            "Credential helper {helper:?} failed on {action:?}: {}",
+            output.status
+        ));
+    }
+
+    String::from_utf8(output.stdout)
+        .with_context(|| format!("Invalid output from credential helper {helper:?}"))
+}
+
+/// Run the helper through the shell, to allow passing arguments.
+#[cfg(unix)]
+fn shell_command(helper: &str, action: &str) -> Command {
+    let mut command = Command::new("sh");
+    command.arg("-c").arg(format!("{helper} \"$@\"")).arg(helper).arg(action);
+    command
+}
+
+/// Run the helper through the shell, to allow passing arguments.
+#[cfg(windows)]
+fn shell_command(helper: &str, action: &str) -> Command {
+    let mut command = Command::new("cmd");
+    command.arg("/C").arg(format!("{helper} {action}"));
+    command
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn store_get_erase() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let store_path = tempdir.path().join("token");
+
+        // Minimal helper keeping the request in a file.
+        let helper = format!(
+            r#"helper() {{
+                case "$1" in
+                    get) cat "{0}" 2>/dev/null || true ;;
+                    store) cat > "{0}" ;;
+                    erase) rm -f "{0}" ;;
+                esac
+            }}
+            helper"#,
+            store_path.display(),
+        );
+        let uri = "https://api.phylum.io";
+
+        assert_eq!(get(&helper, uri).unwrap(), None);
+
+        store(&helper, uri, &RefreshToken::new("secret")).unwrap();
+        assert_eq!(get(&helper, uri).unwrap(), Some(RefreshToken::new("secret")));
+
+        erase(&helper, uri).unwrap();
+        assert_eq!(get(&helper, uri).unwrap(), None);
+    }
+
+    #[test]
+    fn helper_ignoring_input() {
+        // The request is larger than the pipe buffer, so writing it fails once the
+        // helper exits without reading it.
+        let uri = format!("https://{}.example.com", "a".repeat(1 << 20));
+        let token = get("helper() { echo token=secret; }; helper", &uri).unwrap();
+        assert_eq!(token, Some(RefreshToken::new("secret")));
+    }
+
+    #[test]
+    fn failing_helper() {
+        assert!(get("false", "https://api.phylum.io").is_err());
+        assert!(store("false", "https://api.phylum.io", &RefreshToken::new("token")).is_err());
+    }
+}
@@ -0,0 +1,259 @@
+//! OAuth 2.0 device authorization grant (RFC 8628).
+//!
+//! This allows logging in from machines without a browser, like SSH sessions
+//! or containers, by completing the authorization on a different device.
+
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, Context, Result};
+use log::debug;
+use phylum_types::types::auth::{RefreshToken, TokenResponse};
+use reqwest::{Client, Url};
+use serde::Deserialize;
+
+use super::oidc::{fetch_oidc_server_settings, ResponseError, OIDC_CLIENT_ID, OIDC_SCOPES};
+use crate::config::ConnectionInfo;
+use crate::{http, print_user_warning};
+
+/// Grant type used for polling the token endpoint.
+const DEVICE_CODE_GRANT_TYPE: &str = "urn:ietf:params:oauth:grant-type:device_code";
+
+/// Token endpoint polling schedule.
+#[derive(Copy, Clone, Debug)]
+struct PollSettings {
+    /// Polling interval used when the server does not specify one.
+    default_interval: Duration,
+    /// Polling interval increase requested by a `slow_down` response.
+    slow_down_increment: Duration,
+}
+
+impl Default for PollSettings {
+    /// Intervals recommended by RFC 8628.
+    fn default() -> Self {
+        Self {
+            default_interval: Duration::from_secs(5),
+            slow_down_increment: Duration::from_secs(5),
+        }
+    }
+}
+
+/// Device authorization endpoint response.
+#[derive(Debug, Deserialize)]
+pub struct DeviceAuthorizationResponse {
+    pub device_code: String,
+    pub user_code: String,
+    pub verification_uri: Url,
+    pub verification_uri_complete: Option<Url>,
+    /// Lifetime of the device code in seconds.
+    pub expires_in: u64,
+    /// Minimum polling interval in seconds.
+    pub interval: Option<u64>,
+}
+
+/// Handle the device authorization login flow.
+pub async fn handle_device_flow(
+    ignore_certs: bool,
+    connection: &ConnectionInfo,
+) -> Result<RefreshToken> {
+    device_flow(ignore_certs, connection, PollSettings::default()).await
+}
+
+/// Run the device authorization login flow with a custom polling schedule.
+async fn device_flow(
+    ignore_certs: bool,
+    connection: &ConnectionInfo,
+    poll_settings: PollSettings,
+) -> Result<RefreshToken> {
+    let oidc_settings = fetch_oidc_server_settings(ignore_certs, connection).await?;
+    let device_endpoint = oidc_settings
+        .device_authorization_endpoint
+        .ok_or_else(|| anyhow!("Server does not support the device authorization flow"))?;
+
+    let client = http::api_client_builder(connection, ignore_certs)?.build()?;
+    let authorization = request_device_code(&client, device_endpoint).await?;
+
+    // Instruct user on how to complete login.
+    print_user_warning!(
+        "To complete the login process, visit {} in a browser and enter the code: {}",
+        authorization.verification_uri,
+        authorization.user_code,
+    );
+    if let Some(uri) = &authorization.verification_uri_complete {
+        print_user_warning!("Alternatively, you can use this link which includes the code: {uri}");
+    }
+
+    poll_for_tokens(&client, oidc_settings.token_endpoint, &authorization, poll_settings)
+        .await
+        .map(|tokens| tokens.refresh_token)
+}
+
+/// Request a new device and user code.
+async fn request_device_code(client: &Client, url: Url) -> Result<DeviceAuthorizationResponse> {
+    let scope = OIDC_SCOPES.join(" ");
+    let body = [("client_id", OIDC_CLIENT_ID), ("scope", scope.as_str())];
+    let response = client
+        .post(url)
+        .header("Accept", "application/json")
+        .timeout(Duration::from_secs(5))
+        .form(&body)
+        .send()
+        .await?;
+
+    if let Err(error) = response.error_for_status_ref() {
+        Err(anyhow!(response.text().await?)).context(error)
+    } else {
+        Ok(response.json::<DeviceAuthorizationResponse>().await?)
+    }
+}
+
+/// Poll the token endpoint until the user has completed the authorization.
+async fn poll_for_tokens(
+    client: &Client,
+    token_url: Url,
+    authorization: &DeviceAuthorizationResponse,
+    poll_settings: PollSettings,
+) -> Result<TokenResponse> {
+    let deadline = Instant::now() + Duration::from_secs(authorization.expires_in);
+    let mut interval =
+        authorization.interval.map_or(poll_settings.default_interval, Duration::from_secs);
+
+    let body = [
+        ("client_id", OIDC_CLIENT_ID),
+        ("device_code", authorization.device_code.as_str()),
+        ("grant_type", DEVICE_CODE_GRANT_TYPE),
+    ];
+
+    loop {
+        tokio::time::sleep(interval).await;
+
+        let response = client
+            .post(token_url.clone())
+            .header("Accept", "application/json")
+            .timeout(Duration::from_secs(5))
+            .form(&body)
+            .send()
+            .await?;
+
+        if response.status().is_success() {
+            return Ok(response.json::<TokenResponse>().await?);
+        }
+
+        let status = response.status();
+        let text = response.text().await?;
+        let error = match serde_json::from_str::<ResponseError>(&text) {
+            Ok(error) => error,
+            Err(_) => return Err(anyhow!(text)).context(status),
+        };
+
+        match error.error.as_str() {
+            "authorization_pending" => (),
+            "slow_down" => interval += poll_settings.slow_down_increment,
+            "access_denied" => return Err(anyhow!("Authorization request was denied")),
+            "expired_token" => return Err(anyhow!("Device code expired, please try again")),
+            _ => {
+                let description = error.error_description.unwrap_or(error.error);
+                return Err(anyhow!(description)).context(status);
+            },
+        }
+
+        if Instant::now() + interval >= deadline {
+            return Err(anyhow!("Device code expired, please try again"));
+        }
+
+        debug!("Authorization pending, polling again in {}s", interval.as_secs_f32());
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    use serde_json::json;
+    use wiremock::matchers::{body_string_contains, method, path};
+    use wiremock::{Mock, ResponseTemplate};
+
+    use super::*;
+    use crate::test::mockito::*;
+
+    /// Poll quickly, to avoid slowing down tests.
+    const FAST_POLL: PollSettings = PollSettings {
+        default_interval: Duration::from_millis(10),
+        slow_down_increment: Duration::from_millis(10),
+    };
+
+    #[tokio::test]
+    async fn device_flow_is_successful() -> Result<()> {
+        let mock_server = build_mock_server().await;
+        let connection = ConnectionInfo { uri: mock_server.uri(), ..Default::default() };
+
+        let token = device_flow(false, &connection, FAST_POLL).await?;
+        assert_eq!(token, RefreshToken::new(DUMMY_REFRESH_TOKEN));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn device_flow_waits_for_authorization() -> Result<()> {
+        let mock_server = build_mock_server().await;
+        let connection = ConnectionInfo { uri: mock_server.uri(), ..Default::default() };
+
+        // Fail the first two token requests, before falling back to success.
+        let requests = Arc::new(AtomicUsize::new(0));
+        let counter = requests.clone();
+        Mock::given(method("POST"))
+            .and(path(TOKEN_URI))
+            .and(body_string_contains("device_code"))
+            .respond_with_fn(move |_| {
+                let error = match counter.fetch_add(1, Ordering::SeqCst) {
+                    0 => "authorization_pending",
+                    _ => "slow_down",
+                };
+                ResponseTemplate::new(400).set_body_json(json!({ "error": error }))
+            })
+            .up_to_n_times(2)
+            .with_priority(1)
+            .mount(&mock_server)
+            .await;
+
+        let token = device_flow(false, &connection, FAST_POLL).await?;
+        assert_eq!(token, RefreshToken::new(DUMMY_REFRESH_TOKEN));
+        assert_eq!(requests.load(Ordering::SeqCst), 2);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn device_flow_fails_on_expiry() {
+        let mock_server = build_mock_server().await;
+        let connection = ConnectionInfo { uri: mock_server.uri(), ..Default::default() };
+
+        Mock::given(method("POST"))
+            .and(path(TOKEN_URI))
+            .respond_with(
+                ResponseTemplate::new(400).set_body_json(json!({ "error": "expired_token" })),
+            )
+            .with_priority(1)
+            .mount(&mock_server)
+            .await;
+
+        assert!(device_flow(false, &connection, FAST_POLL).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn device_flow_fails_on_denial() {
+        let mock_server = build_mock_server().await;
+        let connection = ConnectionInfo { uri: mock_server.uri(), ..Default::default() };
+
+        Mock::given(method("POST"))
+            .and(path(TOKEN_URI))
+            .respond_with(
+                ResponseTemplate::new(400).set_body_json(json!({ "error": "access_denied" })),
+            )
+            .with_priority(1)
+            .mount(&mock_server)
+            .await;
+
+        assert!(device_flow(false, &connection, FAST_POLL).await.is_err());
+    }
+}
@@ -2,25 +2,37 @@
 use std::collections::HashSet;
 use std::string::FromUtf8Error;
 
-use base64::engine::general_purpose::STANDARD;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
 use base64::prelude::*;
 use base64::DecodeError;
+use chrono::{DateTime, Utc};
+use serde::de::DeserializeOwned;
 use serde::Deserialize;
 use serde_json::Error as JsonError;
 
 /// Get user roles from a bearer token without performing validation.
 pub fn user_roles(bearer: &str) -> Result<HashSet<RealmRole>, JwtError> {
+    let payload: PhylumBearer = payload(bearer)?;
+    Ok(payload.realm_access.roles)
+}
+
+/// Get the expiration time of a bearer token without performing validation.
+pub fn expiry(bearer: &str) -> Result<Option<DateTime<Utc>>, JwtError> {
+    let payload: Expiry = payload(bearer)?;
+    Ok(payload.exp.and_then(|exp| DateTime::from_timestamp(exp, 0)))
+}
+
+/// Decode the payload of a bearer token.
+fn payload<T: DeserializeOwned>(bearer: &str) -> Result<T, JwtError> {
     // Extract the base64 payload.
     let (_, payload_base64, _) = parts(bearer)?;
 
-    // Decode the payload.
-    let payload_bytes = STANDARD.decode(payload_base64)?;
+    // Decode the payload, which is base64url encoded without padding.
+    let payload_bytes = URL_SAFE_NO_PAD.decode(payload_base64.trim_end_matches('='))?;
     let payload_text = String::from_utf8(payload_bytes)?;
 
     // Parse as JSON.
-    let payload: PhylumBearer = serde_json::from_str(&payload_text)?;
-
-    Ok(payload.realm_access.roles)
+    Ok(serde_json::from_str(&payload_text)?)
 }
 
 /// Split a bearer token into header/payload/signature.
@@ -71,8 +83,34 @@ struct PhylumBearer {
     realm_access: RealmAccess,
 }
 
+/// Partial JWT payload with the expiration time.
+#[derive(Deserialize, Debug)]
+struct Expiry {
+    exp: Option<i64>,
+}
+
 /// Partial Phylum JWT realm access.
 #[derive(Deserialize, Debug)]
 struct RealmAccess {
     roles: HashSet<RealmRole>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_payload() {
+        let payload =
+            r#"{"exp":1700000000,"realm_access":{"roles":["vulnreach","offline_access"]}}"#;
+        let bearer = format!("e30.{}.c2ln", URL_SAFE_NO_PAD.encode(payload));
+
+        let expires_at = expiry(&bearer).unwrap();
+        assert_eq!(expires_at, DateTime::from_timestamp(1_700_000_000, 0));
+
+        let roles = user_roles(&bearer).unwrap();
+        assert_eq!(roles, HashSet::from([RealmRole::Vulnreach, RealmRole::Unknown]));
+
+        assert!(matches!(expiry("e30"), Err(JwtError::MissingPayload)));
+    }
+}
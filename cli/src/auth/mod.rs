@@ -1,11 +1,16 @@
+pub use device::*;
 pub use ip_addr_ext::*;
 pub use oidc::*;
 pub use server::*;
 
+pub mod ci;
+pub mod credential_helper;
+mod device;
 mod ip_addr_ext;
 pub mod jwt;
 mod oidc;
 mod server;
+pub mod token_cache;
 
 pub fn is_locksmith_token(token: impl AsRef<str>) -> bool {
     token.as_ref().starts_with("ph0_")
@@ -10,6 +10,7 @@ use base64::engine::general_purpose;
 use base64::Engine as _;
 use chrono::{DateTime, Utc};
 use maplit::hashmap;
+use phylum_api::endpoints;
 use phylum_types::types::auth::{AccessToken, AuthorizationCode, RefreshToken, TokenResponse};
 use rand::distributions::Alphanumeric;
 use rand::{thread_rng, Rng};
@@ -20,8 +21,8 @@ use uuid::Uuid;
 
 use super::ip_addr_ext::IpAddrExt;
 use super::is_locksmith_token;
-use crate::api::endpoints;
-use crate::app::USER_AGENT;
+use crate::config::ConnectionInfo;
+use crate::http;
 
 pub const OIDC_SCOPES: [&str; 2] = ["openid", "offline_access"];
 
@@ -101,6 +102,8 @@ impl<'a> From<&'a CodeVerifier> for &'a str {
 #[derive(Debug, Serialize, Deserialize)]
 pub struct OidcServerSettings {
     pub token_endpoint: Url,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub device_authorization_endpoint: Option<Url>,
 }
 
 /// Locksmith URLs
@@ -168,14 +171,11 @@ pub fn check_if_routable(hostname: impl AsRef<str>) -> Result<bool> {
 
 pub async fn fetch_oidc_server_settings(
     ignore_certs: bool,
-    api_uri: &str,
+    connection: &ConnectionInfo,
 ) -> Result<OidcServerSettings> {
-    let client = reqwest::Client::builder()
-        .user_agent(USER_AGENT.as_str())
-        .danger_accept_invalid_certs(ignore_certs)
-        .build()?;
+    let client = http::api_client_builder(connection, ignore_certs)?.build()?;
     let response = client
-        .get(endpoints::oidc_discovery(api_uri)?)
+        .get(endpoints::oidc_discovery(&connection.uri)?)
         .header("Accept", "application/json")
         .timeout(Duration::from_secs(5))
         .send()
@@ -190,14 +190,11 @@ pub async fn fetch_oidc_server_settings(
 
 pub async fn fetch_locksmith_server_settings(
     ignore_certs: bool,
-    api_uri: &str,
+    connection: &ConnectionInfo,
 ) -> Result<LocksmithServerSettings> {
-    let client = reqwest::Client::builder()
-        .user_agent(USER_AGENT.as_str())
-        .danger_accept_invalid_certs(ignore_certs)
-        .build()?;
+    let client = http::api_client_builder(connection, ignore_certs)?.build()?;
     let response = client
-        .get(endpoints::locksmith_discovery(api_uri)?)
+        .get(endpoints::locksmith_discovery(&connection.uri)?)
         .header("Accept", "application/json")
         .timeout(Duration::from_secs(5))
         .send()
@@ -254,6 +251,7 @@ pub async fn acquire_tokens(
     token_name: Option<String>,
     expiry: Option<DateTime<Utc>>,
     ignore_certs: bool,
+    connection: &ConnectionInfo,
 ) -> Result<LocksmithTokenResponse> {
     let token_url = locksmith_settings.token_endpoint.clone();
 
@@ -265,10 +263,7 @@ pub async fn acquire_tokens(
         expiry,
     )?;
 
-    let client = reqwest::Client::builder()
-        .user_agent(USER_AGENT.as_str())
-        .danger_accept_invalid_certs(ignore_certs)
-        .build()?;
+    let client = http::api_client_builder(connection, ignore_certs)?.build()?;
     let response = client
         .post(token_url)
         .header("Accept", "application/json")
@@ -301,15 +296,13 @@ pub async fn refresh_tokens(
     oidc_settings: &OidcServerSettings,
     refresh_token: &RefreshToken,
     ignore_certs: bool,
+    connection: &ConnectionInfo,
 ) -> Result<TokenResponse> {
     let token_url = oidc_settings.token_endpoint.clone();
 
     let body = build_grant_type_refresh_token_post_body(refresh_token)?;
 
-    let client = reqwest::Client::builder()
-        .user_agent(USER_AGENT.as_str())
-        .danger_accept_invalid_certs(ignore_certs)
-        .build()?;
+    let client = http::api_client_builder(connection, ignore_certs)?.build()?;
     let response = client
         .post(token_url)
         .header("Content-Type", "application/json")
@@ -331,15 +324,15 @@ pub async fn refresh_tokens(
 pub async fn renew_access_token(
     refresh_token: &RefreshToken,
     ignore_certs: bool,
-    api_uri: &str,
+    connection: &ConnectionInfo,
 ) -> Result<AccessToken> {
     // Locksmith tokens are their own access tokens
     if is_locksmith_token(refresh_token) {
         return Ok(AccessToken::new(refresh_token));
     }
 
-    let oidc_settings = fetch_oidc_server_settings(ignore_certs, api_uri).await?;
-    refresh_tokens(&oidc_settings, refresh_token, ignore_certs)
+    let oidc_settings = fetch_oidc_server_settings(ignore_certs, connection).await?;
+    refresh_tokens(&oidc_settings, refresh_token, ignore_certs, connection)
         .await
         .map(|token| token.access_token)
 }
@@ -349,38 +342,10 @@ pub struct LocksmithTokenResponse {
     pub token: RefreshToken,
 }
 
-/// Represents the userdata stored for an authentication token.
-#[derive(Debug, Clone, Deserialize, Serialize)]
-pub struct UserInfo {
-    pub email: String,
-    pub name: Option<String>,
-}
-
-impl UserInfo {
-    pub fn identity(&self) -> String {
-        match &self.name {
-            Some(name) => format!("{} <{}>", name, self.email),
-            None => format!("<{}>", self.email),
-        }
-    }
-}
-
 /// Keycloak error response.
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct ResponseError {
-    error: String,
-}
-
-#[cfg(test)]
-mod test {
-    use super::*;
-
-    #[test]
-    fn user_info_identity() {
-        let named = UserInfo { email: "john@example.com".into(), name: Some("John Doe".into()) };
-        assert_eq!(named.identity(), "John Doe <john@example.com>");
-
-        let nameless = UserInfo { email: "prince@example.com".into(), name: None };
-        assert_eq!(nameless.identity(), "<prince@example.com>");
-    }
+    pub error: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error_description: Option<String>,
 }
@@ -22,6 +22,7 @@ use super::oidc::{
     acquire_tokens, build_auth_url, check_if_routable, fetch_locksmith_server_settings, AuthAction,
     ChallengeCode, CodeVerifier, LocksmithServerSettings,
 };
+use crate::config::ConnectionInfo;
 #[cfg(test)]
 use crate::test::open;
 
@@ -180,9 +181,9 @@ pub async fn handle_auth_flow(
     token_name: Option<String>,
     expiry: Option<DateTime<Utc>>,
     ignore_certs: bool,
-    api_uri: &str,
+    connection: &ConnectionInfo,
 ) -> Result<RefreshToken> {
-    let locksmith_settings = fetch_locksmith_server_settings(ignore_certs, api_uri).await?;
+    let locksmith_settings = fetch_locksmith_server_settings(ignore_certs, connection).await?;
     let (code_verifier, challenge_code) = CodeVerifier::generate(64)?;
     let state: String = thread_rng().sample_iter(&Alphanumeric).take(32).map(char::from).collect();
     let (auth_code, callback_url) =
@@ -196,6 +197,7 @@ pub async fn handle_auth_flow(
         token_name,
         expiry,
         ignore_certs,
+        connection,
     )
     .await
     .map(|tokens| tokens.token)
@@ -228,12 +230,12 @@ mod test {
     async fn when_started_with_good_configuration_handle_auth_flow_for_login_is_successful(
     ) -> Result<()> {
         let mock_server = build_mock_server().await;
-        let api_uri = mock_server.uri();
+        let connection = ConnectionInfo { uri: mock_server.uri(), ..Default::default() };
 
         let (_verifier, _challenge) =
             CodeVerifier::generate(64).expect("Failed to build PKCE verifier and challenge");
 
-        let result = handle_auth_flow(AuthAction::Login, None, None, false, &api_uri).await?;
+        let result = handle_auth_flow(AuthAction::Login, None, None, false, &connection).await?;
 
         debug!("{:?}", result);
 
@@ -244,12 +246,12 @@ mod test {
     async fn when_started_with_good_configuration_handle_auth_flow_for_register_is_successful(
     ) -> Result<()> {
         let mock_server = build_mock_server().await;
-        let api_uri = mock_server.uri();
+        let connection = ConnectionInfo { uri: mock_server.uri(), ..Default::default() };
 
         let (_verifier, _challenge) =
             CodeVerifier::generate(64).expect("Failed to build PKCE verifier and challenge");
 
-        let result = handle_auth_flow(AuthAction::Register, None, None, false, &api_uri).await?;
+        let result = handle_auth_flow(AuthAction::Register, None, None, false, &connection).await?;
 
         debug!("{:?}", result);
 
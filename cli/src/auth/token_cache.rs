@@ -0,0 +1,196 @@
+//! Access token cache shared between CLI invocations.
+//!
+//! Access tokens are cached in the state directory until shortly before they
+//! expire, to avoid contacting the OIDC server for every command. Cache
+//! entries are keyed by a hash of the API URI and refresh token, so the
+//! refresh token itself is never written to the cache.
+
+use std::collections::HashMap;
+use std::fs::DirBuilder;
+use std::io::Write;
+#[cfg(unix)]
+use std::os::unix::fs::DirBuilderExt;
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Duration, Utc};
+pub use phylum_api::Token;
+use phylum_types::types::auth::{AccessToken, RefreshToken};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tempfile::NamedTempFile;
+
+use crate::auth::{is_locksmith_token, jwt, renew_access_token};
+use crate::config::{parse_config, ConnectionInfo};
+use crate::dirs;
+
+/// Minimum remaining lifetime of cached tokens used for API requests.
+pub const REQUEST_MIN_LIFETIME_SECS: i64 = 60;
+
+/// Minimum remaining lifetime of cached tokens printed by `auth token
+/// --bearer`.
+///
+/// These tokens are passed to other tools, so they need to stay valid for
+/// longer than a single request.
+pub const BEARER_MIN_LIFETIME_SECS: i64 = 300;
+
+/// Get an access token, using the cache if possible.
+///
+/// Cached tokens are only used if they are valid for at least
+/// `min_lifetime_secs`, otherwise a new token is requested.
+pub async fn access_token(
+    refresh_token: &RefreshToken,
+    ignore_certs: bool,
+    connection: &ConnectionInfo,
+    min_lifetime_secs: i64,
+) -> Result<Token> {
+    // Locksmith tokens are their own access tokens.
+    if is_locksmith_token(refresh_token) {
+        return Ok(Token { access_token: AccessToken::new(refresh_token), expires_at: None });
+    }
+
+    let key = cache_key(refresh_token, &connection.uri);
+    let path = cache_path();
+
+    let cached = path.as_deref().ok().and_then(|path| load(path, &key, min_lifetime_secs));
+    if let Some(token) = cached {
+        log::debug!("Using cached access token");
+        return Ok(token);
+    }
+
+    let access_token = renew_access_token(refresh_token, ignore_certs, connection).await?;
+    let expires_at = jwt::expiry(access_token.as_str()).ok().flatten();
+    let token = Token { access_token, expires_at };
+
+    // Tokens without expiration time cannot be invalidated, so they're never
+    // cached.
+    if token.expires_at.is_some() {
+        if let Err(err) = path.and_then(|path| store(&path, key, &token)) {
+            log::debug!("Could not cache access token: {err:?}");
+        }
+    }
+
+    Ok(token)
+}
+
+/// Location of the access token cache.
+fn cache_path() -> Result<PathBuf> {
+    Ok(dirs::state_dir()?.join("phylum").join("tokens.yaml"))
+}
+
+/// Cache key for a refresh token.
+fn cache_key(refresh_token: &RefreshToken, api_uri: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(api_uri.as_bytes());
+    hasher.update([0]);
+    hasher.update(refresh_token.as_str().as_bytes());
+    format!("{:x}", hasher.finalize())
+}
+
+/// Load a token valid for at least `min_lifetime_secs` from the cache.
+fn load(path: &Path, key: &str, min_lifetime_secs: i64) -> Option<Token> {
+    let mut cache: TokenCache = parse_config(path).ok()?;
+    let entry = cache.tokens.remove(key)?;
+    if entry.expires_at - Duration::seconds(min_lifetime_secs) <= Utc::now() {
+        return None;
+    }
+
+    Some(Token {
+        access_token: AccessToken::new(entry.access_token),
+        expires_at: Some(entry.expires_at),
+    })
+}
+
+/// Add a token to the cache, removing all expired tokens.
+fn store(path: &Path, key: String, token: &Token) -> Result<()> {
+    let Some(expires_at) = token.expires_at else { return Ok(()) };
+
+    let mut cache: TokenCache = parse_config(path).unwrap_or_default();
+    let now = Utc::now();
+    cache.tokens.retain(|_, entry| entry.expires_at > now);
+
+    let access_token = token.access_token.as_str().to_owned();
+    cache.tokens.insert(key, CacheEntry { access_token, expires_at });
+
+    write_cache(path, &cache)
+}
+
+/// Atomically replace the cache file.
+///
+/// Concurrent CLI invocations can update the cache at the same time, so every
+/// write uses a unique temporary file.
+fn write_cache(path: &Path, cache: &TokenCache) -> Result<()> {
+    let yaml = serde_yaml::to_string(cache)?;
+
+    let dir = path.parent().ok_or_else(|| anyhow!("token cache path is a directory"))?;
+    let mut builder = DirBuilder::new();
+    builder.recursive(true);
+    #[cfg(unix)]
+    builder.mode(0o700);
+    builder.create(dir)?;
+
+    // Temporary files are only accessible by the current user.
+    let mut file = NamedTempFile::new_in(dir)?;
+    file.write_all(yaml.as_bytes())?;
+    file.persist(path)?;
+
+    Ok(())
+}
+
+/// Persisted access tokens.
+#[derive(Serialize, Deserialize, Default, Debug)]
+struct TokenCache {
+    tokens: HashMap<String, CacheEntry>,
+}
+
+/// Persisted access token.
+#[derive(Serialize, Deserialize, Debug)]
+struct CacheEntry {
+    access_token: String,
+    expires_at: DateTime<Utc>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cached_tokens() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let path = tempdir.path().join("tokens.yaml");
+
+        let token = |expires_in| Token {
+            access_token: AccessToken::new("token"),
+            expires_at: Some(Utc::now() + Duration::seconds(expires_in)),
+        };
+
+        store(&path, "fresh".into(), &token(3600)).unwrap();
+        store(&path, "stale".into(), &token(30)).unwrap();
+        store(&path, "expired".into(), &token(-30)).unwrap();
+
+        let min_lifetime = REQUEST_MIN_LIFETIME_SECS;
+        assert_eq!(load(&path, "fresh", min_lifetime).unwrap().access_token.as_str(), "token");
+        assert!(load(&path, "stale", min_lifetime).is_none());
+        assert!(load(&path, "missing", min_lifetime).is_none());
+
+        // Bearer tokens require a longer remaining lifetime.
+        store(&path, "short".into(), &token(120)).unwrap();
+        assert!(load(&path, "short", min_lifetime).is_some());
+        assert!(load(&path, "short", BEARER_MIN_LIFETIME_SECS).is_none());
+
+        // Expired tokens are removed with the next write.
+        store(&path, "fresh".into(), &token(3600)).unwrap();
+        let cache: TokenCache = parse_config(&path).unwrap();
+        assert_eq!(cache.tokens.len(), 3);
+
+        // No temporary files are left behind.
+        assert_eq!(std::fs::read_dir(tempdir.path()).unwrap().count(), 1);
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+    }
+}
@@ -0,0 +1,183 @@
+//! Baselines of accepted policy rejections.
+//!
+//! A baseline is a snapshot of the policy rejections present at one point in
+//! time. Rejections in the baseline are treated as suppressed, so analysis only
+//! fails on newly introduced rejections.
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::types::{PolicyEvaluationResponseRaw, PolicyRejection};
+
+/// Current version of the baseline file format.
+const VERSION: u32 = 1;
+
+/// Snapshot of accepted policy rejections.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug)]
+pub struct Baseline {
+    version: u32,
+    rejections: BTreeSet<BaselineEntry>,
+}
+
+/// Rejection of a single package accepted by the baseline.
+#[derive(Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Clone, Debug)]
+struct BaselineEntry {
+    purl: String,
+    source_type: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    tag: Option<String>,
+    title: String,
+}
+
+impl BaselineEntry {
+    fn new(purl: &str, rejection: &PolicyRejection) -> Self {
+        Self {
+            purl: purl.into(),
+            source_type: rejection.source.source_type.clone(),
+            tag: rejection.source.tag.clone(),
+            title: rejection.title.clone(),
+        }
+    }
+
+    /// Check if this entry matches a package's rejection.
+    ///
+    /// Tagged rejections are matched by their tag, to allow for changes to the
+    /// rejection's title.
+    fn matches(&self, purl: &str, rejection: &PolicyRejection) -> bool {
+        let source = &rejection.source;
+        self.purl == purl
+            && self.source_type == source.source_type
+            && match (&self.tag, &source.tag) {
+                (Some(tag), Some(other)) => tag == other,
+                _ => self.title == rejection.title,
+            }
+    }
+}
+
+impl Baseline {
+    /// Create a baseline from all unsuppressed rejections.
+    pub fn from_status(status: &PolicyEvaluationResponseRaw) -> Self {
+        let rejections = status
+            .dependencies
+            .iter()
+            .flat_map(|dependency| {
+                dependency
+                    .rejections
+                    .iter()
+                    .filter(|rejection| !rejection.suppressed)
+                    .map(|rejection| BaselineEntry::new(&dependency.purl, rejection))
+            })
+            .collect();
+
+        Self { version: VERSION, rejections }
+    }
+
+    /// Load a baseline from a file.
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Could not read baseline {:?}", path.display()))?;
+        serde_json::from_str(&content)
+            .with_context(|| format!("Invalid baseline {:?}", path.display()))
+    }
+
+    /// Write the baseline to a file.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let mut content = serde_json::to_string_pretty(self)?;
+        content.push('\n');
+        fs::write(path, content)
+            .with_context(|| format!("Could not write baseline {:?}", path.display()))
+    }
+
+    /// Number of rejections in the baseline.
+    pub fn len(&self) -> usize {
+        self.rejections.len()
+    }
+
+    /// Check if the baseline contains no rejections.
+    pub fn is_empty(&self) -> bool {
+        self.rejections.is_empty()
+    }
+
+    /// Suppress all rejections contained in the baseline.
+    ///
+    /// Returns the number of suppressed rejections.
+    pub fn apply(&self, status: &mut PolicyEvaluationResponseRaw) -> usize {
+        status.suppress_rejections(|dependency, rejection| {
+            self.rejections.iter().any(|entry| entry.matches(&dependency.purl, rejection))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{EvaluatedDependency, RejectionSource, RiskLevel};
+
+    fn rejection(tag: Option<&str>, title: &str) -> PolicyRejection {
+        PolicyRejection {
+            title: title.into(),
+            source: RejectionSource {
+                source_type: "vulnerability".into(),
+                tag: tag.map(String::from),
+                domain: None,
+                severity: Some(RiskLevel::High),
+                description: None,
+                reason: None,
+            },
+            suppressed: false,
+        }
+    }
+
+    fn status(rejections: Vec<PolicyRejection>) -> PolicyEvaluationResponseRaw {
+        PolicyEvaluationResponseRaw {
+            is_failure: true,
+            incomplete_packages_count: 0,
+            help: String::new(),
+            dependencies: vec![EvaluatedDependency {
+                purl: "pkg:npm/lodash@4.17.20".into(),
+                registry: "npm".into(),
+                name: "lodash".into(),
+                version: "4.17.20".into(),
+                rejections,
+            }],
+            job_link: None,
+        }
+    }
+
+    #[test]
+    fn existing_rejections_pass() {
+        let baseline = Baseline::from_status(&status(vec![
+            rejection(Some("CVE-2021-1"), "Old title"),
+            rejection(None, "Untagged"),
+        ]));
+        assert_eq!(baseline.len(), 2);
+
+        let mut current =
+            status(vec![rejection(Some("CVE-2021-1"), "New title"), rejection(None, "Untagged")]);
+        assert_eq!(baseline.apply(&mut current), 2);
+        assert!(!current.is_failure);
+    }
+
+    #[test]
+    fn new_rejections_fail() {
+        let baseline = Baseline::from_status(&status(vec![rejection(Some("CVE-2021-1"), "A")]));
+
+        let mut current =
+            status(vec![rejection(Some("CVE-2021-1"), "A"), rejection(Some("CVE-2022-2"), "B")]);
+        assert_eq!(baseline.apply(&mut current), 1);
+        assert!(current.is_failure);
+        assert!(!current.dependencies[0].rejections[1].suppressed);
+    }
+
+    #[test]
+    fn roundtrip() {
+        let baseline = Baseline::from_status(&status(vec![rejection(None, "Untagged")]));
+        let json = serde_json::to_string(&baseline).unwrap();
+        assert_eq!(serde_json::from_str::<Baseline>(&json).unwrap(), baseline);
+        assert!(json.contains(r#""version":1"#));
+    }
+}
@@ -1,10 +1,9 @@
-use std::fmt::Display;
+use std::env;
 use std::time::UNIX_EPOCH;
 
 use anyhow::{Context, Result};
 #[cfg(feature = "selfmanage")]
 use clap::ArgMatches;
-use env_logger::Env;
 use log::LevelFilter;
 use phylum_cli::api::PhylumApi;
 #[cfg(feature = "extensions")]
@@ -14,12 +13,15 @@ use phylum_cli::commands::sandbox;
 #[cfg(feature = "selfmanage")]
 use phylum_cli::commands::uninstall;
 use phylum_cli::commands::{
-    auth, find_dependency_files, group, init, jobs, org, packages, parse, project, status,
-    CommandResult, ExitCode,
+    self, auth, baseline, check_lock, find_dependency_files, group, init, jobs, lint, org,
+    packages, parse, project, status, suppressions, verify_installed, CommandResult, ExitCode,
 };
 use phylum_cli::config::{self, Config};
+use phylum_cli::error::{self, ErrorFormat};
+use phylum_cli::logging::LogFormat;
+use phylum_cli::progress::{self, ProgressMode};
 use phylum_cli::spinner::Spinner;
-use phylum_cli::{print, print_user_failure, print_user_success, update};
+use phylum_cli::{logging, print, print_user_success, update};
 
 const LICENSE_BLURB: &str = r#"
 Copyright (C) 2022  Phylum, Inc.
@@ -27,12 +29,6 @@ License GPLv3+: GNU GPL version 3 or later <https://gnu.org/licenses/gpl.html>
 This is free software: you are free to change and redistribute it.
 There is NO WARRANTY, to the extent permitted by law."#;
 
-/// Print an error to the user before exiting with the passed exit code.
-fn exit_fail(message: impl Display, exit_code: ExitCode) -> ! {
-    print_user_failure!("Error: {}", message);
-    exit_code.exit()
-}
-
 /// Construct an instance of `PhylumApi` given configuration, optional timeout,
 /// and whether we need API to ignore certificates.
 async fn api_factory(config: Config, timeout: Option<u64>) -> Result<PhylumApi> {
@@ -66,7 +62,7 @@ async fn check_for_updates(config: &mut Config) -> Result<()> {
     config.last_update = Some(now);
     config.save().unwrap_or_else(|e| log::error!("Failed to save config: {}", e));
 
-    if update::needs_update(false).await {
+    if update::needs_update(false, &config.connection).await {
         print::print_update_message();
     }
 
@@ -82,7 +78,20 @@ async fn handle_commands() -> CommandResult {
     // Required for printing help messages since `get_matches()` consumes `App`
     let app_helper = &mut app.clone();
     let ver = app.get_version().unwrap().to_owned();
-    let matches = app.get_matches();
+    let matches = match app.try_get_matches() {
+        Ok(matches) => matches,
+        Err(err) => {
+            error::set_format(ErrorFormat::from_raw_args(env::args_os()));
+            error::exit_usage(err);
+        },
+    };
+
+    error::set_format(ErrorFormat::from_arg(
+        matches.get_one::<String>("error-format").map(String::as_str),
+    ));
+    progress::set_mode(ProgressMode::from_arg(
+        matches.get_one::<String>("progress").map(String::as_str),
+    ));
 
     // Set the log level based on CLI arguments.
     let log_level = match (matches.get_count("verbose"), matches.get_count("quiet")) {
@@ -95,21 +104,21 @@ async fn handle_commands() -> CommandResult {
         (0, 0) => None,
     };
 
-    // Initialize the logger at the desired level.
-    match log_level {
-        Some(level) => env_logger::Builder::new().filter_level(level).init(),
-        None => env_logger::Builder::from_env(Env::default().default_filter_or("warn")).init(),
-    };
+    // Log to stderr until the configured log format and file are known.
+    logging::init(log_level, LogFormat::default(), None)?;
 
     let mut config = config::load_config(&matches)?;
 
-    config.set_ignore_certs_cli(matches.get_flag("no-check-certificate"));
+    // Reconfigure the logger with the desired format and file.
+    let log_format = config.log.format.unwrap_or_default();
+    logging::init(log_level, log_format, config.log.file.as_deref())?;
+
     if config.ignore_certs() {
         log::warn!("Ignoring TLS server certificate verification per user request.");
     }
 
     // We initialize these value here, for later use by the PhylumApi object.
-    let timeout = matches.get_one::<String>("timeout").and_then(|t| t.parse::<u64>().ok());
+    let timeout = config.timeout();
 
     let (subcommand, sub_matches) = matches.subcommand().unwrap();
 
@@ -133,6 +142,10 @@ async fn handle_commands() -> CommandResult {
         "parse" => parse::handle_parse(sub_matches),
         #[cfg(unix)]
         "parse-sandboxed" => parse::handle_parse_sandboxed(sub_matches),
+        "check-lock" => check_lock::handle_check_lock(sub_matches),
+        "verify-installed" => verify_installed::handle_verify_installed(sub_matches),
+        "lint" => lint::handle_lint(sub_matches),
+        "suppressions" => suppressions::handle_suppressions(sub_matches),
         "ping" => handle_ping(Spinner::wrap(api).await?).await,
         "project" => {
             project::handle_project(&Spinner::wrap(api).await?, app_helper, sub_matches, config)
@@ -142,23 +155,30 @@ async fn handle_commands() -> CommandResult {
         "history" => jobs::handle_history(&Spinner::wrap(api).await?, sub_matches, config).await,
         "group" => group::handle_group(&Spinner::wrap(api).await?, sub_matches, config).await,
         "analyze" => jobs::handle_analyze(&Spinner::wrap(api).await?, sub_matches, config).await,
+        "baseline" => {
+            baseline::handle_baseline(&Spinner::wrap(api).await?, sub_matches, config).await
+        },
         "init" => init::handle_init(&Spinner::wrap(api).await?, sub_matches, config).await,
         "status" => status::handle_status(sub_matches).await,
         "org" => org::handle_org(&Spinner::wrap(api).await?, sub_matches, config).await,
+        "config" => commands::config::handle_config(sub_matches, config),
 
         #[cfg(feature = "selfmanage")]
         "uninstall" => uninstall::handle_uninstall(sub_matches),
         #[cfg(feature = "selfmanage")]
-        "update" => handle_update(sub_matches, config.ignore_certs()).await,
+        "update" => handle_update(sub_matches, &config).await,
 
         #[cfg(feature = "extensions")]
-        "extension" => extensions::handle_extensions(Box::pin(api), sub_matches, app_helper).await,
+        "extension" => {
+            extensions::handle_extensions(Box::pin(api), sub_matches, app_helper, &config).await
+        },
         #[cfg(unix)]
         "sandbox" => sandbox::handle_sandbox(sub_matches).await,
         "find-dependency-files" => find_dependency_files::handle_command(),
         #[cfg(feature = "extensions")]
         extension_subcmd => {
-            extensions::handle_run_extension(Box::pin(api), extension_subcmd, sub_matches).await
+            extensions::handle_run_extension(Box::pin(api), extension_subcmd, sub_matches, &config)
+                .await
         },
         #[cfg(not(feature = "extensions"))]
         _ => unreachable!(),
@@ -171,8 +191,9 @@ async fn handle_ping(api: PhylumApi) -> CommandResult {
 }
 
 #[cfg(feature = "selfmanage")]
-async fn handle_update(matches: &ArgMatches, ignore_certs: bool) -> CommandResult {
-    let res = update::do_update(matches.get_flag("prerelease"), ignore_certs).await;
+async fn handle_update(matches: &ArgMatches, config: &Config) -> CommandResult {
+    let prerelease = matches.get_flag("prerelease");
+    let res = update::do_update(prerelease, &config.connection, config.ignore_certs()).await;
     let message = res?;
     print_user_success!("{}", message);
     Ok(ExitCode::Ok)
@@ -185,8 +206,5 @@ fn handle_version(app_name: &str, ver: &str) -> CommandResult {
 
 #[tokio::main(flavor = "current_thread")]
 async fn main() {
-    match handle_commands().await {
-        Ok(code) => code.exit(),
-        Err(error) => exit_fail(format!("{error:?}"), ExitCode::Generic),
-    }
+    error::exit(handle_commands().await)
 }
@@ -17,13 +17,13 @@ use crate::{auth, print_user_failure, print_user_success, print_user_warning};
 /// Register a user. Opens a browser, and redirects the user to the oauth server
 /// registration page
 async fn handle_auth_register(mut config: Config, matches: &ArgMatches) -> Result<()> {
-    let api_uri = &config.connection.uri;
+    let connection = &config.connection;
     let ignore_certs = config.ignore_certs();
     config.auth_info = PhylumApi::register(
         config.auth_info,
         matches.get_one("token-name").cloned(),
         ignore_certs,
-        api_uri,
+        connection,
     )
     .await?;
     config.save().map_err(|error| anyhow!(error))?;
@@ -33,16 +33,27 @@ async fn handle_auth_register(mut config: Config, matches: &ArgMatches) -> Resul
 /// Login a user. Opens a browser, and redirects the user to the oauth server
 /// login page
 async fn handle_auth_login(mut config: Config, matches: &ArgMatches) -> Result<()> {
-    let api_uri = &config.connection.uri;
+    let connection = &config.connection;
     let ignore_certs = config.ignore_certs();
-    config.auth_info = PhylumApi::login(
-        config.auth_info,
-        matches.get_one("token-name").cloned(),
-        ignore_certs,
-        api_uri,
-        matches.get_flag("reauth"),
-    )
-    .await?;
+    // CI credentials are only valid for the current job, so nothing is saved.
+    if matches.get_flag("ci") {
+        PhylumApi::login_ci(ignore_certs, connection).await?;
+        print_user_warning!("Set `PHYLUM_CI_AUTH=true` to authenticate other commands in this job");
+        return Ok(());
+    }
+
+    config.auth_info = if matches.get_flag("device") {
+        PhylumApi::login_device(config.auth_info, ignore_certs, connection).await?
+    } else {
+        PhylumApi::login(
+            config.auth_info,
+            matches.get_one("token-name").cloned(),
+            ignore_certs,
+            connection,
+            matches.get_flag("reauth"),
+        )
+        .await?
+    };
     config.save().map_err(|error| anyhow!(error))?;
     Ok(())
 }
@@ -50,6 +61,7 @@ async fn handle_auth_login(mut config: Config, matches: &ArgMatches) -> Result<(
 /// Display the current authentication status to the user.
 pub async fn handle_auth_status(config: Config, timeout: Option<u64>) -> CommandResult {
     let auth_type = match config.auth_info.offline_access() {
+        _ if config.auth_info.ci() => "CI identity token",
         Some(token) if is_locksmith_token(token) => "API key",
         Some(_) => "OpenID Connect",
         None => {
@@ -81,6 +93,18 @@ pub async fn handle_auth_status(config: Config, timeout: Option<u64>) -> Command
 
 /// Display the current authentication token to the user, if one exists.
 pub async fn handle_auth_token(config: &Config, matches: &clap::ArgMatches) -> CommandResult {
+    // CI identity tokens only provide short-lived access tokens.
+    if config.auth_info.ci() {
+        if !matches.get_flag("bearer") {
+            print_user_failure!("CI authentication has no refresh token, use `--bearer` instead");
+            return Ok(ExitCode::Generic);
+        }
+
+        let token = auth::ci::access_token(config.ignore_certs(), &config.connection).await?;
+        println!("{}", token.access_token);
+        return Ok(ExitCode::Ok);
+    }
+
     let refresh_token = match config.auth_info.offline_access() {
         Some(refresh_token) => refresh_token,
         None => {
@@ -92,10 +116,14 @@ pub async fn handle_auth_token(config: &Config, matches: &clap::ArgMatches) -> C
     };
 
     if matches.get_flag("bearer") {
-        let api_uri = &config.connection.uri;
-        let access_token =
-            auth::renew_access_token(refresh_token, config.ignore_certs(), api_uri).await?;
-        println!("{}", access_token);
+        // Ensure the token stays valid long enough to be used by other tools.
+        let connection = &config.connection;
+        let min_lifetime = auth::token_cache::BEARER_MIN_LIFETIME_SECS;
+        let ignore_certs = config.ignore_certs();
+        let token =
+            auth::token_cache::access_token(refresh_token, ignore_certs, connection, min_lifetime)
+                .await?;
+        println!("{}", token.access_token);
         Ok(ExitCode::Ok)
     } else {
         println!("{refresh_token}");
@@ -129,7 +157,7 @@ pub async fn handle_auth_set_token(
         Some(t) => RefreshToken::new(t),
         None => stdin_read_token().await?,
     };
-    config.auth_info.set_offline_access(offline_access);
+    config.auth_info.set_offline_access(offline_access)?;
     config.save()?;
     Ok(ExitCode::Ok)
 }
@@ -212,7 +240,7 @@ pub async fn handle_auth_create_token(
     let token_name = matches.get_one::<String>("token-name").unwrap().to_string();
     let expiry = matches.get_one::<String>("expiry");
     let ignore_certs = api.config().ignore_certs();
-    let api_uri = &api.config().connection.uri;
+    let connection = &api.config().connection;
 
     // Try to parse expiry.
     let expiry = expiry
@@ -230,9 +258,14 @@ pub async fn handle_auth_create_token(
         },
     };
 
-    let token =
-        auth::handle_auth_flow(AuthAction::Login, Some(token_name), expiry, ignore_certs, api_uri)
-            .await?;
+    let token = auth::handle_auth_flow(
+        AuthAction::Login,
+        Some(token_name),
+        expiry,
+        ignore_certs,
+        connection,
+    )
+    .await?;
 
     eprintln!();
     println!("{token}");
@@ -0,0 +1,24 @@
+//! Subcommand `phylum baseline`.
+
+use clap::ArgMatches;
+
+use crate::api::PhylumApi;
+use crate::commands::jobs::{self, BaselineMode};
+use crate::commands::CommandResult;
+use crate::config::Config;
+
+/// Handle `phylum baseline` subcommand.
+pub async fn handle_baseline(
+    api: &PhylumApi,
+    matches: &ArgMatches,
+    config: Config,
+) -> CommandResult {
+    match matches.subcommand() {
+        Some(("update", matches)) => {
+            let path = matches.get_one::<String>("baseline").unwrap();
+            let baseline = BaselineMode::Update(path.into());
+            jobs::analyze(api, matches, config, Some(baseline)).await
+        },
+        _ => unreachable!("invalid clap configuration"),
+    }
+}
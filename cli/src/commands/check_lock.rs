@@ -0,0 +1,58 @@
+//! Subcommand `phylum check-lock`.
+
+use std::path::PathBuf;
+
+use anyhow::anyhow;
+use clap::ArgMatches;
+use phylum_lockfile::drift::{self, LockfileDrift};
+use phylum_lockfile::DepFiles;
+
+use crate::commands::{CommandResult, ExitCode};
+use crate::format::Format;
+use crate::print_user_warning;
+
+/// Handle `phylum check-lock` subcommand.
+pub fn handle_check_lock(matches: &ArgMatches) -> CommandResult {
+    let pretty_print = !matches.get_flag("json");
+
+    let mut reports = Vec::new();
+    match matches.get_many::<String>("manifest") {
+        // Fail on explicitly specified manifests which cannot be checked.
+        Some(manifests) => {
+            for manifest in manifests {
+                reports.push(drift::check_lockfile_drift(manifest)?);
+            }
+        },
+        // Skip discovered manifests without a lockfile.
+        None => {
+            for manifest in discover_manifests() {
+                match drift::check_lockfile_drift(&manifest) {
+                    Ok(report) => reports.push(report),
+                    Err(err) => print_user_warning!("Skipping {manifest:?}: {err:#}"),
+                }
+            }
+
+            if reports.is_empty() {
+                return Err(anyhow!("No supported manifest with a lockfile found"));
+            }
+        },
+    }
+
+    reports.write_stdout(pretty_print);
+
+    if reports.iter().all(LockfileDrift::is_empty) {
+        Ok(ExitCode::Ok)
+    } else {
+        Ok(ExitCode::LockfileDrift)
+    }
+}
+
+/// Find all manifests in the current directory supporting drift detection.
+fn discover_manifests() -> Vec<PathBuf> {
+    DepFiles::find_at(".")
+        .manifests
+        .into_iter()
+        .map(|(path, _)| path)
+        .filter(|path| drift::is_supported_manifest(path))
+        .collect()
+}
@@ -0,0 +1,267 @@
+//! Subcommand `phylum config`.
+
+use std::path::PathBuf;
+
+use anyhow::{anyhow, Result};
+use clap::ArgMatches;
+use serde::Serialize;
+
+use crate::commands::{CommandResult, ExitCode};
+use crate::config::{
+    Config, ConnectionInfo, Origin, Profile, Setting, API_KEY_SETTING, DEFAULT_PROFILE,
+    PROFILE_SETTING, SETTINGS,
+};
+use crate::format::Format;
+use crate::{print_user_failure, print_user_success, print_user_warning};
+
+/// Placeholder for hidden credentials.
+const REDACTED: &str = "<redacted>";
+
+/// Handle `phylum config` subcommand.
+pub fn handle_config(matches: &ArgMatches, config: Config) -> CommandResult {
+    match matches.subcommand() {
+        Some(("get", matches)) => handle_config_get(matches, &config),
+        Some(("set", matches)) => handle_config_set(matches, config),
+        Some(("unset", matches)) => handle_config_unset(matches, config),
+        Some(("list", matches)) => handle_config_list(matches, &config),
+        Some(("path", _)) => handle_config_path(&config),
+        Some(("profile", matches)) => match matches.subcommand() {
+            Some(("list", matches)) => handle_profile_list(matches, &config),
+            Some(("use", matches)) => handle_profile_use(matches, config),
+            Some(("add", matches)) => handle_profile_add(matches, config),
+            Some(("remove", matches)) => handle_profile_remove(matches, config),
+            _ => unreachable!("invalid clap configuration"),
+        },
+        _ => unreachable!("invalid clap configuration"),
+    }
+}
+
+/// Handle `phylum config get` subcommand.
+fn handle_config_get(matches: &ArgMatches, config: &Config) -> CommandResult {
+    let key = matches.get_one::<String>("key").unwrap();
+
+    match config.get(key)? {
+        Some(value) => {
+            println!("{value}");
+            Ok(ExitCode::Ok)
+        },
+        None => Ok(ExitCode::NotFound),
+    }
+}
+
+/// Handle `phylum config set` subcommand.
+fn handle_config_set(matches: &ArgMatches, mut config: Config) -> CommandResult {
+    let key = matches.get_one::<String>("key").unwrap();
+    let value = matches.get_one::<String>("value").unwrap();
+
+    config.set(key, Some(value))?;
+    save(&config)?;
+
+    print_user_success!("Successfully updated {key:?}");
+    warn_overridden(&config, key)?;
+
+    Ok(ExitCode::Ok)
+}
+
+/// Handle `phylum config unset` subcommand.
+fn handle_config_unset(matches: &ArgMatches, mut config: Config) -> CommandResult {
+    let key = matches.get_one::<String>("key").unwrap();
+
+    config.set(key, None)?;
+    save(&config)?;
+
+    print_user_success!("Successfully reset {key:?} to its default value");
+    warn_overridden(&config, key)?;
+
+    Ok(ExitCode::Ok)
+}
+
+/// Handle `phylum config list` subcommand.
+fn handle_config_list(matches: &ArgMatches, config: &Config) -> CommandResult {
+    let show_origin = matches.get_flag("show-origin");
+
+    let mut settings = Vec::new();
+    for setting in SETTINGS {
+        let mut value = config.get(setting.key)?;
+
+        // Avoid accidentally leaking credentials.
+        if setting.key == API_KEY_SETTING && value.is_some() {
+            value = Some(REDACTED.into());
+        }
+
+        let origin = match show_origin {
+            true => Some(origin(config, setting)?),
+            false => None,
+        };
+
+        settings.push(SettingListEntry { key: setting.key.into(), value, origin });
+    }
+
+    let pretty = !matches.get_flag("json");
+    settings.write_stdout(pretty);
+
+    Ok(ExitCode::Ok)
+}
+
+/// Handle `phylum config path` subcommand.
+fn handle_config_path(config: &Config) -> CommandResult {
+    match &config.path {
+        Some(path) => {
+            println!("{}", path.display());
+            Ok(ExitCode::Ok)
+        },
+        None => {
+            print_user_failure!("Configuration files are disabled");
+            Ok(ExitCode::Generic)
+        },
+    }
+}
+
+/// Describe where a setting's effective value came from.
+fn origin(config: &Config, setting: &Setting) -> Result<String> {
+    let origin = match config.origin(setting.key)? {
+        Origin::Default => "default".into(),
+        Origin::File => match &config.path {
+            Some(path) => format!("file:{}", path.display()),
+            None => "file".into(),
+        },
+        Origin::Env => format!("env:{}", setting.env.unwrap_or_default()),
+        Origin::Flag => format!("flag:--{}", setting.flag.unwrap_or_default()),
+        Origin::CredentialHelper => "credential_helper".into(),
+    };
+    Ok(origin)
+}
+
+/// Warn if a setting written to the configuration file is not in effect.
+fn warn_overridden(config: &Config, key: &str) -> Result<()> {
+    let setting = match SETTINGS.iter().find(|setting| setting.key == key) {
+        Some(setting) => setting,
+        None => return Ok(()),
+    };
+
+    if matches!(config.origin(key)?, Origin::Env | Origin::Flag) {
+        print_user_warning!(
+            "Setting {key:?} is currently overridden by {}",
+            origin(config, setting)?
+        );
+    }
+
+    Ok(())
+}
+
+/// Write the configuration, failing if configuration files are disabled.
+fn save(config: &Config) -> Result<()> {
+    if config.path.is_none() {
+        return Err(anyhow!("Configuration files are disabled"));
+    }
+    config.save()
+}
+
+/// Handle `phylum config profile list` subcommand.
+fn handle_profile_list(matches: &ArgMatches, config: &Config) -> CommandResult {
+    let names = [DEFAULT_PROFILE].into_iter().chain(config.profiles.keys().map(String::as_str));
+    let profiles: Vec<_> = names
+        .filter_map(|name| {
+            let profile = config.profile_settings(name)?;
+            Some(ProfileListEntry {
+                name: name.into(),
+                uri: profile.connection.uri,
+                in_use: name == config.profile(),
+            })
+        })
+        .collect();
+
+    let pretty = !matches.get_flag("json");
+    profiles.write_stdout(pretty);
+
+    Ok(ExitCode::Ok)
+}
+
+/// Handle `phylum config profile use` subcommand.
+fn handle_profile_use(matches: &ArgMatches, mut config: Config) -> CommandResult {
+    let name = matches.get_one::<String>("name").unwrap();
+
+    if !config.has_profile(name) {
+        print_user_failure!("Profile {name:?} does not exist");
+        return Ok(ExitCode::NotFound);
+    }
+
+    config.set_default_profile(name)?;
+    save(&config)?;
+
+    print_user_success!("Successfully set default profile to {name:?}");
+    warn_overridden(&config, PROFILE_SETTING)?;
+
+    Ok(ExitCode::Ok)
+}
+
+/// Handle `phylum config profile add` subcommand.
+fn handle_profile_add(matches: &ArgMatches, mut config: Config) -> CommandResult {
+    let name = matches.get_one::<String>("name").unwrap();
+
+    if config.has_profile(name) {
+        print_user_failure!("Profile {name:?} already exists");
+        return Ok(ExitCode::AlreadyExists);
+    }
+
+    let mut connection = ConnectionInfo::default();
+    if let Some(uri) = matches.get_one::<String>("uri") {
+        connection.uri = uri.clone();
+    }
+    connection.ca_bundle = matches.get_one::<String>("ca-bundle").map(PathBuf::from);
+    connection.proxy = matches.get_one::<String>("proxy").cloned();
+
+    let profile = Profile {
+        connection,
+        ignore_certs: matches.get_flag("no-check-certificate"),
+        ..Profile::default()
+    };
+    config.profiles.insert(name.clone(), profile);
+    save(&config)?;
+
+    print_user_success!("Successfully created profile {name:?}");
+
+    Ok(ExitCode::Ok)
+}
+
+/// Handle `phylum config profile remove` subcommand.
+fn handle_profile_remove(matches: &ArgMatches, mut config: Config) -> CommandResult {
+    let name = matches.get_one::<String>("name").unwrap();
+
+    if name == DEFAULT_PROFILE {
+        print_user_failure!("The {DEFAULT_PROFILE:?} profile cannot be removed");
+        return Ok(ExitCode::Generic);
+    }
+
+    if !config.has_profile(name) {
+        print_user_failure!("Profile {name:?} does not exist");
+        return Ok(ExitCode::NotFound);
+    }
+
+    if config.file_value(PROFILE_SETTING)?.as_deref() == Some(name) {
+        config.set_default_profile(DEFAULT_PROFILE)?;
+    }
+    config.profiles.remove(name);
+    save(&config)?;
+
+    print_user_success!("Successfully removed profile {name:?}");
+
+    Ok(ExitCode::Ok)
+}
+
+/// Output entry in the `phylum config list` subcommand.
+#[derive(Serialize)]
+pub struct SettingListEntry {
+    pub key: String,
+    pub value: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub origin: Option<String>,
+}
+
+/// Output entry in the `phylum config profile list` subcommand.
+#[derive(Serialize)]
+pub struct ProfileListEntry {
+    pub name: String,
+    pub uri: String,
+    pub in_use: bool,
+}
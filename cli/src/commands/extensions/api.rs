@@ -16,6 +16,7 @@ use std::str::FromStr;
 use anyhow::{anyhow, Error, Result};
 use deno_runtime::deno_core::{op2, OpDecl, OpState};
 use deno_runtime::permissions::PermissionsContainer;
+use phylum_api::ApiError;
 use phylum_lockfile::ParsedLockfile;
 use phylum_project::ProjectConfig;
 use phylum_types::types::auth::{AccessToken, RefreshToken};
@@ -24,20 +25,18 @@ use phylum_types::types::package::{PackageDescriptor, PackageDescriptorAndLockfi
 use reqwest::StatusCode;
 use serde::{Deserialize, Serialize};
 
-use crate::api::{PhylumApiError, ResponseError};
-use crate::auth::UserInfo;
+use crate::ci;
 use crate::commands::extensions::state::ExtensionState;
 use crate::commands::parse;
 #[cfg(unix)]
 use crate::commands::ExitCode;
-use crate::config::Config;
 #[cfg(unix)]
 use crate::dirs;
 use crate::permissions::{self, Permission};
 use crate::types::{
     AnalysisPackageDescriptor, ListUserGroupsResponse, Package, PackageSpecifier,
     PackageSubmitResponse, PolicyEvaluationResponse, PolicyEvaluationResponseRaw, ProjectListEntry,
-    PurlWithOrigin,
+    PurlWithOrigin, UserInfo,
 };
 
 /// Package format accepted by extension API.
@@ -187,7 +186,9 @@ async fn analyze(
     };
 
     let api_packages: Vec<_> = packages.into_iter().map(From::from).collect();
-    let job_id = api.submit_request(&api_packages, project, label, group.map(String::from)).await?;
+    let group = group.map(String::from);
+    let metadata = ci::job_metadata();
+    let job_id = api.submit_request(&api_packages, project, label, group, metadata).await?;
 
     Ok(job_id)
 }
@@ -229,33 +230,15 @@ async fn get_user_info(op_state: Rc<RefCell<OpState>>) -> Result<UserInfo> {
     api.user_info().await.map_err(Error::from)
 }
 
-/// Retrieve the refresh token.
-async fn refresh_token(config: &Config) -> Result<RefreshToken> {
-    config
-        .auth_info
-        .offline_access()
-        .cloned()
-        .ok_or_else(|| anyhow!("User is not currently authenticated"))
-}
-
 /// Retrieve the access token.
 /// Equivalent to `phylum auth token --bearer`.
 #[op2(async)]
 #[serde]
-async fn get_access_token(
-    op_state: Rc<RefCell<OpState>>,
-    ignore_certs: bool,
-) -> Result<AccessToken> {
+async fn get_access_token(op_state: Rc<RefCell<OpState>>) -> Result<AccessToken> {
     let state = ExtensionState::from(op_state);
     let api = state.api().await?;
-    let config = api.config();
 
-    let refresh_token = refresh_token(config).await?;
-
-    let access_token =
-        crate::auth::renew_access_token(&refresh_token, ignore_certs, &config.connection.uri)
-            .await?;
-    Ok(access_token)
+    api.access_token().await?.ok_or_else(|| anyhow!("User is not currently authenticated"))
 }
 
 /// Retrieve the refresh token.
@@ -266,9 +249,19 @@ async fn get_access_token(
 async fn get_refresh_token(op_state: Rc<RefCell<OpState>>) -> Result<RefreshToken> {
     let state = ExtensionState::from(op_state);
     let api = state.api().await?;
-    let config = api.config();
+    let auth_info = &api.config().auth_info;
+
+    // CI identity tokens only provide short-lived access tokens.
+    if auth_info.ci() {
+        return Err(anyhow!(
+            "CI authentication has no refresh token, use `getAccessToken` instead"
+        ));
+    }
 
-    refresh_token(config).await
+    auth_info
+        .offline_access()
+        .cloned()
+        .ok_or_else(|| anyhow!("User is not currently authenticated"))
 }
 
 /// Retrieve a job's status.
@@ -368,7 +361,7 @@ async fn create_project(
     // Retrieve the id if the project already exists, otherwise return the id or the
     // error.
     match api.create_project(&name, organization.clone(), group.clone(), repository_url).await {
-        Err(PhylumApiError::Response(ResponseError { code: StatusCode::CONFLICT, .. })) => api
+        Err(phylum_api::Error::Api(ApiError { status: StatusCode::CONFLICT, .. })) => api
             .get_project_id(&name, organization.as_deref(), group.as_deref())
             .await
             .map(|id| CreatedProject { id, status: CreatedProjectStatus::Exists })
@@ -14,6 +14,7 @@ use walkdir::WalkDir;
 
 use crate::api::PhylumApi;
 use crate::commands::CommandResult;
+use crate::config::Config;
 use crate::permissions::Permissions;
 use crate::{deno, dirs, fs_compare};
 
@@ -168,13 +169,14 @@ impl Extension {
     pub async fn run(
         self,
         api: BoxFuture<'static, Result<PhylumApi>>,
+        config: &Config,
         args: Vec<String>,
     ) -> CommandResult {
         // Disable logging for running extensions.
         log::set_max_level(LevelFilter::Off);
 
         // Execute Deno extension.
-        deno::run(api, self, args).await
+        deno::run(api, config, self, args).await
     }
 }
 
@@ -16,6 +16,7 @@ use log::error;
 use crate::api::PhylumApi;
 use crate::commands::extensions::extension::{Extension, ExtensionManifest};
 use crate::commands::{CommandResult, ExitCode};
+use crate::config::Config;
 use crate::permissions::Permission;
 use crate::print::print_sc_help;
 use crate::{app, print_user_success, print_user_warning};
@@ -106,6 +107,7 @@ pub async fn handle_extensions(
     api: BoxFuture<'static, Result<PhylumApi>>,
     matches: &ArgMatches,
     app: &mut Command,
+    config: &Config,
 ) -> CommandResult {
     match matches.subcommand() {
         Some(("install", matches)) => {
@@ -120,7 +122,7 @@ pub async fn handle_extensions(
         Some(("uninstall", matches)) => {
             handle_uninstall_extension(matches.get_one::<String>("NAME").unwrap()).await
         },
-        Some(("run", matches)) => handle_run_extension_from_path(app, api, matches).await,
+        Some(("run", matches)) => handle_run_extension_from_path(app, api, matches, config).await,
         Some(("new", matches)) => {
             handle_create_extension(matches.get_one::<String>("PATH").unwrap()).await
         },
@@ -136,12 +138,13 @@ pub async fn handle_run_extension(
     api: BoxFuture<'static, Result<PhylumApi>>,
     name: &str,
     args: &ArgMatches,
+    config: &Config,
 ) -> CommandResult {
     let options = args.get_many("OPTIONS").map(|options| options.cloned().collect());
 
     let extension = Extension::load(name)?;
 
-    extension.run(api, options.unwrap_or_default()).await
+    extension.run(api, config, options.unwrap_or_default()).await
 }
 
 /// Handle the `extension run <PATH>` command path.
@@ -151,6 +154,7 @@ pub async fn handle_run_extension_from_path(
     app: &mut Command,
     api: BoxFuture<'static, Result<PhylumApi>>,
     matches: &ArgMatches,
+    config: &Config,
 ) -> CommandResult {
     let path = matches.get_one::<String>("PATH").unwrap();
     let options = matches.get_many("OPTIONS").map(|options| options.cloned().collect());
@@ -168,7 +172,7 @@ pub async fn handle_run_extension_from_path(
         ask_permissions(&extension)?;
     }
 
-    extension.run(api, options.unwrap_or_default()).await
+    extension.run(api, config, options.unwrap_or_default()).await
 }
 
 /// Handle the `extension install` subcommand path.
@@ -3,10 +3,11 @@
 use std::cmp::Ordering;
 
 use clap::ArgMatches;
+use phylum_api::ApiError;
 use reqwest::StatusCode;
 use serde::Serialize;
 
-use crate::api::{PhylumApi, PhylumApiError, ResponseError};
+use crate::api::PhylumApi;
 use crate::commands::{CommandResult, ExitCode};
 use crate::config::Config;
 use crate::format::Format;
@@ -52,11 +53,11 @@ pub async fn handle_group_create(
             print_user_success!("Successfully created group {}", group_name);
             Ok(ExitCode::Ok)
         },
-        Err(PhylumApiError::Response(ResponseError { code: StatusCode::CONFLICT, .. })) => {
+        Err(phylum_api::Error::Api(ApiError { status: StatusCode::CONFLICT, .. })) => {
             print_user_failure!("Group '{}' already exists", group_name);
             Ok(ExitCode::AlreadyExists)
         },
-        Err(PhylumApiError::Response(ResponseError { code: StatusCode::FORBIDDEN, .. }))
+        Err(phylum_api::Error::Api(ApiError { status: StatusCode::FORBIDDEN, .. }))
             if org.is_some() =>
         {
             print_user_failure!("Authorization failed, only organization admins can create groups");
@@ -76,9 +77,8 @@ pub async fn handle_group_delete(
 
     if let Some(org) = config.org() {
         let response = api.org_delete_group(org, group_name).await;
-        if let Err(PhylumApiError::Response(ResponseError {
-            code: StatusCode::FORBIDDEN, ..
-        })) = response
+        if let Err(phylum_api::Error::Api(ApiError { status: StatusCode::FORBIDDEN, .. })) =
+            response
         {
             print_user_failure!("Authorization failed, only organization admins can delete groups");
             return Ok(ExitCode::NotAuthenticated);
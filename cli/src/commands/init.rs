@@ -8,11 +8,12 @@ use clap::parser::ValuesRef;
 use clap::ArgMatches;
 use dialoguer::{Confirm, FuzzySelect, Input, MultiSelect};
 use git2::Repository;
+use phylum_api::ApiError;
 use phylum_lockfile::LockfileFormat;
 use phylum_project::{DepfileConfig, ProjectConfig, PROJ_CONF_FILE};
 use reqwest::StatusCode;
 
-use crate::api::{PhylumApi, PhylumApiError, ResponseError};
+use crate::api::PhylumApi;
 use crate::commands::{project, CommandResult, ExitCode};
 use crate::config::{self, Config};
 use crate::{print_user_success, print_user_warning};
@@ -70,7 +71,7 @@ pub async fn handle_init(api: &PhylumApi, matches: &ArgMatches, config: Config)
 
     let mut project_config = match result {
         // If project already exists, try looking it up to link to it.
-        Err(PhylumApiError::Response(ResponseError { code: StatusCode::CONFLICT, .. })) => {
+        Err(phylum_api::Error::Api(ApiError { status: StatusCode::CONFLICT, .. })) => {
             let uuid = api
                 .get_project_id(&project, org, group.as_deref())
                 .await
@@ -1,11 +1,14 @@
 use std::fs;
 #[cfg(feature = "vulnreach")]
 use std::io;
+use std::path::PathBuf;
 use std::str::FromStr;
 
 use anyhow::{anyhow, Context, Result};
+use chrono::Local;
 use console::style;
 use log::debug;
+use phylum_lockfile::confusion::{self, ConfusionFinding};
 use phylum_lockfile::ParseError;
 use phylum_project::DepfileConfig;
 use phylum_types::types::common::{JobId, ProjectId};
@@ -17,25 +20,56 @@ use vulnreach_types::{Job, JobPackage};
 use crate::api::PhylumApi;
 #[cfg(feature = "vulnreach")]
 use crate::auth::jwt::RealmRole;
+use crate::baseline::Baseline;
+use crate::ci::{self, CiProvider};
 use crate::commands::{parse, CommandResult, ExitCode};
 use crate::config::{self, Config};
-use crate::format::Format;
-use crate::types::AnalysisPackageDescriptor;
+use crate::format::{Format, OutputFormat};
+use crate::progress::{self, Event};
+use crate::report::{annotations, AnalysisReport, Depfile};
+use crate::suppressions::Suppressions;
+use crate::types::{self, AnalysisPackageDescriptor, PolicyEvaluationResponseRaw};
 #[cfg(feature = "vulnreach")]
 use crate::vulnreach;
 use crate::{print_user_failure, print_user_success, print_user_warning};
 
+/// Local analysis results reported together with the job status.
+#[derive(Default)]
+pub struct LocalResults {
+    /// Dependency confusion findings, reported as policy failures.
+    pub dependency_confusion: Vec<ConfusionFinding>,
+    /// Submitted dependency files, used to locate findings.
+    pub depfiles: Vec<Depfile>,
+    /// Emit native annotations when running in CI.
+    pub annotations: bool,
+    /// Path for writing a GitLab code quality report.
+    pub code_quality_report: Option<PathBuf>,
+    /// Baseline of accepted policy rejections.
+    pub baseline: Option<BaselineMode>,
+    /// Local suppressions of policy rejections.
+    pub suppressions: Suppressions,
+}
+
+/// Baseline handling for analysis results.
+pub enum BaselineMode {
+    /// Suppress rejections in the baseline, creating it if it does not exist.
+    Apply(PathBuf),
+    /// Replace the baseline with the current rejections.
+    Update(PathBuf),
+}
+
 /// Output analysis job results.
 pub async fn print_job_status(
     api: &PhylumApi,
     job_id: &JobId,
     ignored_packages: impl Into<Vec<PackageDescriptor>>,
-    pretty: bool,
+    local_results: LocalResults,
+    format: OutputFormat,
 ) -> CommandResult {
     let response = api.get_job_status_raw(job_id, ignored_packages).await;
 
     // Provide nicer messages for specific errors.
-    let status = match response {
+    let mut status = match response {
         Ok(status) => status,
         Err(err) if err.status() == Some(StatusCode::NOT_FOUND) => {
             print_user_warning!("No results found for JobId {job_id}.");
@@ -44,15 +78,94 @@ pub async fn print_job_status(
         Err(err) => return Err(err.into()),
     };
 
-    status.write_stdout(pretty);
+    // Apply suppressions before the baseline, to keep them out of new baselines.
+    let today = Local::now().date_naive();
+    for suppression in local_results.suppressions.expired(today) {
+        print_user_warning!(
+            "Ignoring expired suppression of {} (expired {})",
+            suppression.description(),
+            suppression.expires.map(|date| date.to_string()).unwrap_or_default(),
+        );
+    }
+    local_results.suppressions.apply(&mut status, today);
+
+    if let Some(mode) = &local_results.baseline {
+        apply_baseline(&mut status, mode, format == OutputFormat::Pretty)?;
+    }
+
+    status.is_failure |= !local_results.dependency_confusion.is_empty();
+
+    let report = AnalysisReport {
+        status,
+        depfiles: local_results.depfiles,
+        dependency_confusion: local_results.dependency_confusion,
+    };
+    report.write_format(format)?;
+
+    if let Some(provider) = CiProvider::detect().filter(|_| local_results.annotations) {
+        let stdout = format == OutputFormat::Pretty;
+        if let Err(err) = annotations::emit(provider, &report, stdout) {
+            print_user_warning!("Could not write CI annotations: {err}");
+        }
+    }
+
+    if let Some(path) = &local_results.code_quality_report {
+        if let Err(err) = annotations::write_code_quality_report(&report, path) {
+            print_user_warning!("Could not write code quality report {path:?}: {err}");
+        }
+    }
 
-    if status.is_failure {
+    if report.status.is_failure {
         Ok(ExitCode::FailedPolicy)
     } else {
         Ok(ExitCode::Ok)
     }
 }
 
+/// Suppress all rejections accepted by the baseline.
+fn apply_baseline(
+    status: &mut PolicyEvaluationResponseRaw,
+    mode: &BaselineMode,
+    pretty_print: bool,
+) -> Result<()> {
+    let baseline = match mode {
+        BaselineMode::Apply(path) if path.exists() => Baseline::load(path)?,
+        BaselineMode::Apply(path) => {
+            let baseline = Baseline::from_status(status);
+            baseline.save(path)?;
+
+            print_user_warning!(
+                "Created baseline {:?} with {} existing rejection(s)",
+                path.display(),
+                baseline.len()
+            );
+
+            baseline
+        },
+        BaselineMode::Update(path) => {
+            let baseline = Baseline::from_status(status);
+            baseline.save(path)?;
+
+            if pretty_print {
+                print_user_success!(
+                    "Wrote {} rejection(s) to baseline {:?}",
+                    baseline.len(),
+                    path.display()
+                );
+            }
+
+            baseline
+        },
+    };
+
+    let suppressed = baseline.apply(status);
+    if pretty_print && suppressed > 0 {
+        print_user_success!("Suppressed {suppressed} rejection(s) present in the baseline");
+    }
+
+    Ok(())
+}
+
 /// Handle the history subcommand.
 ///
 /// This allows us to list last N job runs, list the projects, list runs
@@ -63,18 +176,20 @@ pub async fn handle_history(
     matches: &clap::ArgMatches,
     config: Config,
 ) -> CommandResult {
-    let pretty_print = !matches.get_flag("json");
+    let format = OutputFormat::from_matches(matches);
 
     if let Some(job_id) = matches.get_one::<String>("JOB_ID") {
         let job_id =
             JobId::from_str(job_id).with_context(|| format!("{job_id:?} is not a valid Job ID"))?;
-        return print_job_status(api, &job_id, [], pretty_print).await;
+        let local_results =
+            LocalResults { suppressions: Suppressions::load_current()?, ..LocalResults::default() };
+        return print_job_status(api, &job_id, [], local_results, format).await;
     } else if let Some(project) = matches.get_one::<String>("project") {
         let group = matches.get_one::<String>("group").map(String::as_str);
 
         let history = api.get_project_history(project, config.org(), group).await?;
 
-        history.write_stdout(pretty_print);
+        history.write_format(format)?;
     } else {
         let resp = match api.get_status().await {
             Ok(resp) => resp,
@@ -88,7 +203,7 @@ pub async fn handle_history(
             Err(err) => return Err(err.into()),
         };
 
-        resp.write_stdout(pretty_print);
+        resp.write_format(format)?;
     }
 
     Ok(ExitCode::Ok)
@@ -99,11 +214,27 @@ pub async fn handle_analyze(
     api: &PhylumApi,
     matches: &clap::ArgMatches,
     config: Config,
+) -> CommandResult {
+    let baseline =
+        matches.get_one::<String>("baseline").map(|path| BaselineMode::Apply(path.into()));
+    analyze(api, matches, config, baseline).await
+}
+
+/// Analyze the project's dependency files.
+pub async fn analyze(
+    api: &PhylumApi,
+    matches: &clap::ArgMatches,
+    config: Config,
+    baseline: Option<BaselineMode>,
 ) -> CommandResult {
     let sandbox_generation = !matches.get_flag("skip-sandbox");
     let generate_lockfiles = !matches.get_flag("no-generation");
     let label = matches.get_one::<String>("label");
-    let pretty_print = !matches.get_flag("json");
+    let format = OutputFormat::from_matches(matches);
+    let pretty_print = format == OutputFormat::Pretty;
+
+    // Validate suppressions before submitting the job.
+    let suppressions = Suppressions::load_current()?;
 
     let jobs_project = JobsProject::new(api, matches, config).await?;
 
@@ -111,8 +242,24 @@ pub async fn handle_analyze(
     let current_project = phylum_project::get_current_project();
     let project_root = current_project.as_ref().map(|p| p.root());
 
+    for depfile in &jobs_project.depfiles {
+        progress::report(Event::DepfileDiscovered {
+            path: &depfile.path,
+            format: &depfile.depfile_type,
+        });
+    }
+
     let mut packages = Vec::new();
+    let mut sources = Vec::new();
+    let mut depfiles = Vec::new();
     for depfile in jobs_project.depfiles {
+        let generation = generate_lockfiles
+            && parse::requires_generation(&depfile.path, Some(&depfile.depfile_type));
+        progress::report(Event::ParsingStarted { path: &depfile.path });
+        if generation {
+            progress::report(Event::GenerationStarted { path: &depfile.path });
+        }
+
         let parse_result = parse::parse_depfile(
             &depfile.path,
             project_root,
@@ -123,7 +270,7 @@ pub async fn handle_analyze(
 
         // Map dedicated exit codes for failures due to disabled generation or
         // unknown dependency file format.
-        let parsed_depfile = match parse_result {
+        let mut parsed_depfile = match parse_result {
             Ok(parsed_depfile) => parsed_depfile,
             Err(err @ ParseError::ManifestWithoutGeneration(_)) => {
                 print_user_failure!("Could not parse manifest: {}", err);
@@ -133,7 +280,7 @@ pub async fn handle_analyze(
                 print_user_failure!("Could not parse manifest: {}", err);
                 return Ok(ExitCode::UnknownManifestFormat);
             },
-            Err(ParseError::Other(err)) => {
+            Err(err @ ParseError::Other(_)) => {
                 return Err(err).with_context(|| {
                     format!(
                         "Could not parse dependency file {:?} as {:?} type",
@@ -144,6 +291,15 @@ pub async fn handle_analyze(
             },
         };
 
+        if generation {
+            progress::report(Event::GenerationFinished { path: &depfile.path });
+        }
+        progress::report(Event::ParsingFinished {
+            path: &depfile.path,
+            format: parsed_depfile.format.name(),
+            packages: parsed_depfile.packages.len(),
+        });
+
         if pretty_print {
             print_user_success!(
                 "Successfully parsed dependency file {:?} as type {:?}",
@@ -152,8 +308,10 @@ pub async fn handle_analyze(
             );
         }
 
-        let mut analysis_packages =
-            AnalysisPackageDescriptor::descriptors_from_lockfile(parsed_depfile);
+        sources.append(&mut parsed_depfile.sources);
+        depfiles.push(Depfile::new(&parsed_depfile, &depfile.path));
+
+        let mut analysis_packages = types::descriptors_from_lockfile(parsed_depfile);
         packages.append(&mut analysis_packages);
     }
 
@@ -171,6 +329,15 @@ pub async fn handle_analyze(
         return Ok(ExitCode::Ok);
     }
 
+    // Check for dependency confusion with internal registries.
+    let dependency_confusion = match &current_project {
+        Some(project) => {
+            let internal = project.internal_packages();
+            confusion::check_confusion(&sources, &internal.namespaces, &internal.registries)
+        },
+        None => Vec::new(),
+    };
+
     debug!("Submitting request...");
     let job_id = api
         .submit_request(
@@ -178,9 +345,11 @@ pub async fn handle_analyze(
             jobs_project.project_id,
             label.map(String::from),
             jobs_project.group,
+            ci::job_metadata(),
         )
         .await?;
     debug!("Response => {:?}", job_id);
+    progress::report(Event::JobSubmitted { job_id: job_id.to_string(), packages: packages.len() });
 
     if pretty_print {
         print_user_success!("Job ID: {}", job_id);
@@ -202,7 +371,19 @@ pub async fn handle_analyze(
     }
 
     debug!("Requesting status...");
-    print_job_status(api, &job_id, ignored_packages, pretty_print).await
+    progress::report(Event::Polling { job_id: job_id.to_string() });
+    let local_results = LocalResults {
+        dependency_confusion,
+        depfiles,
+        annotations: !matches.get_flag("no-annotations"),
+        code_quality_report: matches.get_one::<String>("code-quality-report").map(PathBuf::from),
+        baseline,
+        suppressions,
+    };
+    let result = print_job_status(api, &job_id, ignored_packages, local_results, format).await;
+    progress::report(Event::Done { job_id: job_id.to_string() });
+
+    result
 }
 
 /// Perform vulnerability reachability analysis.
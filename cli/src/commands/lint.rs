@@ -0,0 +1,85 @@
+//! Subcommand `phylum lint`.
+
+use std::path::{Path, PathBuf};
+use std::{env, fs};
+
+use anyhow::{anyhow, Context};
+use clap::ArgMatches;
+use phylum_lockfile::lint::{self, Severity, SourceFinding};
+use serde::Serialize;
+
+use crate::commands::{parse, CommandResult, ExitCode};
+use crate::format::Format;
+use crate::{config, print_user_warning};
+
+/// Lint findings for a single lockfile.
+#[derive(Serialize)]
+pub struct LintReport {
+    pub lockfile: PathBuf,
+    pub findings: Vec<SourceFinding>,
+}
+
+/// Handle `phylum lint` subcommand.
+pub fn handle_lint(matches: &ArgMatches) -> CommandResult {
+    let pretty_print = !matches.get_flag("json");
+    let strictness = matches.get_one::<String>("strictness").map(|level| match level.as_str() {
+        "info" => Severity::Info,
+        "warning" => Severity::Warning,
+        _ => Severity::Error,
+    });
+
+    let project = phylum_project::get_current_project();
+    let depfiles = config::depfiles(matches, project.as_ref())?;
+
+    // Paths outside of the project or git repository are considered external.
+    let current_dir = env::current_dir()?;
+    let repo_root = match &project {
+        Some(project) => project.root().clone(),
+        None => current_dir
+            .ancestors()
+            .find(|dir| dir.join(".git").exists())
+            .unwrap_or(&current_dir)
+            .to_path_buf(),
+    };
+
+    let mut reports = Vec::new();
+    for depfile in depfiles {
+        // Only lockfiles record the resolved dependency sources.
+        let (format, lockfile) =
+            match parse::find_depfile_format(&depfile.path, Some(&depfile.depfile_type)) {
+                Some((format, Some(lockfile))) => (format, lockfile),
+                _ => {
+                    print_user_warning!("Skipping {:?}: No lockfile found", depfile.path);
+                    continue;
+                },
+            };
+
+        let contents = fs::read_to_string(&lockfile)
+            .with_context(|| format!("Could not read lockfile {:?}", lockfile.display()))?;
+        let packages = format
+            .parser()
+            .parse(&contents)
+            .with_context(|| format!("Could not parse lockfile {:?}", lockfile.display()))?;
+
+        let lockfile_dir = fs::canonicalize(&lockfile)?
+            .parent()
+            .map(Path::to_path_buf)
+            .ok_or_else(|| anyhow!("Invalid lockfile path {:?}", lockfile.display()))?;
+        let findings = lint::lint_sources(&packages, &lockfile_dir, &repo_root);
+
+        reports.push(LintReport { lockfile, findings });
+    }
+
+    reports.write_stdout(pretty_print);
+
+    // Fail if any finding reaches the requested strictness level.
+    let failed = strictness.is_some_and(|strictness| {
+        reports.iter().flat_map(|report| &report.findings).any(|f| f.severity >= strictness)
+    });
+
+    if failed {
+        Ok(ExitCode::LintFailure)
+    } else {
+        Ok(ExitCode::Ok)
+    }
+}
@@ -1,12 +1,16 @@
 use std::process;
 
 pub mod auth;
+pub mod baseline;
+pub mod check_lock;
+pub mod config;
 #[cfg(feature = "extensions")]
 pub mod extensions;
 pub mod find_dependency_files;
 pub mod group;
 pub mod init;
 pub mod jobs;
+pub mod lint;
 pub mod org;
 pub mod packages;
 pub mod parse;
@@ -14,8 +18,10 @@ pub mod project;
 #[cfg(unix)]
 pub mod sandbox;
 pub mod status;
+pub mod suppressions;
 #[cfg(feature = "selfmanage")]
 pub mod uninstall;
+pub mod verify_installed;
 
 /// Shorthand type for Result whose ok value is CommandValue
 pub type CommandResult = anyhow::Result<ExitCode>;
@@ -25,6 +31,7 @@ pub type CommandResult = anyhow::Result<ExitCode>;
 pub enum ExitCode {
     Ok,
     Generic,
+    Usage,
     NotAuthenticated,
     AuthenticationFailure,
     PackageNotFound,
@@ -37,6 +44,10 @@ pub enum ExitCode {
     ManifestWithoutGeneration,
     UnknownManifestFormat,
     MissingOrg,
+    LockfileDrift,
+    InstalledMismatch,
+    LintFailure,
+    StaleSuppressions,
     FailedPolicy,
     SandboxStart,
     SandboxStartCollision,
@@ -48,6 +59,35 @@ impl ExitCode {
     pub fn exit(&self) -> ! {
         process::exit(self.into());
     }
+
+    /// Stable identifier of the exit code.
+    pub fn name(&self) -> &'static str {
+        match self {
+            ExitCode::Ok => "ok",
+            ExitCode::Generic => "generic",
+            ExitCode::Usage => "usage",
+            ExitCode::NotAuthenticated => "not_authenticated",
+            ExitCode::AuthenticationFailure => "authentication_failure",
+            ExitCode::PackageNotFound => "package_not_found",
+            ExitCode::AlreadyExists => "already_exists",
+            ExitCode::NoHistoryFound => "no_history_found",
+            ExitCode::JsError => "js_error",
+            ExitCode::ConfirmationFailed => "confirmation_failed",
+            ExitCode::NotFound => "not_found",
+            ExitCode::InvalidTokenExpiration => "invalid_token_expiration",
+            ExitCode::ManifestWithoutGeneration => "manifest_without_generation",
+            ExitCode::UnknownManifestFormat => "unknown_manifest_format",
+            ExitCode::MissingOrg => "missing_org",
+            ExitCode::LockfileDrift => "lockfile_drift",
+            ExitCode::InstalledMismatch => "installed_mismatch",
+            ExitCode::LintFailure => "lint_failure",
+            ExitCode::StaleSuppressions => "stale_suppressions",
+            ExitCode::FailedPolicy => "failed_policy",
+            ExitCode::SandboxStart => "sandbox_start",
+            ExitCode::SandboxStartCollision => "sandbox_start_collision",
+            ExitCode::Custom(_) => "custom",
+        }
+    }
 }
 
 impl From<&ExitCode> for i32 {
@@ -55,6 +95,7 @@ impl From<&ExitCode> for i32 {
         match code {
             ExitCode::Ok => 0,
             ExitCode::Generic => 1,
+            ExitCode::Usage => 2,
             ExitCode::NotAuthenticated => 10,
             ExitCode::AuthenticationFailure => 11,
             ExitCode::PackageNotFound => 12,
@@ -67,6 +108,10 @@ impl From<&ExitCode> for i32 {
             ExitCode::ManifestWithoutGeneration => 20,
             ExitCode::UnknownManifestFormat => 21,
             ExitCode::MissingOrg => 22,
+            ExitCode::LockfileDrift => 23,
+            ExitCode::InstalledMismatch => 24,
+            ExitCode::LintFailure => 25,
+            ExitCode::StaleSuppressions => 26,
             ExitCode::FailedPolicy => 100,
             ExitCode::SandboxStart => 117,
             ExitCode::SandboxStartCollision => 118,
@@ -1,6 +1,8 @@
 //! `phylum parse` command for lockfile parsing
 
 use std::borrow::Cow;
+#[cfg(unix)]
+use std::mem;
 use std::path::{Path, PathBuf};
 #[cfg(unix)]
 use std::process::Command as StdCommand;
@@ -16,14 +18,29 @@ use birdcage::process::{Command, Stdio};
 #[cfg(unix)]
 use birdcage::{Birdcage, Exception, Sandbox};
 use clap::ArgMatches;
+#[cfg(unix)]
+use phylum_lockfile::PackageSource;
 use phylum_lockfile::{LockfileFormat, ParseError, ParsedLockfile};
+#[cfg(unix)]
+use serde::{Deserialize, Serialize};
 
 use crate::commands::{CommandResult, ExitCode};
-use crate::types::AnalysisPackageDescriptor;
-use crate::{config, print_user_failure, print_user_warning};
+use crate::{config, print_user_failure, print_user_warning, types};
 #[cfg(unix)]
 use crate::{dirs, permissions};
 
+/// Parsed dependency file passed from the sandboxed parser.
+///
+/// Package sources are not serialized with the [`ParsedLockfile`], so they
+/// are passed separately.
+#[cfg(unix)]
+#[derive(Serialize, Deserialize)]
+struct SandboxedLockfile {
+    #[serde(flatten)]
+    lockfile: ParsedLockfile,
+    sources: Vec<PackageSource>,
+}
+
 pub fn lockfile_types(add_auto: bool) -> Vec<&'static str> {
     let mut lockfile_types = LockfileFormat::iter().map(|format| format.name()).collect::<Vec<_>>();
 
@@ -65,7 +82,7 @@ pub fn handle_parse(matches: &ArgMatches) -> CommandResult {
                 print_user_failure!("Could not parse manifest: {}", err);
                 return Ok(ExitCode::UnknownManifestFormat);
             },
-            Err(ParseError::Other(err)) => {
+            Err(err @ ParseError::Other(_)) => {
                 return Err(err).with_context(|| {
                     format!(
                         "Could not parse dependency file {:?} as {:?} type",
@@ -76,8 +93,7 @@ pub fn handle_parse(matches: &ArgMatches) -> CommandResult {
             },
         };
 
-        let mut analysis_packages =
-            AnalysisPackageDescriptor::descriptors_from_lockfile(parsed_lockfile);
+        let mut analysis_packages = types::descriptors_from_lockfile(parsed_lockfile);
         pkgs.append(&mut analysis_packages);
     }
 
@@ -144,7 +160,7 @@ fn child_parse_depfile(
         phylum_lockfile::parse_depfile(&contents, display_path, lockfile_type, generation_path);
 
     // Map lockfile generation failure to specific exit code.
-    let parsed = match parse_result {
+    let mut parsed = match parse_result {
         Ok(parsed) => parsed,
         Err(ParseError::ManifestWithoutGeneration(_)) => {
             return Ok(ExitCode::ManifestWithoutGeneration)
@@ -154,7 +170,9 @@ fn child_parse_depfile(
     };
 
     // Serialize dependency file to stdout.
-    println!("{}", serde_json::to_string(&parsed)?);
+    let sources = mem::take(&mut parsed.sources);
+    let sandboxed = SandboxedLockfile { lockfile: parsed, sources };
+    println!("{}", serde_json::to_string(&sandboxed)?);
 
     Ok(ExitCode::Ok)
 }
@@ -224,8 +242,10 @@ fn parse_depfile_sandboxed(
         }
     } else {
         let json = String::from_utf8_lossy(&output.stdout);
-        let parsed_lockfile = serde_json::from_str(&json).map_err(anyhow::Error::from)?;
-        Ok(parsed_lockfile)
+        let SandboxedLockfile { mut lockfile, sources } =
+            serde_json::from_str(&json).map_err(anyhow::Error::from)?;
+        lockfile.sources = sources;
+        Ok(lockfile)
     }
 }
 
@@ -240,7 +260,34 @@ fn parse_depfile_sandboxed(
 }
 
 /// Find a dependency file's format.
-fn find_depfile_format(
+pub fn find_depfile_format(
+    path: &Path,
+    depfile_type: Option<&str>,
+) -> Option<(LockfileFormat, Option<PathBuf>)> {
+    let (format, lockfile) = depfile_format(path, depfile_type)?;
+
+    if let Some(lockfile) = lockfile.as_ref().filter(|lockfile| *lockfile != path) {
+        print_user_warning!("{path:?} is not a lockfile, using {lockfile:?} instead");
+    }
+
+    Some((format, lockfile))
+}
+
+/// Check if parsing a dependency file requires lockfile generation.
+pub fn requires_generation(path: &Path, depfile_type: Option<&str>) -> bool {
+    matches!(depfile_format(path, depfile_type), Some((_, None)))
+}
+
+/// Get the lockfile parsed for a dependency file, without generating one.
+pub fn lockfile_path(path: &Path, depfile_type: Option<&str>) -> Option<PathBuf> {
+    depfile_format(path, depfile_type)?.1
+}
+
+/// Find a dependency file's format and the lockfile to parse.
+///
+/// The lockfile is `None` if the dependency file is a manifest without a
+/// lockfile.
+fn depfile_format(
     path: &Path,
     depfile_type: Option<&str>,
 ) -> Option<(LockfileFormat, Option<PathBuf>)> {
@@ -288,10 +335,7 @@ fn find_manifest_format(path: &Path) -> Option<(LockfileFormat, Option<PathBuf>)
 
     // Return existing lockfile or format capable of generating it.
     match manifest_lockfile {
-        Some((format, manifest_lockfile)) => {
-            print_user_warning!("{path:?} is not a lockfile, using {manifest_lockfile:?} instead");
-            Some((format, Some(manifest_lockfile)))
-        },
+        Some((format, manifest_lockfile)) => Some((format, Some(manifest_lockfile))),
         None => fallback_format.map(|format| (format, None)),
     }
 }
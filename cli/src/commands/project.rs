@@ -6,10 +6,11 @@ use anyhow::{anyhow, Context};
 use clap::{ArgMatches, Command};
 use console::style;
 use dialoguer::{Confirm, FuzzySelect, Input};
+use phylum_api::ApiError;
 use phylum_project::{ProjectConfig, PROJ_CONF_FILE};
 use reqwest::StatusCode;
 
-use crate::api::{PhylumApi, PhylumApiError, ResponseError};
+use crate::api::PhylumApi;
 use crate::commands::{init, CommandResult, ExitCode};
 use crate::config::{self, Config};
 use crate::format::Format;
@@ -40,7 +41,7 @@ pub async fn create_project(
     org: Option<String>,
     group: Option<String>,
     repository_url: Option<String>,
-) -> Result<ProjectConfig, PhylumApiError> {
+) -> Result<ProjectConfig, phylum_api::Error> {
     let project_id = api.create_project(project, org, group.clone(), repository_url).await?;
     Ok(ProjectConfig::new(project_id.to_owned(), project.to_owned(), group))
 }
@@ -119,7 +120,7 @@ async fn handle_create_project(
             .await;
     let project_config = match project_config {
         Ok(project) => project,
-        Err(PhylumApiError::Response(ResponseError { code: StatusCode::CONFLICT, .. })) => {
+        Err(phylum_api::Error::Api(ApiError { status: StatusCode::CONFLICT, .. })) => {
             let formatted_project = format_project_reference(org, group.as_deref(), project, None);
             print_user_failure!("Project {} already exists", formatted_project);
             return Ok(ExitCode::AlreadyExists);
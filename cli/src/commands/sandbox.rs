@@ -8,7 +8,7 @@ use birdcage::{Birdcage, Exception, Sandbox};
 use clap::ArgMatches;
 
 use crate::commands::{CommandResult, ExitCode};
-use crate::permissions;
+use crate::{permissions, print_user_failure};
 
 /// Entry point for the `sandbox` subcommand.
 pub async fn handle_sandbox(matches: &ArgMatches) -> CommandResult {
@@ -26,7 +26,7 @@ pub async fn handle_sandbox(matches: &ArgMatches) -> CommandResult {
     let status = match child.wait() {
         Ok(status) => status,
         Err(err) => {
-            eprintln!("Process {cmd:?} failed to start: {err}");
+            print_user_failure!("Process {cmd:?} failed to start: {err}");
             return Ok(ExitCode::SandboxStart);
         },
     };
@@ -0,0 +1,126 @@
+//! Subcommand `phylum suppressions`.
+
+use std::path::PathBuf;
+
+use anyhow::anyhow;
+use chrono::{Days, Local, NaiveDate};
+use clap::ArgMatches;
+use serde::Serialize;
+
+use crate::commands::{CommandResult, ExitCode};
+use crate::format::Format;
+use crate::suppressions::{self, Suppression, Suppressions, SUPPRESSIONS_FILE};
+
+/// Stale entries of a suppressions file.
+#[derive(Serialize)]
+pub struct SuppressionsAudit {
+    pub path: PathBuf,
+    pub entries: Vec<AuditEntry>,
+}
+
+/// Suppression which is expired or about to expire.
+#[derive(Serialize)]
+pub struct AuditEntry {
+    pub status: AuditStatus,
+    #[serde(flatten)]
+    pub suppression: Suppression,
+}
+
+#[derive(Serialize, Copy, Clone, PartialEq, Eq, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditStatus {
+    Expired,
+    Expiring,
+}
+
+/// Handle `phylum suppressions` subcommand.
+pub fn handle_suppressions(matches: &ArgMatches) -> CommandResult {
+    match matches.subcommand() {
+        Some(("audit", matches)) => handle_audit(matches),
+        _ => unreachable!("invalid clap configuration"),
+    }
+}
+
+/// Handle `phylum suppressions audit` subcommand.
+fn handle_audit(matches: &ArgMatches) -> CommandResult {
+    let pretty_print = !matches.get_flag("json");
+    let within = *matches.get_one::<u32>("within").unwrap();
+
+    let path = match matches.get_one::<String>("file") {
+        Some(path) => PathBuf::from(path),
+        None => {
+            suppressions::find_file().ok_or_else(|| anyhow!("No {SUPPRESSIONS_FILE} file found"))?
+        },
+    };
+    let suppressions = Suppressions::load(&path)?;
+
+    let today = Local::now().date_naive();
+    let horizon = today.checked_add_days(Days::new(within.into())).unwrap_or(NaiveDate::MAX);
+    let audit = audit(path, suppressions, today, horizon);
+    audit.write_stdout(pretty_print);
+
+    if audit.entries.iter().any(|entry| entry.status == AuditStatus::Expired) {
+        Ok(ExitCode::StaleSuppressions)
+    } else {
+        Ok(ExitCode::Ok)
+    }
+}
+
+/// Find suppressions which expired, or expire before `horizon`.
+fn audit(
+    path: PathBuf,
+    suppressions: Suppressions,
+    today: NaiveDate,
+    horizon: NaiveDate,
+) -> SuppressionsAudit {
+    let entries = suppressions
+        .entries
+        .into_iter()
+        .filter_map(|suppression| {
+            let status = match suppression.expires? {
+                _ if suppression.is_expired(today) => AuditStatus::Expired,
+                expires if expires <= horizon => AuditStatus::Expiring,
+                _ => return None,
+            };
+            Some(AuditEntry { status, suppression })
+        })
+        .collect();
+
+    SuppressionsAudit { path, entries }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn suppression(expires: Option<&str>) -> Suppression {
+        Suppression {
+            purl: None,
+            version: None,
+            tag: Some("CVE-2021-23337".into()),
+            title: None,
+            justification: "Not reachable".into(),
+            expires: expires.map(|date| date.parse().unwrap()),
+        }
+    }
+
+    #[test]
+    fn audit_stale_entries() {
+        let suppressions = Suppressions {
+            entries: vec![
+                suppression(None),
+                suppression(Some("2024-05-31")),
+                suppression(Some("2024-06-01")),
+                suppression(Some("2024-06-30")),
+                suppression(Some("2024-07-01")),
+            ],
+        };
+
+        let today = "2024-06-01".parse().unwrap();
+        let horizon = "2024-06-30".parse().unwrap();
+        let audit = audit(PathBuf::new(), suppressions, today, horizon);
+
+        let statuses: Vec<_> = audit.entries.iter().map(|entry| entry.status).collect();
+        assert_eq!(statuses, [AuditStatus::Expired, AuditStatus::Expiring, AuditStatus::Expiring]);
+    }
+}
@@ -0,0 +1,78 @@
+//! Subcommand `phylum verify-installed`.
+
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::{anyhow, Context};
+use clap::ArgMatches;
+use phylum_lockfile::installed::{self, InstalledDiff};
+use phylum_lockfile::ParseError;
+
+use crate::commands::{parse, CommandResult, ExitCode};
+use crate::format::Format;
+use crate::{config, print_user_failure};
+
+/// Handle `phylum verify-installed` subcommand.
+pub fn handle_verify_installed(matches: &ArgMatches) -> CommandResult {
+    let pretty_print = !matches.get_flag("json");
+    let root = PathBuf::from(matches.get_one::<String>("path").map_or(".", String::as_str));
+
+    let project = phylum_project::get_current_project();
+    let project_root = project.as_ref().map(|p| p.root());
+    let depfiles = config::depfiles(matches, project.as_ref())?;
+
+    // Collect packages from all lockfiles.
+    //
+    // Lockfile generation is disabled, since a freshly generated lockfile does
+    // not represent the versions which were installed.
+    let mut locked = Vec::new();
+    let mut optional = Vec::new();
+    for depfile in depfiles {
+        let parse_result = parse::parse_depfile(
+            &depfile.path,
+            project_root,
+            Some(&depfile.depfile_type),
+            false,
+            false,
+        );
+
+        let parsed_lockfile = match parse_result {
+            Ok(parsed_lockfile) => parsed_lockfile,
+            Err(err @ ParseError::ManifestWithoutGeneration(_)) => {
+                print_user_failure!("Could not verify manifest: {}", err);
+                return Ok(ExitCode::ManifestWithoutGeneration);
+            },
+            Err(err @ ParseError::UnknownManifestFormat(_)) => {
+                print_user_failure!("Could not verify manifest: {}", err);
+                return Ok(ExitCode::UnknownManifestFormat);
+            },
+            Err(ParseError::Other(err)) => {
+                return Err(err).with_context(|| {
+                    format!("Could not parse dependency file {:?}", depfile.path.display())
+                });
+            },
+        };
+
+        // Optional packages may be skipped on unsupported platforms.
+        let lockfile = parse::lockfile_path(&depfile.path, Some(&depfile.depfile_type));
+        if let Some(data) = lockfile.and_then(|lockfile| fs::read_to_string(lockfile).ok()) {
+            optional.extend(installed::optional_packages(parsed_lockfile.format, &data));
+        }
+
+        locked.extend(parsed_lockfile.packages);
+    }
+
+    let trees = installed::find_installed_trees(&root);
+    if trees.is_empty() {
+        return Err(anyhow!("No installed packages found in {:?}", root.display()));
+    }
+
+    let diff = installed::verify_installed(&locked, &optional, &trees);
+    diff.write_stdout(pretty_print);
+
+    if diff.is_empty() {
+        Ok(ExitCode::Ok)
+    } else {
+        Ok(ExitCode::InstalledMismatch)
+    }
+}
@@ -1,4 +1,6 @@
+use std::collections::{BTreeMap, BTreeSet};
 use std::env::VarError;
+use std::fmt::Display;
 #[cfg(not(unix))]
 use std::fs::File;
 #[cfg(unix)]
@@ -7,95 +9,527 @@ use std::io::{self, Write};
 #[cfg(unix)]
 use std::os::unix::fs::{DirBuilderExt, OpenOptionsExt};
 use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::OnceLock;
 use std::{env, fs};
 
 use anyhow::{anyhow, Context, Result};
+use clap::parser::ValueSource;
 use clap::ArgMatches;
+pub use phylum_api::retry::RetryConfig;
 use phylum_project::{DepfileConfig, ProjectConfig};
 use phylum_types::types::auth::RefreshToken;
 use serde::{Deserialize, Deserializer, Serialize};
 
+use crate::auth::credential_helper;
+use crate::logging::LogFormat;
 use crate::{dirs, print_user_failure, print_user_warning};
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct ConnectionInfo {
     pub uri: String,
+    /// PEM file with additional trusted root certificates.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ca_bundle: Option<PathBuf>,
+    /// Proxy URL for all requests.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proxy: Option<String>,
+    /// Comma-separated hosts which bypass the proxy, like `NO_PROXY`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub no_proxy: Option<String>,
+    /// PEM file with the client certificate chain for mutual TLS.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client_cert: Option<PathBuf>,
+    /// PEM file with the client certificate's private key.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client_key: Option<PathBuf>,
+}
+
+impl Default for ConnectionInfo {
+    fn default() -> Self {
+        Self {
+            uri: "https://api.phylum.io".into(),
+            ca_bundle: None,
+            proxy: None,
+            no_proxy: None,
+            client_cert: None,
+            client_key: None,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
 pub struct AuthInfo {
     offline_access: Option<RefreshToken>,
+    /// External program storing the refresh token.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    credential_helper: Option<String>,
+    /// Authenticate using the CI platform's identity token.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    ci: bool,
     #[serde(skip)]
     env_token: Option<RefreshToken>,
+    /// Refresh token from the credential helper, loaded on first use.
+    #[serde(skip)]
+    helper_token: OnceLock<Option<RefreshToken>>,
+    /// API URI identifying the refresh token in the credential helper.
+    #[serde(skip)]
+    uri: String,
 }
 
 impl AuthInfo {
     pub fn new(offline_access: Option<RefreshToken>) -> Self {
-        Self { offline_access, env_token: None }
+        Self { offline_access, ..Self::default() }
     }
 
     pub fn offline_access(&self) -> Option<&RefreshToken> {
         let env_token = self.env_token.as_ref().filter(|token| !token.as_str().is_empty());
         let token = self.offline_access.as_ref().filter(|token| !token.as_str().is_empty());
-        env_token.or(token)
+        env_token.or_else(|| self.helper_token()).or(token)
+    }
+
+    /// Check if the CI identity token is used for authentication.
+    pub fn ci(&self) -> bool {
+        self.ci
+    }
+
+    /// Update the refresh token.
+    ///
+    /// With a credential helper, the token is stored by the helper instead of
+    /// the configuration file.
+    pub fn set_offline_access(&mut self, offline_access: RefreshToken) -> Result<()> {
+        match &self.credential_helper {
+            Some(helper) => {
+                credential_helper::store(helper, &self.uri, &offline_access)?;
+                self.helper_token = OnceLock::from(Some(offline_access));
+                self.offline_access = None;
+            },
+            None => self.offline_access = Some(offline_access),
+        }
+
+        Ok(())
+    }
+
+    /// Remove the refresh token.
+    pub fn clear_offline_access(&mut self) -> Result<()> {
+        if let Some(helper) = &self.credential_helper {
+            credential_helper::erase(helper, &self.uri)?;
+            self.helper_token = OnceLock::from(None);
+        }
+
+        self.offline_access = None;
+
+        Ok(())
     }
 
-    pub fn set_offline_access(&mut self, offline_access: RefreshToken) {
-        self.offline_access = Some(offline_access);
+    /// Get the refresh token from the credential helper.
+    fn helper_token(&self) -> Option<&RefreshToken> {
+        let helper = self.credential_helper.as_ref()?;
+        let token = self.helper_token.get_or_init(|| {
+            credential_helper::get(helper, &self.uri).unwrap_or_else(|err| {
+                print_user_warning!("Could not get token from credential helper: {err:#}");
+                None
+            })
+        });
+        token.as_ref()
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+/// Name of the profile stored at the top level of the configuration file.
+pub const DEFAULT_PROFILE: &str = "default";
+
+/// Named set of server settings and credentials.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct Profile {
+    #[serde(default)]
+    pub connection: ConnectionInfo,
+    #[serde(default)]
+    pub auth_info: AuthInfo,
+    #[serde(rename = "organization", default, skip_serializing_if = "Option::is_none")]
+    pub org: Option<String>,
+    #[serde(default)]
+    pub ignore_certs: bool,
+}
+
+/// Logging configuration.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct LogConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format: Option<LogFormat>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file: Option<PathBuf>,
+}
+
+impl LogConfig {
+    fn is_empty(&self) -> bool {
+        self.format.is_none() && self.file.is_none()
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
 pub struct Config {
     pub connection: ConnectionInfo,
     pub auth_info: AuthInfo,
     pub last_update: Option<usize>,
     #[serde(skip)]
     pub path: Option<PathBuf>,
-    #[serde(skip)]
-    ignore_certs_cli: bool,
     #[serde(deserialize_with = "default_option_bool")]
     ignore_certs: bool,
     #[serde(rename = "organization")]
     org: Option<String>,
+    /// Timeout for API requests in seconds.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    timeout: Option<u64>,
+    #[serde(default, skip_serializing_if = "LogConfig::is_empty")]
+    pub log: LogConfig,
+    #[serde(default, skip_serializing_if = "RetryConfig::is_default")]
+    retry: RetryConfig,
+    /// Profile used unless another one is selected explicitly.
+    #[serde(rename = "profile", default, skip_serializing_if = "Option::is_none")]
+    default_profile: Option<String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub profiles: BTreeMap<String, Profile>,
+    /// Name of the profile applied to the top-level settings.
     #[serde(skip)]
-    org_cli: Option<String>,
-}
-
-impl Default for Config {
-    fn default() -> Self {
-        Config {
-            connection: ConnectionInfo { uri: "https://api.phylum.io".into() },
-            ignore_certs_cli: Default::default(),
-            ignore_certs: Default::default(),
-            last_update: Default::default(),
-            auth_info: Default::default(),
-            org_cli: Default::default(),
-            path: Default::default(),
-            org: Default::default(),
-        }
-    }
+    profile: Option<String>,
+    /// Top-level settings replaced by the applied profile.
+    #[serde(skip)]
+    top_level: Option<Box<Profile>>,
+    /// Settings overridden by environment variables or CLI arguments.
+    #[serde(skip)]
+    overrides: BTreeMap<&'static str, Override>,
+    /// Overridden settings which were explicitly changed and must be saved.
+    #[serde(skip)]
+    dirty: BTreeSet<&'static str>,
 }
 
 impl Config {
     /// Check if certificates should be ignored.
     pub fn ignore_certs(&self) -> bool {
-        self.ignore_certs_cli || self.ignore_certs
+        self.ignore_certs
     }
 
     /// Set the CLI `--no-check-certificate` override value.
-    pub fn set_ignore_certs_cli(&mut self, ignore_certs_cli: bool) {
-        self.ignore_certs_cli = ignore_certs_cli;
+    pub fn set_ignore_certs_cli(&mut self, ignore_certs_cli: bool) -> Result<()> {
+        if ignore_certs_cli {
+            self.override_value(IGNORE_CERTS_SETTING, "true", Origin::Flag)?;
+        }
+        Ok(())
     }
 
     /// Check target organization.
     pub fn org(&self) -> Option<&str> {
-        self.org_cli.as_ref().or(self.org.as_ref()).map(|org| org.as_str())
+        self.org.as_deref()
+    }
+
+    /// Organization passed with the CLI `--org` argument.
+    pub fn org_cli(&self) -> Option<&str> {
+        self.overrides
+            .get(ORG_SETTING)
+            .filter(|value| value.origin == Origin::Flag)
+            .and_then(|value| value.value.as_deref())
     }
 
     /// Update the config organization.
+    ///
+    /// This replaces any override, so the organization is always saved.
     pub fn set_org(&mut self, org: Option<String>) {
         self.org = org;
+        self.dirty.insert(ORG_SETTING);
+    }
+
+    /// Timeout for API requests in seconds.
+    pub fn timeout(&self) -> Option<u64> {
+        self.timeout
+    }
+
+    /// Retry policy for API requests.
+    pub fn retry(&self) -> RetryConfig {
+        self.retry.clone()
+    }
+
+    /// Set the CLI `--retries` override value.
+    pub fn set_retries_cli(&mut self, retries_cli: Option<u32>) -> Result<()> {
+        match retries_cli {
+            Some(retries) => {
+                self.override_value(RETRIES_SETTING, &retries.to_string(), Origin::Flag)
+            },
+            None => Ok(()),
+        }
+    }
+
+    /// Name of the profile in use.
+    pub fn profile(&self) -> &str {
+        self.profile.as_deref().unwrap_or(DEFAULT_PROFILE)
+    }
+
+    /// Name of the profile used unless another one is selected explicitly.
+    pub fn default_profile(&self) -> &str {
+        self.default_profile.as_deref().unwrap_or(DEFAULT_PROFILE)
+    }
+
+    /// Update the profile used unless another one is selected explicitly.
+    pub fn set_default_profile(&mut self, name: &str) -> Result<()> {
+        self.set(PROFILE_SETTING, Some(name))
+    }
+
+    /// Check if a profile with this name exists.
+    pub fn has_profile(&self, name: &str) -> bool {
+        name == DEFAULT_PROFILE || self.profiles.contains_key(name)
+    }
+
+    /// Current settings of a profile.
+    pub fn profile_settings(&self, name: &str) -> Option<Profile> {
+        if name == self.profile() {
+            return Some(Profile {
+                connection: self.connection.clone(),
+                auth_info: self.auth_info.clone(),
+                org: self.org.clone(),
+                ignore_certs: self.ignore_certs,
+            });
+        }
+
+        match &self.top_level {
+            Some(top_level) if name == DEFAULT_PROFILE => Some((**top_level).clone()),
+            _ => self.profiles.get(name).cloned(),
+        }
+    }
+
+    /// Replace the top-level settings with a named profile.
+    fn apply_profile(&mut self, name: &str) -> Result<()> {
+        if name == DEFAULT_PROFILE {
+            return Ok(());
+        }
+
+        let profile = match self.profiles.get(name) {
+            Some(profile) => profile.clone(),
+            None => return Err(anyhow!("Unknown profile {name:?}")),
+        };
+
+        let top_level = self.replace_profile_settings(profile);
+        self.top_level = Some(Box::new(top_level));
+        self.profile = Some(name.into());
+
+        Ok(())
+    }
+
+    /// Replace the top-level profile settings, returning the previous ones.
+    fn replace_profile_settings(&mut self, profile: Profile) -> Profile {
+        // Credentials from the environment apply to every profile.
+        let env_token = self.auth_info.env_token.take();
+
+        let previous = Profile {
+            connection: std::mem::replace(&mut self.connection, profile.connection),
+            auth_info: std::mem::replace(&mut self.auth_info, profile.auth_info),
+            org: std::mem::replace(&mut self.org, profile.org),
+            ignore_certs: std::mem::replace(&mut self.ignore_certs, profile.ignore_certs),
+        };
+
+        self.auth_info.env_token = env_token;
+
+        previous
+    }
+
+    /// Identify credentials in the credential helper by the Phylum instance.
+    ///
+    /// This must be called before the refresh token is first accessed, since
+    /// the token from the credential helper is cached afterwards.
+    fn identify_credentials(&mut self) {
+        if self.auth_info.uri != self.connection.uri {
+            self.auth_info.uri = self.connection.uri.clone();
+            self.auth_info.helper_token = OnceLock::new();
+        }
+    }
+
+    /// Get the effective value of a setting.
+    pub fn get(&self, key: &str) -> Result<Option<String>> {
+        // API keys from the environment are never persisted.
+        if key == API_KEY_SETTING {
+            return Ok(self.auth_info.offline_access().map(|token| token.as_str().into()));
+        }
+
+        self.get_value(key)
+    }
+
+    /// Get the value of a setting in the configuration file.
+    pub fn file_value(&self, key: &str) -> Result<Option<String>> {
+        match self.overrides.get(key) {
+            Some(value) => Ok(value.file_value.clone()),
+            None => self.get_value(key),
+        }
+    }
+
+    /// Get the source of a setting's effective value.
+    pub fn origin(&self, key: &str) -> Result<Origin> {
+        if let Some(value) = self.overrides.get(key) {
+            return Ok(value.origin);
+        }
+
+        let env_token = self.auth_info.env_token.as_ref();
+        if key == API_KEY_SETTING && env_token.is_some_and(|token| !token.as_str().is_empty()) {
+            return Ok(Origin::Env);
+        }
+
+        if key == API_KEY_SETTING && self.auth_info.helper_token().is_some() {
+            return Ok(Origin::CredentialHelper);
+        }
+
+        if self.get_value(key)? == Config::default().get_value(key)? {
+            Ok(Origin::Default)
+        } else {
+            Ok(Origin::File)
+        }
+    }
+
+    /// Update a setting in the configuration file.
+    ///
+    /// Passing `None` resets the setting to its default value.
+    pub fn set(&mut self, key: &str, value: Option<&str>) -> Result<()> {
+        // Pass credentials through to the credential helper.
+        if key == API_KEY_SETTING {
+            return match value {
+                Some(value) => self.auth_info.set_offline_access(RefreshToken::new(value)),
+                None => self.auth_info.clear_offline_access(),
+            };
+        }
+
+        self.set_value(key, value)?;
+        self.dirty.remove(key);
+
+        // Keep overrides in effect, only updating the value in the file.
+        if let Some(override_value) = self.overrides.get(key).map(|value| value.value.clone()) {
+            let file_value = self.get_value(key)?;
+            self.set_value(key, override_value.as_deref())?;
+            if let Some(value) = self.overrides.get_mut(key) {
+                value.file_value = file_value;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Override a setting without writing it to the configuration file.
+    fn override_value(&mut self, key: &'static str, value: &str, origin: Origin) -> Result<()> {
+        let file_value = match self.overrides.remove(key) {
+            Some(value) => value.file_value,
+            None => self.get_value(key)?,
+        };
+
+        self.set_value(key, Some(value))?;
+
+        let value = self.get_value(key)?;
+        self.overrides.insert(key, Override { origin, value, file_value });
+
+        Ok(())
+    }
+
+    /// Apply environment and CLI overrides for all matching settings.
+    fn apply_overrides(
+        &mut self,
+        matches: &ArgMatches,
+        filter: impl Fn(&str) -> bool,
+    ) -> Result<()> {
+        for setting in SETTINGS.iter().filter(|setting| filter(setting.key)) {
+            if let Some(var) = setting.env.filter(|_| setting.key != API_KEY_SETTING) {
+                match env::var(var) {
+                    Ok(value) if !value.is_empty() => self
+                        .override_value(setting.key, &value, Origin::Env)
+                        .with_context(|| format!("Invalid {var}"))?,
+                    _ => (),
+                }
+            }
+
+            if let Some(value) = setting.flag.and_then(|flag| cli_value(matches, flag)) {
+                self.override_value(setting.key, &value, Origin::Flag)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Get the current value of a setting.
+    fn get_value(&self, key: &str) -> Result<Option<String>> {
+        let path = |path: &Option<PathBuf>| path.as_ref().map(|path| path.display().to_string());
+
+        let value = match key {
+            "connection.uri" => Some(self.connection.uri.clone()),
+            "connection.ca_bundle" => path(&self.connection.ca_bundle),
+            "connection.proxy" => self.connection.proxy.clone(),
+            "connection.no_proxy" => self.connection.no_proxy.clone(),
+            "connection.client_cert" => path(&self.connection.client_cert),
+            "connection.client_key" => path(&self.connection.client_key),
+            API_KEY_SETTING => self.auth_info.offline_access.as_ref().map(|t| t.as_str().into()),
+            "auth_info.credential_helper" => self.auth_info.credential_helper.clone(),
+            "auth_info.ci" => Some(self.auth_info.ci.to_string()),
+            ORG_SETTING => self.org.clone(),
+            IGNORE_CERTS_SETTING => Some(self.ignore_certs.to_string()),
+            "timeout" => self.timeout.map(|timeout| timeout.to_string()),
+            "last_update" => self.last_update.map(|last_update| last_update.to_string()),
+            "log.format" => self.log.format.map(|format| format.as_arg().into()),
+            "log.file" => path(&self.log.file),
+            RETRIES_SETTING => Some(self.retry.max_retries.to_string()),
+            "retry.initial_delay_ms" => Some(self.retry.initial_delay_ms.to_string()),
+            "retry.max_delay_ms" => Some(self.retry.max_delay_ms.to_string()),
+            PROFILE_SETTING => Some(self.default_profile().into()),
+            _ => return Err(unknown_setting(key)),
+        };
+
+        Ok(value)
+    }
+
+    /// Update the current value of a setting.
+    fn set_value(&mut self, key: &str, value: Option<&str>) -> Result<()> {
+        let retry = RetryConfig::default();
+
+        match key {
+            "connection.uri" => {
+                let uri = parse(key, value)?;
+                self.connection.uri = uri.unwrap_or_else(|| ConnectionInfo::default().uri);
+                self.identify_credentials();
+            },
+            "connection.ca_bundle" => self.connection.ca_bundle = parse(key, value)?,
+            "connection.proxy" => self.connection.proxy = parse(key, value)?,
+            "connection.no_proxy" => self.connection.no_proxy = parse(key, value)?,
+            "connection.client_cert" => self.connection.client_cert = parse(key, value)?,
+            "connection.client_key" => self.connection.client_key = parse(key, value)?,
+            API_KEY_SETTING => self.auth_info.offline_access = value.map(RefreshToken::new),
+            "auth_info.credential_helper" => {
+                self.auth_info.credential_helper = parse(key, value)?;
+                self.auth_info.helper_token = OnceLock::new();
+            },
+            "auth_info.ci" => self.auth_info.ci = parse_bool(key, value)?.unwrap_or_default(),
+            ORG_SETTING => self.org = parse(key, value)?,
+            IGNORE_CERTS_SETTING => {
+                self.ignore_certs = parse_bool(key, value)?.unwrap_or_default();
+            },
+            "timeout" => self.timeout = parse(key, value)?,
+            "last_update" => self.last_update = parse(key, value)?,
+            "log.format" => {
+                self.log.format = value
+                    .map(|value| LogFormat::from_arg(value).ok_or_else(|| invalid(key, value)))
+                    .transpose()?;
+            },
+            "log.file" => self.log.file = parse(key, value)?,
+            RETRIES_SETTING => {
+                self.retry.max_retries = parse(key, value)?.unwrap_or(retry.max_retries);
+            },
+            "retry.initial_delay_ms" => {
+                self.retry.initial_delay_ms = parse(key, value)?.unwrap_or(retry.initial_delay_ms);
+            },
+            "retry.max_delay_ms" => {
+                self.retry.max_delay_ms = parse(key, value)?.unwrap_or(retry.max_delay_ms);
+            },
+            PROFILE_SETTING => {
+                let name = value.unwrap_or(DEFAULT_PROFILE);
+                if !self.has_profile(name) {
+                    return Err(anyhow!("Unknown profile {name:?}"));
+                }
+                self.default_profile = (name != DEFAULT_PROFILE).then(|| name.into());
+            },
+            _ => return Err(unknown_setting(key)),
+        }
+
+        Ok(())
     }
 
     /// Write updates to the configuration file.
@@ -105,10 +539,152 @@ impl Config {
             None => return Ok(()),
         };
 
-        save_config(path, self)
+        // Restore overridden settings, unless they were explicitly changed since.
+        let mut config = self.clone();
+        for (key, value) in &self.overrides {
+            if !self.dirty.contains(key) {
+                config.set_value(key, value.file_value.as_deref())?;
+            }
+        }
+
+        // Move the applied profile's settings back into the profile.
+        if let (Some(name), Some(top_level)) = (&self.profile, &self.top_level) {
+            let profile = config.replace_profile_settings((**top_level).clone());
+            if let Some(entry) = config.profiles.get_mut(name) {
+                *entry = profile;
+            }
+        }
+
+        save_config(path, &config)
+    }
+}
+
+/// Setting accessible through `phylum config`.
+pub struct Setting {
+    /// Dotted path of the setting in the configuration file.
+    pub key: &'static str,
+    /// Environment variable overriding the setting.
+    pub env: Option<&'static str>,
+    /// CLI argument overriding the setting.
+    pub flag: Option<&'static str>,
+}
+
+/// Setting for the refresh token.
+pub const API_KEY_SETTING: &str = "auth_info.offline_access";
+
+/// Setting for the profile in use.
+pub const PROFILE_SETTING: &str = "profile";
+
+/// Setting for the organization.
+const ORG_SETTING: &str = "organization";
+
+/// Setting for disabling TLS certificate verification.
+const IGNORE_CERTS_SETTING: &str = "ignore_certs";
+
+/// Setting for the maximum number of API request retries.
+const RETRIES_SETTING: &str = "retry.max_retries";
+
+/// All settings accessible through `phylum config`.
+pub const SETTINGS: &[Setting] = &[
+    Setting { key: "connection.uri", env: Some("PHYLUM_API_URI"), flag: None },
+    Setting { key: "connection.ca_bundle", env: Some("PHYLUM_CA_BUNDLE"), flag: None },
+    Setting { key: "connection.proxy", env: Some("PHYLUM_PROXY"), flag: None },
+    Setting { key: "connection.no_proxy", env: Some("PHYLUM_NO_PROXY"), flag: None },
+    Setting { key: "connection.client_cert", env: Some("PHYLUM_CLIENT_CERT"), flag: None },
+    Setting { key: "connection.client_key", env: Some("PHYLUM_CLIENT_KEY"), flag: None },
+    Setting { key: API_KEY_SETTING, env: Some("PHYLUM_API_KEY"), flag: None },
+    Setting {
+        key: "auth_info.credential_helper",
+        env: Some("PHYLUM_CREDENTIAL_HELPER"),
+        flag: None,
+    },
+    Setting { key: "auth_info.ci", env: Some("PHYLUM_CI_AUTH"), flag: None },
+    Setting { key: ORG_SETTING, env: Some("PHYLUM_ORG"), flag: Some("org") },
+    Setting {
+        key: IGNORE_CERTS_SETTING,
+        env: Some("PHYLUM_NO_CHECK_CERTIFICATE"),
+        flag: Some("no-check-certificate"),
+    },
+    Setting { key: "timeout", env: Some("PHYLUM_TIMEOUT"), flag: Some("timeout") },
+    Setting { key: "last_update", env: None, flag: None },
+    Setting { key: "log.format", env: Some("PHYLUM_LOG_FORMAT"), flag: Some("log-format") },
+    Setting { key: "log.file", env: Some("PHYLUM_LOG_FILE"), flag: Some("log-file") },
+    Setting { key: RETRIES_SETTING, env: Some("PHYLUM_RETRIES"), flag: Some("retries") },
+    Setting {
+        key: "retry.initial_delay_ms",
+        env: Some("PHYLUM_RETRY_INITIAL_DELAY_MS"),
+        flag: None,
+    },
+    Setting { key: "retry.max_delay_ms", env: Some("PHYLUM_RETRY_MAX_DELAY_MS"), flag: None },
+    Setting { key: PROFILE_SETTING, env: Some("PHYLUM_PROFILE"), flag: Some("profile") },
+];
+
+/// Source of a setting's effective value.
+#[derive(Serialize, Copy, Clone, PartialEq, Eq, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum Origin {
+    Default,
+    File,
+    Env,
+    Flag,
+    CredentialHelper,
+}
+
+/// Setting overridden through the environment or CLI arguments.
+#[derive(Debug, Clone, PartialEq)]
+struct Override {
+    origin: Origin,
+    value: Option<String>,
+    /// Value in the configuration file, restored before saving.
+    file_value: Option<String>,
+}
+
+/// Parse the value of a setting.
+fn parse<T>(key: &str, value: Option<&str>) -> Result<Option<T>>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    value
+        .map(|value| value.parse().map_err(|err| anyhow!("{}: {err}", invalid(key, value))))
+        .transpose()
+}
+
+/// Parse the value of a boolean setting.
+///
+/// Besides `true` and `false`, the common `1`/`0`, `yes`/`no` and `on`/`off`
+/// values of environment variables are accepted.
+fn parse_bool(key: &str, value: Option<&str>) -> Result<Option<bool>> {
+    let value = match value {
+        Some(value) => value,
+        None => return Ok(None),
+    };
+
+    match value.to_ascii_lowercase().as_str() {
+        "true" | "1" | "yes" | "on" => Ok(Some(true)),
+        "false" | "0" | "no" | "off" => Ok(Some(false)),
+        _ => Err(invalid(key, value)),
     }
 }
 
+fn invalid(key: &str, value: &str) -> anyhow::Error {
+    anyhow!("Invalid value {value:?} for setting {key:?}")
+}
+
+fn unknown_setting(key: &str) -> anyhow::Error {
+    anyhow!("Unknown setting {key:?}")
+}
+
+/// Get the value of an argument passed on the command line.
+fn cli_value(matches: &ArgMatches, id: &str) -> Option<String> {
+    if matches.value_source(id) != Some(ValueSource::CommandLine) {
+        return None;
+    }
+
+    let value = matches.get_raw(id)?.next()?;
+    value.to_str().map(String::from)
+}
+
 fn default_option_bool<'de, D>(deserializer: D) -> Result<bool, D::Error>
 where
     D: Deserializer<'de>,
@@ -118,9 +694,13 @@ where
 
 /// Load the configuration file.
 pub fn load_config(matches: &ArgMatches) -> Result<Config> {
-    // Early return with configuration disabled.
+    // Only apply overrides with configuration disabled. Profiles are stored in
+    // the configuration file, so they cannot be selected.
     if matches.get_flag("no-config") {
-        return Ok(Config::default());
+        let mut config = Config::default();
+        config.apply_overrides(matches, |key| key != PROFILE_SETTING)?;
+        config.identify_credentials();
+        return Ok(config);
     }
 
     let settings_path = get_home_settings_path()?;
@@ -134,8 +714,15 @@ pub fn load_config(matches: &ArgMatches) -> Result<Config> {
     let mut config: Config = read_configuration(&config_path)
         .with_context(|| anyhow!("Failed to read configuration at {:?}", config_path))?;
 
-    // Store CLI org separately, to allow overriding without ever writing it.
-    config.org_cli = matches.get_one::<String>("org").cloned();
+    // Apply the selected profile, before overriding any of its settings.
+    config.apply_overrides(matches, |key| key == PROFILE_SETTING)?;
+    let profile = config.default_profile().to_owned();
+    config.apply_profile(&profile)?;
+
+    // Override settings, preferring CLI arguments over the environment.
+    config.apply_overrides(matches, |key| key != PROFILE_SETTING)?;
+
+    config.identify_credentials();
 
     config.path = Some(config_path);
 
@@ -292,10 +879,11 @@ mod tests {
 
     fn test_config() -> Config {
         Config {
-            connection: ConnectionInfo { uri: String::from(LOCALHOST) },
+            connection: ConnectionInfo { uri: String::from(LOCALHOST), ..Default::default() },
             auth_info: AuthInfo {
                 offline_access: Some(RefreshToken::new(CONFIG_TOKEN)),
                 env_token: Some(RefreshToken::new(ENV_TOKEN)),
+                ..Default::default()
             },
             ..Config::default()
         }
@@ -334,11 +922,211 @@ mod tests {
         assert_eq!(config.auth_info.env_token, None);
     }
 
+    #[test]
+    fn profiles() {
+        const STAGING: &str = "https://api.staging.example.com";
+        const STAGING_TOKEN: &str = "STAGING TOKEN";
+
+        let tempfile = NamedTempFile::new().unwrap();
+        let mut config = test_config();
+        config.path = Some(tempfile.path().into());
+        config.profiles.insert("staging".into(), Profile {
+            connection: ConnectionInfo { uri: STAGING.into(), ..Default::default() },
+            org: Some("staging-org".into()),
+            ..Default::default()
+        });
+
+        assert!(config.clone().apply_profile("missing").is_err());
+
+        config.apply_profile("staging").unwrap();
+        assert_eq!(config.profile(), "staging");
+        assert_eq!(config.connection.uri, STAGING);
+        assert_eq!(config.org(), Some("staging-org"));
+        assert_eq!(config.profile_settings(DEFAULT_PROFILE).unwrap().connection.uri, LOCALHOST);
+
+        // Environment token overrides all profiles.
+        assert_eq!(config.auth_info.offline_access(), Some(&RefreshToken::new(ENV_TOKEN)));
+
+        // Changes are written to the applied profile.
+        config.auth_info.set_offline_access(RefreshToken::new(STAGING_TOKEN)).unwrap();
+        config.save().unwrap();
+
+        let saved: Config = parse_config(tempfile.path()).unwrap();
+        assert_eq!(saved.connection.uri, LOCALHOST);
+        assert_eq!(saved.auth_info.offline_access(), Some(&RefreshToken::new(CONFIG_TOKEN)));
+        let staging = &saved.profiles["staging"];
+        assert_eq!(staging.connection.uri, STAGING);
+        assert_eq!(staging.auth_info.offline_access(), Some(&RefreshToken::new(STAGING_TOKEN)));
+    }
+
+    #[test]
+    fn settings() {
+        let mut expected = test_config();
+        expected.identify_credentials();
+        let mut config = expected.clone();
+
+        for setting in SETTINGS {
+            let value = config.get_value(setting.key).unwrap();
+            config.set(setting.key, value.as_deref()).unwrap();
+        }
+        assert_eq!(config, expected);
+
+        config.set("timeout", Some("30")).unwrap();
+        assert_eq!(config.timeout(), Some(30));
+        assert_eq!(config.origin("timeout").unwrap(), Origin::File);
+
+        config.set("timeout", None).unwrap();
+        assert_eq!(config.get("timeout").unwrap(), None);
+        assert_eq!(config.origin("timeout").unwrap(), Origin::Default);
+
+        assert_eq!(config.get(API_KEY_SETTING).unwrap().as_deref(), Some(ENV_TOKEN));
+        assert_eq!(config.origin(API_KEY_SETTING).unwrap(), Origin::Env);
+
+        assert!(config.set("timeout", Some("soon")).is_err());
+        assert!(config.set("log.format", Some("xml")).is_err());
+        assert!(config.set("profile", Some("missing")).is_err());
+        assert!(config.set("missing", Some("value")).is_err());
+    }
+
+    #[test]
+    fn overrides_are_not_saved() {
+        let tempfile = NamedTempFile::new().unwrap();
+        let mut config = test_config();
+        config.path = Some(tempfile.path().into());
+
+        config.override_value("organization", "env-org", Origin::Env).unwrap();
+        config.override_value("organization", "cli-org", Origin::Flag).unwrap();
+        config.override_value("retry.max_retries", "5", Origin::Env).unwrap();
+        assert_eq!(config.org(), Some("cli-org"));
+        assert_eq!(config.origin("organization").unwrap(), Origin::Flag);
+        assert_eq!(config.retry().max_retries, 5);
+
+        // Updating an overridden setting only changes the file.
+        config.set("retry.max_retries", Some("7")).unwrap();
+        assert_eq!(config.retry().max_retries, 5);
+        assert_eq!(config.file_value("retry.max_retries").unwrap().as_deref(), Some("7"));
+
+        config.save().unwrap();
+
+        let saved: Config = parse_config(tempfile.path()).unwrap();
+        assert_eq!(saved.org(), None);
+        assert_eq!(saved.retry().max_retries, 7);
+
+        // Explicit changes replace overrides.
+        config.set_org(Some("linked-org".into()));
+        config.save().unwrap();
+
+        let saved: Config = parse_config(tempfile.path()).unwrap();
+        assert_eq!(saved.org(), Some("linked-org"));
+    }
+
+    #[test]
+    fn explicit_change_matching_override_is_saved() {
+        let tempfile = NamedTempFile::new().unwrap();
+        let mut config = test_config();
+        config.path = Some(tempfile.path().into());
+
+        config.override_value("organization", "env-org", Origin::Env).unwrap();
+        config.set_org(Some("env-org".into()));
+        config.save().unwrap();
+
+        let saved: Config = parse_config(tempfile.path()).unwrap();
+        assert_eq!(saved.org(), Some("env-org"));
+    }
+
+    #[test]
+    fn cli_overrides() {
+        let tempfile = NamedTempFile::new().unwrap();
+        let mut config = test_config();
+        config.path = Some(tempfile.path().into());
+
+        config.set_ignore_certs_cli(false).unwrap();
+        assert!(!config.ignore_certs());
+        config.set_ignore_certs_cli(true).unwrap();
+        assert!(config.ignore_certs());
+
+        config.set_retries_cli(Some(9)).unwrap();
+        assert_eq!(config.retry().max_retries, 9);
+
+        config.override_value("organization", "env-org", Origin::Env).unwrap();
+        assert_eq!(config.org_cli(), None);
+        config.override_value("organization", "cli-org", Origin::Flag).unwrap();
+        assert_eq!(config.org_cli(), Some("cli-org"));
+
+        config.save().unwrap();
+
+        let saved: Config = parse_config(tempfile.path()).unwrap();
+        assert!(!saved.ignore_certs());
+        assert_eq!(saved.retry(), RetryConfig::default());
+        assert_eq!(saved.org(), None);
+    }
+
+    #[test]
+    fn bool_settings() {
+        let mut config = test_config();
+
+        for (value, expected) in [("1", true), ("0", false), ("YES", true), ("no", false)] {
+            config.override_value("ignore_certs", value, Origin::Env).unwrap();
+            assert_eq!(config.ignore_certs(), expected);
+            config.set("auth_info.ci", Some(value)).unwrap();
+            assert_eq!(config.auth_info.ci(), expected);
+        }
+
+        assert!(config.set("ignore_certs", Some("maybe")).is_err());
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn credential_helper() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let config_path = tempdir.path().join("settings.yaml");
+        let token_path = tempdir.path().join("token");
+
+        let mut config = test_config();
+        config.path = Some(config_path.clone());
+        config.auth_info.env_token = None;
+        let helper =
+            format!("f() {{ [ \"$1\" = store ] && cat > {0}; cat {0}; }}; f", token_path.display());
+        config.set("auth_info.credential_helper", Some(&helper)).unwrap();
+
+        config.auth_info.set_offline_access(RefreshToken::new("HELPER TOKEN")).unwrap();
+        config.save().unwrap();
+
+        // Tokens are only stored by the helper.
+        let saved: Config = parse_config(&config_path).unwrap();
+        assert_eq!(saved.auth_info.offline_access, None);
+        assert!(fs::read_to_string(&token_path).unwrap().contains("token=HELPER TOKEN"));
+
+        assert_eq!(saved.auth_info.offline_access(), Some(&RefreshToken::new("HELPER TOKEN")));
+        assert_eq!(saved.origin(API_KEY_SETTING).unwrap(), Origin::CredentialHelper);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn credential_helper_uri() {
+        let mut config = test_config();
+        config.auth_info.env_token = None;
+        config.auth_info.offline_access = None;
+        config
+            .set("auth_info.credential_helper", Some("f() { echo token=\"$(cat)\"; }; f"))
+            .unwrap();
+        config.identify_credentials();
+
+        let token = config.auth_info.offline_access().unwrap().as_str().to_owned();
+        assert_eq!(token, format!("uri={LOCALHOST}"));
+
+        // Changing the instance resolves its own token.
+        config.set("connection.uri", Some("https://api.example.com")).unwrap();
+        let token = config.auth_info.offline_access().unwrap().as_str().to_owned();
+        assert_eq!(token, "uri=https://api.example.com");
+    }
+
     #[test]
     fn prefer_env_token() {
         let auth = AuthInfo {
             offline_access: Some(RefreshToken::new(CONFIG_TOKEN)),
             env_token: Some(RefreshToken::new(ENV_TOKEN)),
+            ..Default::default()
         };
         assert_eq!(auth.offline_access(), Some(&RefreshToken::new(ENV_TOKEN)));
     }
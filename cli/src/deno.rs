@@ -1,8 +1,11 @@
 //! Deno runtime for extensions.
 
 use std::borrow::Cow;
+use std::fs::File;
+use std::io::BufReader;
 use std::path::{Path, PathBuf};
 use std::rc::Rc;
+use std::sync::Arc;
 
 use anyhow::{anyhow, Context, Error, Result};
 use console::style;
@@ -17,12 +20,14 @@ use deno_core::{
 };
 use deno_runtime::deno_core::error::JsError;
 use deno_runtime::deno_core::{
-    Extension, ModuleLoader, ModuleSource, ModuleSpecifier, ModuleType, ResolutionKind,
+    Extension, ModuleLoader, ModuleSource, ModuleSpecifier, ModuleType, OpState, ResolutionKind,
     SourceMapGetter,
 };
+use deno_runtime::deno_tls::rustls::{Certificate, RootCertStore};
+use deno_runtime::deno_tls::{self, Proxy, RootCertStoreProvider, TlsKey};
 use deno_runtime::permissions::{Permissions, PermissionsContainer, PermissionsOptions};
 use deno_runtime::worker::{MainWorker, WorkerOptions};
-use deno_runtime::{fmt_errors, BootstrapOptions};
+use deno_runtime::{deno_fetch, fmt_errors, BootstrapOptions};
 use futures::future::BoxFuture;
 use tokio::fs;
 use url::Url;
@@ -31,6 +36,9 @@ use crate::api::PhylumApi;
 use crate::commands::extensions::state::ExtensionState;
 use crate::commands::extensions::{api, extension};
 use crate::commands::{CommandResult, ExitCode};
+use crate::config::{Config, ConnectionInfo};
+use crate::error::{self, ErrorFormat};
+use crate::http;
 
 /// Internal extension module that creates global Phylum object.
 const EXTENSION_API: &[ExtensionFileSource] = &include_js_files!(
@@ -49,9 +57,13 @@ export const ApiVersion = Phylum.ApiVersion;
 /// Execute Phylum extension.
 pub async fn run(
     api: BoxFuture<'static, Result<PhylumApi>>,
+    config: &Config,
     extension: extension::Extension,
     args: Vec<String>,
 ) -> CommandResult {
+    let fetch_settings = FetchSettings::new(&config.connection)?;
+    let http_client = http::client_builder(&config.connection, config.ignore_certs())?.build()?;
+
     let state = ExtensionState::new(api, extension.clone());
     let phylum_api = Extension {
         name: "phylum-ext",
@@ -62,7 +74,10 @@ pub async fn run(
         esm_files: Cow::Borrowed(EXTENSION_API),
         esm_entry_point: Some("ext:phylum_api/main.js"),
         ops: api::api_decls().into(),
-        op_state_fn: Some(Box::new(|deno_state| deno_state.put(state))),
+        op_state_fn: Some(Box::new(|deno_state| {
+            fetch_settings.apply(deno_state);
+            deno_state.put(state);
+        })),
         ..Default::default()
     };
 
@@ -72,7 +87,7 @@ pub async fn run(
     let bootstrap =
         BootstrapOptions { args, user_agent: "phylum-cli/extension".into(), ..Default::default() };
 
-    let module_loader = Rc::new(ExtensionsModuleLoader::new(extension.path()));
+    let module_loader = Rc::new(ExtensionsModuleLoader::new(extension.path(), http_client));
     let source_map_getter: Rc<dyn SourceMapGetter> = Rc::new(module_loader.clone());
 
     let origin_storage_dir = extension.state_path();
@@ -106,6 +121,85 @@ pub async fn run(
     Ok(ExitCode::Ok)
 }
 
+/// Connection settings for the extensions' `fetch` API.
+struct FetchSettings {
+    root_cert_store: Option<RootCertStore>,
+    proxy: Option<Proxy>,
+    client_key: Option<TlsKey>,
+}
+
+impl FetchSettings {
+    fn new(connection: &ConnectionInfo) -> Result<Self> {
+        // Deno has no support for `NO_PROXY` with explicitly configured
+        // proxies, so `no_proxy` cannot be applied here.
+        let proxy = connection.proxy.clone().map(|url| Proxy { url, basic_auth: None });
+
+        let root_cert_store = match &connection.ca_bundle {
+            Some(ca_bundle) => {
+                let mut root_cert_store = deno_tls::create_default_root_cert_store();
+                for certificate in load_certs(ca_bundle)? {
+                    root_cert_store.add(&certificate).map_err(|err| {
+                        anyhow!("Invalid certificate in CA bundle {:?}: {err}", ca_bundle.display())
+                    })?;
+                }
+                Some(root_cert_store)
+            },
+            None => None,
+        };
+
+        let client_key = match &connection.client_cert {
+            Some(cert_path) => {
+                let certificates = load_certs(cert_path)?;
+
+                // The private key may be part of the certificate file.
+                let key_path = connection.client_key.as_deref().unwrap_or(cert_path);
+                let key_pem = std::fs::read(key_path).with_context(|| {
+                    format!("Could not read client key {:?}", key_path.display())
+                })?;
+                let key = deno_tls::load_private_keys(&key_pem)?
+                    .into_iter()
+                    .next()
+                    .ok_or_else(|| anyhow!("No private key in {:?}", key_path.display()))?;
+
+                Some(TlsKey(certificates, key))
+            },
+            None => None,
+        };
+
+        Ok(Self { root_cert_store, proxy, client_key })
+    }
+
+    /// Override Deno's default `fetch` options.
+    fn apply(self, deno_state: &mut OpState) {
+        let options = deno_state.borrow_mut::<deno_fetch::Options>();
+        if let Some(root_cert_store) = self.root_cert_store {
+            options.root_cert_store_provider = Some(Arc::new(StaticRootCertStore(root_cert_store)));
+        }
+        if self.proxy.is_some() {
+            options.proxy = self.proxy;
+        }
+        if self.client_key.is_some() {
+            options.client_cert_chain_and_key = self.client_key;
+        }
+    }
+}
+
+/// Root certificate store with the user's CA bundle.
+struct StaticRootCertStore(RootCertStore);
+
+impl RootCertStoreProvider for StaticRootCertStore {
+    fn get_or_try_init(&self) -> Result<&RootCertStore> {
+        Ok(&self.0)
+    }
+}
+
+/// Load all certificates from a PEM file.
+fn load_certs(path: &Path) -> Result<Vec<Certificate>> {
+    let file = File::open(path)
+        .with_context(|| format!("Could not read certificates {:?}", path.display()))?;
+    deno_tls::load_certs(&mut BufReader::new(file))
+}
+
 /// Pretty-print an anyhow error as Deno JS error.
 fn print_js_error(error: Error) -> CommandResult {
     let js_error: JsError = error.downcast::<JsError>()?;
@@ -119,7 +213,13 @@ fn print_js_error(error: Error) -> CommandResult {
         return Err(anyhow!(message.to_owned()));
     }
 
-    eprintln!("{}: {}", style("Extension error").red(), fmt_errors::format_js_error(&js_error));
+    match error::format() {
+        ErrorFormat::Text => {
+            let message = fmt_errors::format_js_error(&js_error);
+            eprintln!("{}: {}", style("Extension error").red(), message);
+        },
+        ErrorFormat::Json => error::print_failure(js_error.exception_message),
+    }
 
     Ok(ExitCode::JsError)
 }
@@ -128,13 +228,15 @@ fn print_js_error(error: Error) -> CommandResult {
 struct ExtensionsModuleLoader {
     extension_path: Rc<PathBuf>,
     source_mapper: Rc<SourceMapper>,
+    http_client: reqwest::Client,
 }
 
 impl ExtensionsModuleLoader {
-    fn new(extension_path: PathBuf) -> Self {
+    fn new(extension_path: PathBuf, http_client: reqwest::Client) -> Self {
         Self {
             extension_path: Rc::new(extension_path),
             source_mapper: Rc::new(SourceMapper::new()),
+            http_client,
         }
     }
 
@@ -158,8 +260,8 @@ impl ExtensionsModuleLoader {
         Ok(fs::read_to_string(path).await?)
     }
 
-    async fn load_from_remote(path: &Url) -> Result<String> {
-        let response = reqwest::get(path.clone()).await?;
+    async fn load_from_remote(http_client: &reqwest::Client, path: &Url) -> Result<String> {
+        let response = http_client.get(path.clone()).send().await?;
         Ok(response.text().await?)
     }
 }
@@ -188,6 +290,7 @@ impl ModuleLoader for ExtensionsModuleLoader {
         let module_specifier = module_specifier.clone();
         let extension_path = self.extension_path.clone();
         let source_mapper = self.source_mapper.clone();
+        let http_client = self.http_client.clone();
 
         ModuleLoadResponse::Async(Box::pin(async move {
             // Inject Phylum API module.
@@ -224,7 +327,10 @@ impl ModuleLoader for ExtensionsModuleLoader {
                     ExtensionsModuleLoader::load_from_filesystem(&extension_path, &module_specifier)
                         .await?
                 },
-                "https" => ExtensionsModuleLoader::load_from_remote(&module_specifier).await?,
+                "https" => {
+                    ExtensionsModuleLoader::load_from_remote(&http_client, &module_specifier)
+                        .await?
+                },
                 _ => return Err(anyhow!("Unsupported module specifier: {}", module_specifier)),
             };
 
@@ -0,0 +1,419 @@
+//! Reporting of command failures.
+//!
+//! Failures are printed as human-readable messages by default. Using
+//! `--error-format json`, the failure which terminated the CLI is instead
+//! written to stderr as a single-line JSON object, identified by the name of
+//! its exit code.
+
+use std::error::Error as StdError;
+use std::ffi::OsString;
+use std::fmt::Display;
+use std::sync::Mutex;
+use std::{io, mem};
+
+use clap::error::ErrorKind as ClapErrorKind;
+use once_cell::sync::OnceCell;
+use phylum_api::ApiError;
+use phylum_lockfile::ParseError;
+use serde::Serialize;
+
+use crate::commands::ExitCode;
+
+/// Format used for reporting failures.
+static ERROR_FORMAT: OnceCell<ErrorFormat> = OnceCell::new();
+
+/// Failure messages buffered until exit when reporting failures as JSON.
+static FAILURES: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+/// Output format for failures.
+#[derive(Copy, Clone, PartialEq, Eq, Default, Debug)]
+pub enum ErrorFormat {
+    #[default]
+    Text,
+    Json,
+}
+
+impl ErrorFormat {
+    /// Parse an error format from its CLI argument.
+    pub fn from_arg(arg: Option<&str>) -> Self {
+        match arg {
+            Some("json") => Self::Json,
+            _ => Self::Text,
+        }
+    }
+
+    /// Parse an error format from unparsed CLI arguments.
+    ///
+    /// This allows reporting failures when the arguments are invalid.
+    pub fn from_raw_args(args: impl IntoIterator<Item = OsString>) -> Self {
+        let mut args = args.into_iter();
+        let mut format = None;
+
+        while let Some(arg) = args.next() {
+            let arg = arg.to_string_lossy();
+            if arg == "--" {
+                break;
+            } else if arg == "--error-format" {
+                format = args.next().map(|value| value.to_string_lossy().into_owned());
+            } else if let Some(value) = arg.strip_prefix("--error-format=") {
+                format = Some(value.into());
+            }
+        }
+
+        Self::from_arg(format.as_deref())
+    }
+}
+
+/// Set the format used for all failures.
+///
+/// The format can only be set once, later calls are ignored.
+pub fn set_format(format: ErrorFormat) {
+    let _ = ERROR_FORMAT.set(format);
+}
+
+/// Format used for reporting failures.
+pub fn format() -> ErrorFormat {
+    ERROR_FORMAT.get().copied().unwrap_or_default()
+}
+
+/// Report a failure message to the user.
+///
+/// When reporting failures as JSON, the message is held back until the CLI
+/// exits and then becomes part of the JSON error.
+pub fn print_failure(message: impl Display) {
+    match format() {
+        ErrorFormat::Text => eprintln!("❗ {message}"),
+        ErrorFormat::Json => FAILURES.lock().unwrap().push(message.to_string()),
+    }
+}
+
+/// Report the result of a command and terminate the application.
+pub fn exit(result: anyhow::Result<ExitCode>) -> ! {
+    let failures = mem::take(&mut *FAILURES.lock().unwrap());
+
+    let exit_code = match result {
+        Ok(exit_code) => {
+            match ErrorReport::from_exit_code(&exit_code, failures) {
+                Ok(report) if format() == ErrorFormat::Json => report.print(),
+                Ok(_) => (),
+                // Failures which did not terminate the command are just messages.
+                Err(failures) => {
+                    for failure in failures {
+                        eprintln!("❗ {failure}");
+                    }
+                },
+            }
+            exit_code
+        },
+        Err(error) => {
+            match format() {
+                ErrorFormat::Text => eprintln!("❗ Error: {error:?}"),
+                ErrorFormat::Json => {
+                    let mut report = ErrorReport::from_error(&error, &ExitCode::Generic);
+                    report.messages = failures;
+                    report.print();
+                },
+            }
+            ExitCode::Generic
+        },
+    };
+
+    exit_code.exit()
+}
+
+/// Report invalid CLI arguments and terminate the application.
+///
+/// Help and version requests are printed by clap as usual.
+pub fn exit_usage(error: clap::Error) -> ! {
+    match error.kind() {
+        ClapErrorKind::DisplayHelp
+        | ClapErrorKind::DisplayVersion
+        | ClapErrorKind::DisplayHelpOnMissingArgumentOrSubcommand => error.exit(),
+        _ => (),
+    }
+
+    match format() {
+        ErrorFormat::Text => {
+            let _ = error.print();
+        },
+        ErrorFormat::Json => print_failure(usage_message(&error)),
+    }
+
+    exit(Ok(ExitCode::Usage))
+}
+
+/// Get the first line of a clap error, without its `error:` prefix.
+fn usage_message(error: &clap::Error) -> String {
+    let rendered = error.render().to_string();
+    let message = rendered.lines().next().unwrap_or_default();
+    message.strip_prefix("error: ").unwrap_or(message).into()
+}
+
+/// Category of a failure.
+#[derive(Serialize, Copy, Clone, PartialEq, Eq, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorKind {
+    /// Invalid command-line arguments.
+    Usage,
+    /// Phylum API request failure.
+    Api,
+    /// Missing or invalid authentication.
+    Auth,
+    /// Dependency file parsing failure.
+    Parse,
+    /// Dependencies failed the project's policy.
+    Policy,
+    /// Dependencies failed a local check.
+    Check,
+    /// Sandboxed process failure.
+    Sandbox,
+    /// Extension JavaScript error.
+    Js,
+    /// Filesystem or other I/O failure.
+    Io,
+    Other,
+}
+
+impl From<&ExitCode> for ErrorKind {
+    fn from(exit_code: &ExitCode) -> Self {
+        match exit_code {
+            ExitCode::Usage => Self::Usage,
+            ExitCode::NotAuthenticated
+            | ExitCode::AuthenticationFailure
+            | ExitCode::InvalidTokenExpiration => Self::Auth,
+            ExitCode::PackageNotFound
+            | ExitCode::AlreadyExists
+            | ExitCode::NoHistoryFound
+            | ExitCode::NotFound
+            | ExitCode::MissingOrg => Self::Api,
+            ExitCode::ManifestWithoutGeneration | ExitCode::UnknownManifestFormat => Self::Parse,
+            ExitCode::FailedPolicy => Self::Policy,
+            ExitCode::LockfileDrift
+            | ExitCode::InstalledMismatch
+            | ExitCode::LintFailure
+            | ExitCode::StaleSuppressions => Self::Check,
+            ExitCode::SandboxStart | ExitCode::SandboxStartCollision => Self::Sandbox,
+            ExitCode::JsError => Self::Js,
+            ExitCode::Ok
+            | ExitCode::Generic
+            | ExitCode::ConfirmationFailed
+            | ExitCode::Custom(_) => Self::Other,
+        }
+    }
+}
+
+/// Machine-readable failure.
+#[derive(Serialize, Debug)]
+pub struct ErrorReport {
+    /// Name of the exit code.
+    pub code: &'static str,
+    pub exit_code: i32,
+    pub kind: ErrorKind,
+    pub message: String,
+    /// HTTP status of failed API requests.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<u16>,
+    /// Chain of errors which caused the failure.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub causes: Vec<String>,
+    /// Additional failure messages reported before the error.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub messages: Vec<String>,
+}
+
+impl ErrorReport {
+    /// Create a report for an error propagated out of a command.
+    pub fn from_error(error: &anyhow::Error, exit_code: &ExitCode) -> Self {
+        // Use the outermost error with a known category.
+        let (kind, status) =
+            error.chain().find_map(classify).unwrap_or_else(|| (ErrorKind::from(exit_code), None));
+
+        Self {
+            code: exit_code.name(),
+            exit_code: exit_code.into(),
+            kind,
+            message: error.to_string(),
+            status,
+            causes: error.chain().skip(1).map(|cause| cause.to_string()).collect(),
+            messages: Vec::new(),
+        }
+    }
+
+    /// Create a report for a command's exit code.
+    ///
+    /// Returns the reported failure messages if the exit code does not indicate
+    /// a failure.
+    pub fn from_exit_code(
+        exit_code: &ExitCode,
+        mut messages: Vec<String>,
+    ) -> Result<Self, Vec<String>> {
+        // Custom exit codes are forwarded from other processes, so they are only
+        // reported if the CLI itself indicated a failure.
+        match exit_code {
+            ExitCode::Ok => return Err(messages),
+            ExitCode::Custom(_) if messages.is_empty() => return Err(messages),
+            _ => (),
+        }
+
+        // Use the last failure as primary message, since it caused the exit.
+        let message = messages.pop().unwrap_or_else(|| default_message(exit_code).into());
+
+        Ok(Self {
+            code: exit_code.name(),
+            exit_code: exit_code.into(),
+            kind: exit_code.into(),
+            message,
+            status: None,
+            causes: Vec::new(),
+            messages,
+        })
+    }
+
+    /// Write the report to stderr.
+    fn print(&self) {
+        match serde_json::to_string(self) {
+            Ok(json) => eprintln!("{json}"),
+            Err(err) => eprintln!("❗ Error: {} ({err})", self.message),
+        }
+    }
+}
+
+/// Determine the category of an error.
+fn classify(error: &(dyn StdError + 'static)) -> Option<(ErrorKind, Option<u16>)> {
+    let (kind, status) = if let Some(error) = error.downcast_ref::<phylum_api::Error>() {
+        (ErrorKind::Api, error.status())
+    } else if let Some(error) = error.downcast_ref::<ApiError>() {
+        (ErrorKind::Api, Some(error.status))
+    } else if let Some(error) = error.downcast_ref::<reqwest::Error>() {
+        (ErrorKind::Api, error.status())
+    } else if error.is::<ParseError>() {
+        (ErrorKind::Parse, None)
+    } else if error.is::<io::Error>() {
+        (ErrorKind::Io, None)
+    } else if is_js_error(error) {
+        (ErrorKind::Js, None)
+    } else {
+        return None;
+    };
+
+    let status = status.map(|status| status.as_u16());
+    let kind = match status {
+        Some(401 | 403) => ErrorKind::Auth,
+        _ => kind,
+    };
+
+    Some((kind, status))
+}
+
+#[cfg(feature = "extensions")]
+fn is_js_error(error: &(dyn StdError + 'static)) -> bool {
+    error.is::<deno_runtime::deno_core::error::JsError>()
+}
+
+#[cfg(not(feature = "extensions"))]
+fn is_js_error(_error: &(dyn StdError + 'static)) -> bool {
+    false
+}
+
+/// Description of an exit code without a failure message.
+fn default_message(exit_code: &ExitCode) -> &'static str {
+    match exit_code {
+        ExitCode::Ok => "Success",
+        ExitCode::Generic => "Command failed",
+        ExitCode::Usage => "Invalid command-line arguments",
+        ExitCode::NotAuthenticated => "User is not authenticated",
+        ExitCode::AuthenticationFailure => "Authentication failed",
+        ExitCode::PackageNotFound => "Package not found",
+        ExitCode::AlreadyExists => "Resource already exists",
+        ExitCode::NoHistoryFound => "No job history found",
+        ExitCode::JsError => "Extension failed with a JavaScript error",
+        ExitCode::ConfirmationFailed => "Confirmation failed",
+        ExitCode::NotFound => "Resource not found",
+        ExitCode::InvalidTokenExpiration => "Invalid token expiration",
+        ExitCode::ManifestWithoutGeneration => "Manifest requires lockfile generation",
+        ExitCode::UnknownManifestFormat => "Unknown manifest format",
+        ExitCode::MissingOrg => "Missing organization",
+        ExitCode::LockfileDrift => "Lockfile drifted from its manifest",
+        ExitCode::InstalledMismatch => "Installed dependencies do not match the lockfile",
+        ExitCode::LintFailure => "Lint findings exceed the configured strictness",
+        ExitCode::StaleSuppressions => "Suppressions have expired",
+        ExitCode::FailedPolicy => "Dependencies failed the project's policy",
+        ExitCode::SandboxStart => "Sandboxed process failed to start",
+        ExitCode::SandboxStartCollision => "Sandboxed process exited with a reserved exit code",
+        ExitCode::Custom(_) => "Command failed",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Context;
+    use reqwest::StatusCode;
+
+    use super::*;
+
+    #[test]
+    fn api_error_report() {
+        let error =
+            ApiError { status: StatusCode::NOT_FOUND, error_id: None, message: "missing".into() };
+        let error = Err::<(), _>(phylum_api::Error::from(error))
+            .context("Could not fetch project")
+            .unwrap_err();
+
+        let report = serde_json::to_value(ErrorReport::from_error(&error, &ExitCode::Generic));
+        assert_eq!(
+            report.unwrap(),
+            serde_json::json!({
+                "code": "generic",
+                "exit_code": 1,
+                "kind": "api",
+                "message": "Could not fetch project",
+                "status": 404,
+                "causes": ["HTTP request error (404 Not Found):\n\nmissing"],
+            })
+        );
+    }
+
+    #[test]
+    fn parse_error_report() {
+        let error = anyhow::Error::from(ParseError::UnknownManifestFormat("setup.py".into()));
+        let report = ErrorReport::from_error(&error, &ExitCode::Generic);
+        assert_eq!(report.kind, ErrorKind::Parse);
+        assert_eq!(report.status, None);
+
+        let messages = vec!["Lookup failed".into(), "Could not parse manifest".into()];
+        let report = ErrorReport::from_exit_code(&ExitCode::UnknownManifestFormat, messages);
+        let report = report.unwrap();
+        assert_eq!(report.code, "unknown_manifest_format");
+        assert_eq!(report.exit_code, 21);
+        assert_eq!(report.kind, ErrorKind::Parse);
+        assert_eq!(report.message, "Could not parse manifest");
+        assert_eq!(report.messages, ["Lookup failed"]);
+    }
+
+    #[test]
+    fn forwarded_exit_code() {
+        assert!(ErrorReport::from_exit_code(&ExitCode::Custom(3), Vec::new()).is_err());
+        assert!(ErrorReport::from_exit_code(&ExitCode::Ok, vec!["Skipped".into()]).is_err());
+
+        let report = ErrorReport::from_exit_code(&ExitCode::SandboxStart, Vec::new()).unwrap();
+        assert_eq!(report.kind, ErrorKind::Sandbox);
+        assert_eq!(report.message, "Sandboxed process failed to start");
+    }
+
+    #[test]
+    fn raw_error_format() {
+        let format = |args: &[&str]| ErrorFormat::from_raw_args(args.iter().map(OsString::from));
+
+        assert_eq!(format(&["phylum", "--error-format", "json", "--unknown"]), ErrorFormat::Json);
+        assert_eq!(format(&["phylum", "analyze", "--error-format=json"]), ErrorFormat::Json);
+        assert_eq!(format(&["phylum", "--error-format"]), ErrorFormat::Text);
+        assert_eq!(format(&["phylum", "--", "--error-format=json"]), ErrorFormat::Text);
+    }
+
+    #[test]
+    fn usage_error_message() {
+        let error =
+            clap::Command::new("phylum").try_get_matches_from(["phylum", "--unknown"]).unwrap_err();
+        assert_eq!(usage_message(&error), "unexpected argument '--unknown' found");
+    }
+}
@@ -5,6 +5,10 @@ use std::{cmp, str};
 
 use chrono::{DateTime, Local, Utc};
 use console::style;
+use phylum_lockfile::confusion::ConfusionFinding;
+use phylum_lockfile::drift::LockfileDrift;
+use phylum_lockfile::installed::InstalledDiff;
+use phylum_lockfile::lint::Severity;
 use phylum_types::types::group::{GroupMember, ListGroupMembersResponse};
 use phylum_types::types::job::{AllJobsStatusResponse, JobDescriptor};
 use phylum_types::types::package::{PackageStatus, PackageStatusExtended};
@@ -15,9 +19,15 @@ use unicode_width::UnicodeWidthStr;
 #[cfg(feature = "vulnreach")]
 use vulnreach_types::Vulnerability;
 
+use crate::commands::config::{ProfileListEntry, SettingListEntry};
 use crate::commands::group::ListGroupsEntry;
+use crate::commands::lint::LintReport;
 use crate::commands::status::PhylumStatus;
+use crate::commands::suppressions::{AuditStatus, SuppressionsAudit};
 use crate::print::{self, table_format};
+use crate::report::gitlab::GitlabReport;
+use crate::report::sarif::SarifLog;
+use crate::report::{html, junit, markdown, AnalysisReport};
 use crate::types::{
     GetProjectResponse, HistoryJob, Issue, OrgMember, OrgMembersResponse, OrgsResponse, Package,
     PolicyEvaluationResponse, PolicyEvaluationResponseRaw, ProjectListEntry, RiskLevel, UserToken,
@@ -26,6 +36,54 @@ use crate::types::{
 // Maximum length of email column.
 const MAX_EMAIL_WIDTH: usize = 25;
 
+/// Output format selected through `--format`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum OutputFormat {
+    Pretty,
+    Json,
+    Sarif,
+    Junit,
+    Gitlab,
+    Markdown,
+    Html,
+}
+
+impl OutputFormat {
+    /// All accepted `--format` values.
+    pub const NAMES: [&'static str; 7] =
+        ["pretty", "json", "sarif", "junit", "gitlab", "markdown", "html"];
+
+    /// Get the `--format` value of this output format.
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Self::Pretty => "pretty",
+            Self::Json => "json",
+            Self::Sarif => "sarif",
+            Self::Junit => "junit",
+            Self::Gitlab => "gitlab",
+            Self::Markdown => "markdown",
+            Self::Html => "html",
+        }
+    }
+
+    /// Get the output format selected through `--json` or `--format`.
+    pub fn from_matches(matches: &clap::ArgMatches) -> Self {
+        if matches.get_flag("json") {
+            return Self::Json;
+        }
+
+        match matches.get_one::<String>("format").map(String::as_str) {
+            Some("json") => Self::Json,
+            Some("sarif") => Self::Sarif,
+            Some("junit") => Self::Junit,
+            Some("gitlab") => Self::Gitlab,
+            Some("markdown") => Self::Markdown,
+            Some("html") => Self::Html,
+            _ => Self::Pretty,
+        }
+    }
+}
+
 /// Format type for CLI output.
 pub trait Format: Serialize {
     /// Output JSON format.
@@ -45,6 +103,31 @@ pub trait Format: Serialize {
         self.pretty(writer);
     }
 
+    /// Output SARIF format.
+    fn sarif<W: Write>(&self, _writer: &mut W) -> anyhow::Result<()> {
+        Err(unsupported_format(OutputFormat::Sarif))
+    }
+
+    /// Output JUnit XML format.
+    fn junit<W: Write>(&self, _writer: &mut W) -> anyhow::Result<()> {
+        Err(unsupported_format(OutputFormat::Junit))
+    }
+
+    /// Output GitLab dependency scanning report format.
+    fn gitlab<W: Write>(&self, _writer: &mut W) -> anyhow::Result<()> {
+        Err(unsupported_format(OutputFormat::Gitlab))
+    }
+
+    /// Output Markdown format.
+    fn markdown<W: Write>(&self, _writer: &mut W) -> anyhow::Result<()> {
+        Err(unsupported_format(OutputFormat::Markdown))
+    }
+
+    /// Output standalone HTML format.
+    fn html<W: Write>(&self, _writer: &mut W) -> anyhow::Result<()> {
+        Err(unsupported_format(OutputFormat::Html))
+    }
+
     /// Output to stdout.
     fn write_stdout(&self, pretty: bool) {
         if pretty {
@@ -53,6 +136,26 @@ pub trait Format: Serialize {
             self.json(&mut io::stdout());
         }
     }
+
+    /// Output to stdout using the selected format.
+    fn write_format(&self, format: OutputFormat) -> anyhow::Result<()> {
+        let mut stdout = io::stdout();
+        match format {
+            OutputFormat::Pretty => self.pretty(&mut stdout),
+            OutputFormat::Json => self.json(&mut stdout),
+            OutputFormat::Sarif => self.sarif(&mut stdout)?,
+            OutputFormat::Junit => self.junit(&mut stdout)?,
+            OutputFormat::Gitlab => self.gitlab(&mut stdout)?,
+            OutputFormat::Markdown => self.markdown(&mut stdout)?,
+            OutputFormat::Html => self.html(&mut stdout)?,
+        }
+        Ok(())
+    }
+}
+
+/// Error for output formats without support for the current output.
+fn unsupported_format(format: OutputFormat) -> anyhow::Error {
+    anyhow::anyhow!("Output format {:?} is not supported for this command", format.name())
 }
 
 impl Format for PhylumStatus {
@@ -99,64 +202,130 @@ impl Format for PolicyEvaluationResponse {
 
 impl Format for PolicyEvaluationResponseRaw {
     fn pretty<W: Write>(&self, writer: &mut W) {
-        let _ = writeln!(writer);
+        write_policy_status(writer, self, &[]);
+    }
+}
 
-        // Print summary status.
-        let status = if self.is_failure {
-            style("FAILURE").red()
-        } else if self.incomplete_packages_count > 0 {
-            style("INCOMPLETE").yellow()
-        } else {
-            style("SUCCESS").green()
-        };
-        let _ = writeln!(writer, "Phylum Supply Chain Risk Analysis — {status}\n");
-
-        // Print number of unprocessed packages.
-        if self.incomplete_packages_count > 0 {
-            let pluralization = if self.incomplete_packages_count == 1 { "" } else { "s" };
-            let unprocessed_text =
-                format!("{} unprocessed package{}", self.incomplete_packages_count, pluralization);
-            let incomplete_message = format!(
-                "The analysis contains {}, preventing a complete risk analysis. Phylum is \
-                 currently processing these packages and should complete soon. Please wait for up \
-                 to 30 minutes, then re-run the analysis.\n",
-                style(unprocessed_text).yellow(),
-            );
-            let _ = writeln!(writer, "{}", textwrap::fill(&incomplete_message, 80));
-        }
-
-        // Write summary for each issue.
-        for package in self
-            .dependencies
-            .iter()
-            .filter(|package| package.rejections.iter().any(|rejection| !rejection.suppressed))
-        {
-            let _ = writeln!(writer, "[{}] {}@{}", package.registry, package.name, package.version);
-
-            for rejection in package.rejections.iter().filter(|rejection| !rejection.suppressed) {
-                let domain = rejection
-                    .source
-                    .domain
-                    .map_or_else(|| "     ".into(), |domain| format!("[{}]", domain));
-                let message = format!("{domain} {}", rejection.title);
-
-                let colored = match rejection.source.severity {
-                    Some(RiskLevel::Low) | Some(RiskLevel::Info) => style(message).green(),
-                    Some(RiskLevel::Medium) => style(message).yellow(),
-                    _ => style(message).red(),
-                };
+/// Write policy evaluation results together with local dependency confusion
+/// findings.
+fn write_policy_status<W: Write>(
+    writer: &mut W,
+    status: &PolicyEvaluationResponseRaw,
+    dependency_confusion: &[ConfusionFinding],
+) {
+    let _ = writeln!(writer);
+
+    // Print summary status.
+    let result = if status.is_failure {
+        style("FAILURE").red()
+    } else if status.incomplete_packages_count > 0 {
+        style("INCOMPLETE").yellow()
+    } else {
+        style("SUCCESS").green()
+    };
+    let _ = writeln!(writer, "Phylum Supply Chain Risk Analysis — {result}\n");
+
+    // Print number of unprocessed packages.
+    if status.incomplete_packages_count > 0 {
+        let pluralization = if status.incomplete_packages_count == 1 { "" } else { "s" };
+        let unprocessed_text =
+            format!("{} unprocessed package{}", status.incomplete_packages_count, pluralization);
+        let incomplete_message = format!(
+            "The analysis contains {}, preventing a complete risk analysis. Phylum is currently \
+             processing these packages and should complete soon. Please wait for up to 30 \
+             minutes, then re-run the analysis.\n",
+            style(unprocessed_text).yellow(),
+        );
+        let _ = writeln!(writer, "{}", textwrap::fill(&incomplete_message, 80));
+    }
 
-                let _ = writeln!(writer, "  {}", colored);
-            }
-        }
-        if !self.dependencies.is_empty() {
-            let _ = writeln!(writer);
+    // Write summary for each issue.
+    for package in status
+        .dependencies
+        .iter()
+        .filter(|package| package.rejections.iter().any(|rejection| !rejection.suppressed))
+    {
+        let _ = writeln!(writer, "[{}] {}@{}", package.registry, package.name, package.version);
+
+        for rejection in package.rejections.iter().filter(|rejection| !rejection.suppressed) {
+            let domain = rejection
+                .source
+                .domain
+                .map_or_else(|| "     ".into(), |domain| format!("[{}]", domain));
+            let message = format!("{domain} {}", rejection.title);
+
+            let colored = match rejection.source.severity {
+                Some(RiskLevel::Low) | Some(RiskLevel::Info) => style(message).green(),
+                Some(RiskLevel::Medium) => style(message).yellow(),
+                _ => style(message).red(),
+            };
+
+            let _ = writeln!(writer, "  {}", colored);
         }
+    }
+    if !status.dependencies.is_empty() {
+        let _ = writeln!(writer);
+    }
 
-        // Print web URI for the job results.
-        if let Some(job_link) = &self.job_link {
-            let _ = writeln!(writer, "You can find the interactive report here:\n  {}", job_link);
+    // Write local dependency confusion findings.
+    if !dependency_confusion.is_empty() {
+        let _ = writeln!(writer, "{}", style("Dependency confusion").bold());
+        for finding in dependency_confusion {
+            let registries = finding
+                .registries
+                .iter()
+                .map(|registry| registry.as_deref().unwrap_or("public registry"))
+                .collect::<Vec<_>>()
+                .join(", ");
+            let _ = writeln!(
+                writer,
+                "[{}] {}@{}",
+                finding.package_type,
+                finding.name,
+                finding.public_versions.join(", ")
+            );
+            let message = format!("{} ({registries})", finding.kind.description());
+            let _ = writeln!(writer, "  {}", style(message).red());
         }
+        let _ = writeln!(writer);
+    }
+
+    // Print web URI for the job results.
+    if let Some(job_link) = &status.job_link {
+        let _ = writeln!(writer, "You can find the interactive report here:\n  {}", job_link);
+    }
+}
+
+impl Format for AnalysisReport {
+    fn pretty<W: Write>(&self, writer: &mut W) {
+        write_policy_status(writer, &self.status, &self.dependency_confusion);
+    }
+
+    fn sarif<W: Write>(&self, writer: &mut W) -> anyhow::Result<()> {
+        let sarif = SarifLog::new(self);
+        writeln!(writer, "{}", serde_json::to_string_pretty(&sarif)?)?;
+        Ok(())
+    }
+
+    fn junit<W: Write>(&self, writer: &mut W) -> anyhow::Result<()> {
+        junit::write_report(writer, self)?;
+        Ok(())
+    }
+
+    fn gitlab<W: Write>(&self, writer: &mut W) -> anyhow::Result<()> {
+        let report = GitlabReport::new(self);
+        writeln!(writer, "{}", serde_json::to_string_pretty(&report)?)?;
+        Ok(())
+    }
+
+    fn markdown<W: Write>(&self, writer: &mut W) -> anyhow::Result<()> {
+        write!(writer, "{}", markdown::render(self))?;
+        Ok(())
+    }
+
+    fn html<W: Write>(&self, writer: &mut W) -> anyhow::Result<()> {
+        write!(writer, "{}", html::render(self))?;
+        Ok(())
     }
 }
 
@@ -415,6 +584,171 @@ impl Format for OrgMembersResponse {
     }
 }
 
+impl Format for Vec<SettingListEntry> {
+    fn pretty<W: Write>(&self, writer: &mut W) {
+        type Column = (&'static str, fn(&SettingListEntry) -> String);
+        let mut columns: Vec<Column> = vec![
+            ("Key", |setting| setting.key.clone()),
+            ("Value", |setting| setting.value.clone().unwrap_or_default()),
+        ];
+        if self.iter().any(|setting| setting.origin.is_some()) {
+            columns.push(("Origin", |setting| setting.origin.clone().unwrap_or_default()));
+        }
+
+        let table = format_table(self, &columns);
+        let _ = writeln!(writer, "{table}");
+    }
+}
+
+impl Format for Vec<ProfileListEntry> {
+    fn pretty<W: Write>(&self, writer: &mut W) {
+        let table = format_table::<fn(&ProfileListEntry) -> String, _>(self, &[
+            ("Name", |profile| match profile.in_use {
+                true => format!("{} (in use)", profile.name),
+                false => profile.name.clone(),
+            }),
+            ("URI", |profile| profile.uri.clone()),
+        ]);
+        let _ = writeln!(writer, "{table}");
+    }
+}
+
+impl Format for Vec<LockfileDrift> {
+    fn pretty<W: Write>(&self, writer: &mut W) {
+        for drift in self {
+            let manifest = drift.manifest.display();
+            let lockfile = drift.lockfile.display();
+
+            if drift.is_empty() {
+                let _ = writeln!(writer, "✅ {manifest} is in sync with {lockfile}");
+                continue;
+            }
+
+            let _ = writeln!(writer, "❗ {manifest} has drifted from {lockfile}");
+
+            if !drift.missing.is_empty() {
+                let _ = writeln!(writer, "  {}", style("Missing from lockfile:").blue());
+                for dependency in &drift.missing {
+                    let requirement = dependency.requirement.as_deref().unwrap_or_default();
+                    let _ = writeln!(writer, "    {} {requirement}", dependency.name);
+                }
+            }
+
+            if !drift.out_of_range.is_empty() {
+                let _ = writeln!(writer, "  {}", style("Locked version out of range:").blue());
+                for out_of_range in &drift.out_of_range {
+                    let _ = writeln!(
+                        writer,
+                        "    {} {} (requires {})",
+                        out_of_range.name,
+                        style(out_of_range.locked.join(", ")).red(),
+                        out_of_range.requirement,
+                    );
+                }
+            }
+
+            let no_longer_required = drift.no_longer_required.as_deref().unwrap_or_default();
+            if !no_longer_required.is_empty() {
+                let _ = writeln!(writer, "  {}", style("No longer required:").blue());
+                for name in no_longer_required {
+                    let _ = writeln!(writer, "    {name}");
+                }
+            }
+        }
+    }
+}
+
+impl Format for InstalledDiff {
+    fn pretty<W: Write>(&self, writer: &mut W) {
+        if self.is_empty() {
+            let _ = writeln!(writer, "✅ Installed packages match the lockfile");
+            return;
+        }
+
+        if !self.extra.is_empty() {
+            let _ = writeln!(writer, "{}", style("Installed but not locked:").blue());
+            for package in &self.extra {
+                let path = package.path.display();
+                let _ = writeln!(writer, "  {}@{} ({path})", package.name, package.version);
+            }
+        }
+
+        if !self.missing.is_empty() {
+            let _ = writeln!(writer, "{}", style("Locked but not installed:").blue());
+            for package in &self.missing {
+                let _ = writeln!(writer, "  {}@{}", package.name, package.version);
+            }
+        }
+
+        if !self.mismatched.is_empty() {
+            let _ = writeln!(writer, "{}", style("Installed version not locked:").blue());
+            for mismatch in &self.mismatched {
+                let _ = writeln!(
+                    writer,
+                    "  {} {} (locked {})",
+                    mismatch.name,
+                    style(mismatch.installed.join(", ")).red(),
+                    mismatch.locked.join(", "),
+                );
+            }
+        }
+    }
+}
+
+impl Format for Vec<LintReport> {
+    fn pretty<W: Write>(&self, writer: &mut W) {
+        for report in self {
+            let lockfile = report.lockfile.display();
+            if report.findings.is_empty() {
+                let _ = writeln!(writer, "✅ {lockfile} has no non-registry dependencies");
+                continue;
+            }
+
+            let _ = writeln!(writer, "{}", style(lockfile).blue());
+            for finding in &report.findings {
+                let severity = match finding.severity {
+                    Severity::Info => style("info").green(),
+                    Severity::Warning => style("warning").yellow(),
+                    Severity::Error => style("error").red(),
+                };
+                let source = finding.source.as_deref().unwrap_or_default();
+                let _ = writeln!(
+                    writer,
+                    "  [{severity}] {} — {}: {source}",
+                    finding.name,
+                    finding.kind.description(),
+                );
+            }
+        }
+    }
+}
+
+impl Format for SuppressionsAudit {
+    fn pretty<W: Write>(&self, writer: &mut W) {
+        let path = self.path.display();
+        if self.entries.is_empty() {
+            let _ = writeln!(writer, "✅ {path} has no stale suppressions");
+            return;
+        }
+
+        let _ = writeln!(writer, "{}", style(path).blue());
+        for entry in &self.entries {
+            let status = match entry.status {
+                AuditStatus::Expired => style("expired").red(),
+                AuditStatus::Expiring => style("expiring").yellow(),
+            };
+            let suppression = &entry.suppression;
+            let expires = suppression.expires.map(|date| date.to_string()).unwrap_or_default();
+            let _ = writeln!(
+                writer,
+                "  [{status}] {expires} {} — {}",
+                suppression.description(),
+                suppression.justification,
+            );
+        }
+    }
+}
+
 #[cfg(feature = "vulnreach")]
 impl Format for Vulnerability {
     fn pretty<W: Write>(&self, writer: &mut W) {
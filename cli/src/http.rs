@@ -0,0 +1,44 @@
+//! HTTP client configuration.
+//!
+//! All HTTP clients are created through [`client_builder`], to apply the
+//! user's connection settings consistently.
+
+use anyhow::Result;
+use phylum_api::http::HttpConfig;
+use reqwest::ClientBuilder;
+
+use crate::app::USER_AGENT;
+use crate::config::ConnectionInfo;
+
+/// Convert the user's connection settings for the API client.
+pub fn http_config(connection: &ConnectionInfo, ignore_certs: bool) -> HttpConfig {
+    HttpConfig {
+        user_agent: USER_AGENT.to_string(),
+        ca_bundle: connection.ca_bundle.clone(),
+        proxy: connection.proxy.clone(),
+        no_proxy: connection.no_proxy.clone(),
+        client_cert: connection.client_cert.clone(),
+        client_key: connection.client_key.clone(),
+        accept_invalid_certs: ignore_certs,
+    }
+}
+
+/// Create an HTTP client builder for Phylum's API and authentication servers.
+///
+/// Unlike [`client_builder`], this includes the user's client certificate.
+pub fn api_client_builder(
+    connection: &ConnectionInfo,
+    ignore_certs: bool,
+) -> Result<ClientBuilder> {
+    Ok(http_config(connection, ignore_certs).client_builder()?)
+}
+
+/// Create an HTTP client builder for third-party servers.
+///
+/// The user's client certificate is only meant for Phylum, so it is never
+/// presented to other servers.
+pub fn client_builder(connection: &ConnectionInfo, ignore_certs: bool) -> Result<ClientBuilder> {
+    let config =
+        HttpConfig { client_cert: None, client_key: None, ..http_config(connection, ignore_certs) };
+    Ok(config.client_builder()?)
+}
@@ -3,17 +3,25 @@ pub use reqwest::Error;
 pub mod api;
 pub mod app;
 pub mod auth;
+pub mod baseline;
+pub mod ci;
 pub mod commands;
 pub mod config;
 #[cfg(feature = "extensions")]
 pub mod deno;
 pub mod dirs;
+pub mod error;
 pub mod filter;
 pub mod format;
 pub mod fs_compare;
+pub mod http;
+pub mod logging;
 pub mod permissions;
 pub mod print;
+pub mod progress;
+pub mod report;
 pub mod spinner;
+pub mod suppressions;
 #[cfg(test)]
 mod test;
 pub mod types;
@@ -0,0 +1,184 @@
+//! Logger initialization.
+
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::Path;
+use std::sync::{OnceLock, PoisonError, RwLock};
+
+use anyhow::{Context, Result};
+use chrono::{SecondsFormat, Utc};
+use env_logger::{Builder, Env, Logger, Target};
+use log::kv::{self, Key, Value, VisitSource};
+use log::{LevelFilter, Log, Metadata, Record};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value as JsonValue};
+
+/// Output format for log messages.
+#[derive(Serialize, Deserialize, Copy, Clone, PartialEq, Eq, Default, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum LogFormat {
+    #[default]
+    Text,
+    Json,
+}
+
+impl LogFormat {
+    /// Parse a log format from its CLI argument.
+    pub fn from_arg(arg: &str) -> Option<Self> {
+        match arg {
+            "text" => Some(Self::Text),
+            "json" => Some(Self::Json),
+            _ => None,
+        }
+    }
+
+    /// CLI argument for this log format.
+    pub fn as_arg(&self) -> &'static str {
+        match self {
+            Self::Text => "text",
+            Self::Json => "json",
+        }
+    }
+}
+
+/// Global logger, installed by the first call to [`init`].
+static LOGGER: OnceLock<ReloadableLogger> = OnceLock::new();
+
+/// Logger whose configuration can be replaced after installation.
+struct ReloadableLogger {
+    inner: RwLock<Logger>,
+}
+
+impl Log for ReloadableLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        self.inner.read().unwrap_or_else(PoisonError::into_inner).enabled(metadata)
+    }
+
+    fn log(&self, record: &Record) {
+        self.inner.read().unwrap_or_else(PoisonError::into_inner).log(record)
+    }
+
+    fn flush(&self) {
+        self.inner.read().unwrap_or_else(PoisonError::into_inner).flush()
+    }
+}
+
+/// Initialize or reconfigure the global logger.
+///
+/// Without an explicit level, the level is taken from `RUST_LOG`. Logs are
+/// written to stderr unless a log file is specified, which is appended to.
+///
+/// Calling this again replaces the previous configuration, which allows
+/// logging before the configuration file is loaded.
+pub fn init(level: Option<LevelFilter>, format: LogFormat, file: Option<&Path>) -> Result<()> {
+    let mut builder = match level {
+        Some(level) => {
+            let mut builder = Builder::new();
+            builder.filter_level(level);
+            builder
+        },
+        None => Builder::from_env(Env::default().default_filter_or("warn")),
+    };
+
+    if format == LogFormat::Json {
+        builder.format(|buf, record| writeln!(buf, "{}", json_record(record)));
+    }
+
+    if let Some(path) = file {
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .with_context(|| format!("Could not open log file {:?}", path.display()))?;
+        builder.target(Target::Pipe(Box::new(file)));
+    }
+
+    let logger = builder.build();
+    log::set_max_level(logger.filter());
+
+    match LOGGER.get() {
+        Some(installed) => {
+            *installed.inner.write().unwrap_or_else(PoisonError::into_inner) = logger;
+        },
+        None => {
+            let installed = LOGGER.get_or_init(|| ReloadableLogger { inner: RwLock::new(logger) });
+            log::set_logger(installed).context("Could not install logger")?;
+        },
+    }
+
+    Ok(())
+}
+
+/// Convert a log record to a single-line JSON object.
+fn json_record(record: &Record) -> JsonValue {
+    let mut fields = JsonFields(Map::new());
+    let _ = record.key_values().visit(&mut fields);
+
+    json!({
+        "timestamp": Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true),
+        "level": record.level().as_str(),
+        "target": record.target(),
+        "message": record.args().to_string(),
+        "fields": fields.0,
+    })
+}
+
+/// Collector for structured log fields.
+struct JsonFields(Map<String, JsonValue>);
+
+impl<'kvs> VisitSource<'kvs> for JsonFields {
+    fn visit_pair(&mut self, key: Key<'kvs>, value: Value<'kvs>) -> Result<(), kv::Error> {
+        let value = if let Some(value) = value.to_bool() {
+            JsonValue::from(value)
+        } else if let Some(value) = value.to_u64() {
+            JsonValue::from(value)
+        } else if let Some(value) = value.to_i64() {
+            JsonValue::from(value)
+        } else if let Some(value) = value.to_f64() {
+            JsonValue::from(value)
+        } else {
+            JsonValue::from(value.to_string())
+        };
+
+        self.0.insert(key.to_string(), value);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use log::Level;
+
+    use super::*;
+
+    #[test]
+    fn json_fields() {
+        let fields: &[(&str, &dyn kv::ToValue)] =
+            &[("method", &"GET"), ("status", &404u16), ("latency_ms", &12.5)];
+        let record = Record::builder()
+            .level(Level::Debug)
+            .target("phylum_cli::api")
+            .args(format_args!("HTTP request"))
+            .key_values(&fields)
+            .build();
+
+        let json = json_record(&record);
+
+        assert_eq!(json["level"], "DEBUG");
+        assert_eq!(json["target"], "phylum_cli::api");
+        assert_eq!(json["message"], "HTTP request");
+        assert_eq!(json["fields"], json!({ "method": "GET", "status": 404, "latency_ms": 12.5 }));
+        assert!(json["timestamp"].as_str().unwrap().ends_with('Z'));
+    }
+
+    #[test]
+    fn reconfigure() {
+        init(Some(LevelFilter::Error), LogFormat::Text, None).unwrap();
+        assert_eq!(log::max_level(), LevelFilter::Error);
+
+        init(Some(LevelFilter::Debug), LogFormat::Json, None).unwrap();
+        assert_eq!(log::max_level(), LevelFilter::Debug);
+        assert!(log::log_enabled!(log::Level::Debug));
+    }
+}
@@ -25,8 +25,7 @@ macro_rules! print_user_warning {
 #[macro_export]
 macro_rules! print_user_failure {
     ($($tts:tt)*) => {{
-        eprint!("❗ ");
-        eprintln!($($tts)*);
+        $crate::error::print_failure(format_args!($($tts)*));
     }}
 }
 
@@ -0,0 +1,153 @@
+//! Progress reporting for long-running commands.
+//!
+//! Interactive terminals show a [`Spinner`](crate::spinner::Spinner) instead of
+//! progress events. When stderr is not a terminal, events are written as plain
+//! text lines, or as JSON lines using `--progress json`.
+
+use std::fmt::{self, Display, Formatter};
+use std::io::{self, IsTerminal};
+use std::path::Path;
+
+use chrono::{SecondsFormat, Utc};
+use once_cell::sync::OnceCell;
+use serde::Serialize;
+
+/// Progress mode selected through `--progress`.
+static PROGRESS_MODE: OnceCell<ProgressMode> = OnceCell::new();
+
+/// Output mode for progress events.
+#[derive(Copy, Clone, PartialEq, Eq, Default, Debug)]
+pub enum ProgressMode {
+    /// Spinner on a terminal, plain text lines otherwise.
+    #[default]
+    Auto,
+    Text,
+    Json,
+    None,
+}
+
+impl ProgressMode {
+    /// Parse a progress mode from its CLI argument.
+    pub fn from_arg(arg: Option<&str>) -> Self {
+        match arg {
+            Some("text") => Self::Text,
+            Some("json") => Self::Json,
+            Some("none") => Self::None,
+            _ => Self::Auto,
+        }
+    }
+}
+
+/// Set the progress mode for all commands.
+///
+/// The mode can only be set once, later calls are ignored.
+pub fn set_mode(mode: ProgressMode) {
+    let _ = PROGRESS_MODE.set(mode);
+}
+
+/// Progress mode used for all commands.
+pub fn mode() -> ProgressMode {
+    PROGRESS_MODE.get().copied().unwrap_or_default()
+}
+
+/// Check if spinner animations should be drawn.
+pub fn animate() -> bool {
+    mode() == ProgressMode::Auto && io::stderr().is_terminal()
+}
+
+/// Check if progress should be written as plain text lines.
+pub fn text_lines() -> bool {
+    match mode() {
+        ProgressMode::Auto => !io::stderr().is_terminal(),
+        ProgressMode::Text => true,
+        ProgressMode::Json | ProgressMode::None => false,
+    }
+}
+
+/// Report a progress event to the user.
+pub fn report(event: Event) {
+    match mode() {
+        ProgressMode::Json => eprintln!("{}", event.to_json()),
+        _ if text_lines() => eprintln!("{event}"),
+        _ => (),
+    }
+}
+
+/// Progress event.
+#[derive(Serialize, Debug)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum Event<'a> {
+    DepfileDiscovered { path: &'a Path, format: &'a str },
+    ParsingStarted { path: &'a Path },
+    ParsingFinished { path: &'a Path, format: &'a str, packages: usize },
+    GenerationStarted { path: &'a Path },
+    GenerationFinished { path: &'a Path },
+    JobSubmitted { job_id: String, packages: usize },
+    Polling { job_id: String },
+    Done { job_id: String },
+}
+
+impl Event<'_> {
+    /// Convert the event to a single-line JSON object.
+    fn to_json(&self) -> String {
+        #[derive(Serialize)]
+        struct TimedEvent<'a, 'b> {
+            timestamp: String,
+            #[serde(flatten)]
+            event: &'a Event<'b>,
+        }
+
+        let timestamp = Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true);
+        serde_json::to_string(&TimedEvent { timestamp, event: self }).unwrap_or_default()
+    }
+}
+
+impl Display for Event<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::DepfileDiscovered { path, format } => {
+                write!(f, "Found dependency file {:?} of type {format:?}", path.display())
+            },
+            Self::ParsingStarted { path } => write!(f, "Parsing {:?}", path.display()),
+            Self::ParsingFinished { path, format, packages } => {
+                write!(f, "Parsed {packages} packages from {:?} as {format:?}", path.display())
+            },
+            Self::GenerationStarted { path } => {
+                write!(f, "Generating lockfile for {:?}", path.display())
+            },
+            Self::GenerationFinished { path } => {
+                write!(f, "Generated lockfile for {:?}", path.display())
+            },
+            Self::JobSubmitted { job_id, packages } => {
+                write!(f, "Submitted job {job_id} with {packages} packages")
+            },
+            Self::Polling { job_id } => write!(f, "Waiting for results of job {job_id}"),
+            Self::Done { job_id } => write!(f, "Finished job {job_id}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn event_formats() {
+        let event = Event::ParsingFinished {
+            path: Path::new("web/package-lock.json"),
+            format: "npm",
+            packages: 3,
+        };
+
+        let json: serde_json::Value = serde_json::from_str(&event.to_json()).unwrap();
+        assert_eq!(json["event"], "parsing_finished");
+        assert_eq!(json["path"], "web/package-lock.json");
+        assert_eq!(json["packages"], 3);
+        assert!(json["timestamp"].is_string());
+
+        assert_eq!(
+            event.to_string(),
+            "Parsed 3 packages from \"web/package-lock.json\" as \"npm\""
+        );
+    }
+}
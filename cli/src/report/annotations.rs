@@ -0,0 +1,333 @@
+//! Native annotations for CI providers.
+//!
+//! Annotations point at the dependency file which introduced a rejected
+//! package, so they show up inline in the provider's code review UI.
+
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+use crate::ci::CiProvider;
+use crate::report::{domain_name, markdown, AnalysisReport, Depfile};
+use crate::types::RiskLevel;
+
+/// Annotation for a single policy rejection.
+#[derive(Clone, Debug)]
+pub struct Annotation {
+    pub severity: Option<RiskLevel>,
+    pub file: Option<String>,
+    pub line: Option<usize>,
+    pub title: String,
+    pub message: String,
+}
+
+impl Annotation {
+    /// GitHub Actions workflow command.
+    ///
+    /// See <https://docs.github.com/en/actions/using-workflows/workflow-commands-for-github-actions>.
+    pub fn github_command(&self) -> String {
+        let command = match self.severity {
+            Some(RiskLevel::Critical | RiskLevel::High) | None => "error",
+            Some(RiskLevel::Medium) => "warning",
+            Some(RiskLevel::Low | RiskLevel::Info) => "notice",
+        };
+
+        let mut properties = Vec::new();
+        if let Some(file) = &self.file {
+            properties.push(format!("file={}", github_escape_property(file)));
+        }
+        if let Some(line) = self.line {
+            properties.push(format!("line={line}"));
+        }
+        properties.push(format!("title={}", github_escape_property(&self.title)));
+
+        format!("::{command} {}::{}", properties.join(","), github_escape_data(&self.message))
+    }
+
+    /// Azure Pipelines logging command.
+    ///
+    /// See <https://learn.microsoft.com/en-us/azure/devops/pipelines/scripts/logging-commands>.
+    pub fn azure_command(&self) -> String {
+        let issue_type = match self.severity {
+            Some(RiskLevel::Critical | RiskLevel::High) | None => "error",
+            Some(RiskLevel::Medium | RiskLevel::Low | RiskLevel::Info) => "warning",
+        };
+
+        let mut properties = format!("type={issue_type};");
+        if let Some(file) = &self.file {
+            properties.push_str(&format!("sourcepath={};", azure_escape(file)));
+        }
+        if let Some(line) = self.line {
+            properties.push_str(&format!("linenumber={line};"));
+        }
+
+        let message = format!("{}: {}", self.title, self.message);
+        format!("##vso[task.logissue {properties}]{}", azure_escape(&message))
+    }
+
+    /// GitLab code quality issue.
+    fn code_quality_issue(&self) -> CodeQualityIssue {
+        let severity = match self.severity {
+            Some(RiskLevel::Critical) => "critical",
+            Some(RiskLevel::High) | None => "major",
+            Some(RiskLevel::Medium) => "minor",
+            Some(RiskLevel::Low | RiskLevel::Info) => "info",
+        };
+
+        let mut hasher = Sha256::new();
+        hasher.update(self.title.as_bytes());
+        hasher.update([0]);
+        hasher.update(self.message.as_bytes());
+        let fingerprint = hasher.finalize().iter().map(|byte| format!("{byte:02x}")).collect();
+
+        CodeQualityIssue {
+            description: format!("{}: {}", self.title, self.message),
+            check_name: "phylum",
+            fingerprint,
+            severity,
+            location: CodeQualityLocation {
+                path: self.file.clone().unwrap_or_default(),
+                lines: CodeQualityLines { begin: self.line.unwrap_or(1) },
+            },
+        }
+    }
+}
+
+/// Collect annotations for all unsuppressed policy rejections.
+///
+/// File paths are relative to `root`, or to the project root if the depfile is
+/// not within `root`.
+pub fn annotations(report: &AnalysisReport, root: Option<&Path>) -> Vec<Annotation> {
+    let mut annotations = Vec::new();
+
+    for dependency in &report.status.dependencies {
+        let (file, line) = locate(&report.depfiles, root, &dependency.name, &dependency.version);
+
+        for rejection in dependency.rejections.iter().filter(|rejection| !rejection.suppressed) {
+            let domain = domain_name(rejection.source.domain);
+            annotations.push(Annotation {
+                severity: rejection.source.severity,
+                file: file.clone(),
+                line,
+                title: format!("Phylum: {}@{}", dependency.name, dependency.version),
+                message: format!("[{domain}] {}", rejection.title),
+            });
+        }
+    }
+
+    for finding in &report.dependency_confusion {
+        for version in &finding.public_versions {
+            let (file, line) = locate(&report.depfiles, root, &finding.name, version);
+            annotations.push(Annotation {
+                severity: Some(RiskLevel::High),
+                file,
+                line,
+                title: format!("Phylum: {}@{}", finding.name, version),
+                message: finding.kind.description().into(),
+            });
+        }
+    }
+
+    annotations
+}
+
+/// Emit annotations for the detected CI provider.
+///
+/// Workflow and logging commands are written to stdout, unless `stdout` is
+/// reserved for machine-readable output, in which case they go to stderr.
+pub fn emit(provider: CiProvider, report: &AnalysisReport, stdout: bool) -> io::Result<()> {
+    let workspace = provider.workspace().and_then(|workspace| workspace.canonicalize().ok());
+    let annotations = annotations(report, workspace.as_deref());
+
+    let mut output: Box<dyn Write> =
+        if stdout { Box::new(io::stdout().lock()) } else { Box::new(io::stderr().lock()) };
+
+    match provider {
+        CiProvider::GitHubActions => {
+            for annotation in &annotations {
+                writeln!(output, "{}", annotation.github_command())?;
+            }
+
+            // Add the report to the job summary page.
+            if let Some(path) = std::env::var_os("GITHUB_STEP_SUMMARY") {
+                let mut summary = OpenOptions::new().create(true).append(true).open(path)?;
+                writeln!(summary, "{}", markdown::render(report))?;
+            }
+        },
+        CiProvider::AzurePipelines => {
+            for annotation in &annotations {
+                writeln!(output, "{}", annotation.azure_command())?;
+            }
+        },
+        // GitLab only supports annotations through code quality report artifacts,
+        // which are written on request by `write_code_quality_report`.
+        CiProvider::GitLab => (),
+    }
+
+    Ok(())
+}
+
+/// Write a GitLab code quality report.
+///
+/// File paths are relative to the CI workspace, or the current directory
+/// outside of CI.
+pub fn write_code_quality_report(report: &AnalysisReport, path: &Path) -> io::Result<()> {
+    let root = match CiProvider::detect().and_then(|provider| provider.workspace()) {
+        Some(workspace) => workspace,
+        None => std::env::current_dir()?,
+    };
+    let annotations = annotations(report, root.canonicalize().ok().as_deref());
+
+    let issues: Vec<_> = annotations.iter().map(Annotation::code_quality_issue).collect();
+    fs::write(path, serde_json::to_string_pretty(&issues)?)
+}
+
+/// Find the dependency file and line declaring a package.
+fn locate(
+    depfiles: &[Depfile],
+    root: Option<&Path>,
+    name: &str,
+    version: &str,
+) -> (Option<String>, Option<usize>) {
+    let depfile = match depfiles.iter().find(|depfile| depfile.contains(name, version)) {
+        Some(depfile) => depfile,
+        None => return (None, None),
+    };
+
+    let relative_path = root.zip(depfile.absolute_path.as_ref()).and_then(|(root, path)| {
+        path.strip_prefix(root).ok().map(|path| path.to_string_lossy().replace('\\', "/"))
+    });
+    let path = relative_path.unwrap_or_else(|| depfile.path.clone());
+
+    (Some(path), depfile.line(name, version))
+}
+
+/// Escape GitHub workflow command data.
+fn github_escape_data(data: &str) -> String {
+    data.replace('%', "%25").replace('\r', "%0D").replace('\n', "%0A")
+}
+
+/// Escape GitHub workflow command properties.
+fn github_escape_property(property: &str) -> String {
+    github_escape_data(property).replace(':', "%3A").replace(',', "%2C")
+}
+
+/// Escape Azure Pipelines logging command properties and data.
+fn azure_escape(data: &str) -> String {
+    data.replace('%', "%AZP25")
+        .replace(';', "%3B")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+        .replace(']', "%5D")
+}
+
+#[derive(Serialize, Debug)]
+struct CodeQualityIssue {
+    description: String,
+    check_name: &'static str,
+    fingerprint: String,
+    severity: &'static str,
+    location: CodeQualityLocation,
+}
+
+#[derive(Serialize, Debug)]
+struct CodeQualityLocation {
+    path: String,
+    lines: CodeQualityLines,
+}
+
+#[derive(Serialize, Debug)]
+struct CodeQualityLines {
+    begin: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use phylum_types::types::package::{PackageDescriptor, PackageType};
+
+    use super::*;
+    use crate::types::{
+        EvaluatedDependency, PolicyEvaluationResponseRaw, PolicyRejection, RejectionSource,
+        RiskDomain,
+    };
+
+    fn report() -> AnalysisReport {
+        let status = PolicyEvaluationResponseRaw {
+            is_failure: true,
+            incomplete_packages_count: 0,
+            help: String::new(),
+            dependencies: vec![EvaluatedDependency {
+                purl: "pkg:npm/lodash@4.17.20".into(),
+                registry: "npm".into(),
+                name: "lodash".into(),
+                version: "4.17.20".into(),
+                rejections: vec![PolicyRejection {
+                    title: "Prototype pollution, 100%".into(),
+                    source: RejectionSource {
+                        source_type: "vulnerability".into(),
+                        tag: None,
+                        domain: Some(RiskDomain::Vulnerabilities),
+                        severity: Some(RiskLevel::Medium),
+                        description: None,
+                        reason: None,
+                    },
+                    suppressed: false,
+                }],
+            }],
+            job_link: None,
+        };
+        let depfile = Depfile {
+            path: "web/package-lock.json".into(),
+            format: "npm",
+            absolute_path: Some(PathBuf::from("/repo/web/package-lock.json")),
+            contents: Some("{\n  \"lodash\": {\n    \"version\": \"4.17.20\"\n  }\n}".into()),
+            packages: vec![PackageDescriptor {
+                name: "lodash".into(),
+                version: "4.17.20".into(),
+                package_type: PackageType::Npm,
+            }],
+        };
+
+        AnalysisReport { status, depfiles: vec![depfile], dependency_confusion: Vec::new() }
+    }
+
+    #[test]
+    fn github_annotations() {
+        let annotations = annotations(&report(), Some(Path::new("/repo")));
+
+        assert_eq!(annotations.len(), 1);
+        assert_eq!(
+            annotations[0].github_command(),
+            "::warning file=web/package-lock.json,line=2,title=Phylum%3A \
+             lodash@4.17.20::[Vulnerabilities] Prototype pollution, 100%25"
+        );
+    }
+
+    #[test]
+    fn azure_annotations() {
+        let annotations = annotations(&report(), None);
+
+        assert_eq!(
+            annotations[0].azure_command(),
+            "##vso[task.logissue \
+             type=warning;sourcepath=web/package-lock.json;linenumber=2;]Phylum: lodash@4.17.20: \
+             [Vulnerabilities%5D Prototype pollution, 100%AZP25"
+        );
+    }
+
+    #[test]
+    fn code_quality_issues() {
+        let annotations = annotations(&report(), Some(Path::new("/other")));
+        let issue = serde_json::to_value(annotations[0].code_quality_issue()).unwrap();
+
+        assert_eq!(issue["severity"], "minor");
+        assert_eq!(issue["location"]["path"], "web/package-lock.json");
+        assert_eq!(issue["location"]["lines"]["begin"], 2);
+        assert_eq!(issue["fingerprint"].as_str().unwrap().len(), 64);
+    }
+}
@@ -0,0 +1,298 @@
+//! GitLab dependency scanning report output.
+//!
+//! See <https://gitlab.com/gitlab-org/security-products/security-report-schemas>.
+
+use chrono::Utc;
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::report::{AnalysisReport, Depfile};
+use crate::types::RiskLevel;
+
+/// Version of the security report schema.
+const SCHEMA_VERSION: &str = "15.0.7";
+
+/// GitLab dependency scanning report.
+#[derive(Serialize, Debug)]
+pub struct GitlabReport {
+    version: &'static str,
+    scan: Scan,
+    vulnerabilities: Vec<Vulnerability>,
+    dependency_files: Vec<DependencyFile>,
+}
+
+impl GitlabReport {
+    /// Convert policy evaluation results to a dependency scanning report.
+    ///
+    /// Every unsuppressed policy rejection is reported as a vulnerability.
+    pub fn new(report: &AnalysisReport) -> Self {
+        let (status, depfiles) = (&report.status, &report.depfiles);
+        let mut vulnerabilities = Vec::new();
+
+        for dependency in &status.dependencies {
+            let file = location_file(depfiles, &dependency.name, &dependency.version)
+                .unwrap_or(&dependency.purl);
+
+            for rejection in dependency.rejections.iter().filter(|rejection| !rejection.suppressed)
+            {
+                let source = &rejection.source;
+
+                let mut identifiers = vec![Identifier {
+                    identifier_type: "phylum".into(),
+                    name: rejection.title.clone(),
+                    value: source.tag.clone().unwrap_or_else(|| source.source_type.clone()),
+                    url: status.job_link.clone(),
+                }];
+                if let Some(cve) = source.tag.as_ref().filter(|tag| tag.starts_with("CVE-")) {
+                    identifiers.push(Identifier {
+                        identifier_type: "cve".into(),
+                        name: cve.clone(),
+                        value: cve.clone(),
+                        url: Some(format!("https://nvd.nist.gov/vuln/detail/{cve}")),
+                    });
+                }
+
+                vulnerabilities.push(Vulnerability {
+                    id: Uuid::new_v4().to_string(),
+                    name: rejection.title.clone(),
+                    description: source.description.clone(),
+                    severity: severity(source.severity),
+                    solution: source.reason.clone(),
+                    identifiers,
+                    location: Location::new(file, &dependency.name, &dependency.version),
+                });
+            }
+        }
+
+        for finding in &report.dependency_confusion {
+            for version in &finding.public_versions {
+                let file = location_file(depfiles, &finding.name, version).unwrap_or_default();
+
+                vulnerabilities.push(Vulnerability {
+                    id: Uuid::new_v4().to_string(),
+                    name: finding.kind.description().into(),
+                    description: None,
+                    severity: "High",
+                    solution: None,
+                    identifiers: vec![Identifier {
+                        identifier_type: "phylum".into(),
+                        name: finding.kind.description().into(),
+                        value: "dependency-confusion".into(),
+                        url: None,
+                    }],
+                    location: Location::new(file, &finding.name, version),
+                });
+            }
+        }
+
+        let dependency_files = depfiles
+            .iter()
+            .map(|depfile| DependencyFile {
+                path: depfile.path.clone(),
+                package_manager: package_manager(depfile.format),
+                dependencies: depfile
+                    .packages
+                    .iter()
+                    .map(|package| Dependency::new(&package.name, &package.version))
+                    .collect(),
+            })
+            .collect();
+
+        let time = Utc::now().format("%Y-%m-%dT%H:%M:%S").to_string();
+        let scanner = Scanner {
+            id: "phylum",
+            name: "Phylum",
+            version: env!("CARGO_PKG_VERSION"),
+            vendor: Vendor { name: "Phylum" },
+        };
+        let scan = Scan {
+            analyzer: scanner.clone(),
+            scanner,
+            scan_type: "dependency_scanning",
+            start_time: time.clone(),
+            end_time: time,
+            status: "success",
+        };
+
+        Self { version: SCHEMA_VERSION, scan, vulnerabilities, dependency_files }
+    }
+}
+
+/// Find the first depfile containing a package.
+fn location_file<'a>(depfiles: &'a [Depfile], name: &str, version: &str) -> Option<&'a str> {
+    depfiles
+        .iter()
+        .find(|depfile| depfile.contains(name, version))
+        .map(|depfile| depfile.path.as_str())
+}
+
+/// Map risk levels to GitLab severities.
+fn severity(level: Option<RiskLevel>) -> &'static str {
+    match level {
+        Some(RiskLevel::Critical) => "Critical",
+        Some(RiskLevel::High) => "High",
+        Some(RiskLevel::Medium) => "Medium",
+        Some(RiskLevel::Low) => "Low",
+        Some(RiskLevel::Info) => "Info",
+        None => "Unknown",
+    }
+}
+
+/// Map dependency file formats to GitLab's package manager names.
+fn package_manager(format: &str) -> String {
+    let package_manager = match format {
+        "gem" => "bundler",
+        "mvn" => "maven",
+        "gomod" => "go",
+        "msbuild" | "nugetlock" => "nuget",
+        format => format,
+    };
+    package_manager.into()
+}
+
+#[derive(Serialize, Debug)]
+struct Scan {
+    analyzer: Scanner,
+    scanner: Scanner,
+    #[serde(rename = "type")]
+    scan_type: &'static str,
+    start_time: String,
+    end_time: String,
+    status: &'static str,
+}
+
+#[derive(Serialize, Clone, Debug)]
+struct Scanner {
+    id: &'static str,
+    name: &'static str,
+    version: &'static str,
+    vendor: Vendor,
+}
+
+#[derive(Serialize, Clone, Debug)]
+struct Vendor {
+    name: &'static str,
+}
+
+#[derive(Serialize, Debug)]
+struct Vulnerability {
+    id: String,
+    name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+    severity: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    solution: Option<String>,
+    identifiers: Vec<Identifier>,
+    location: Location,
+}
+
+#[derive(Serialize, Debug)]
+struct Identifier {
+    #[serde(rename = "type")]
+    identifier_type: String,
+    name: String,
+    value: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    url: Option<String>,
+}
+
+#[derive(Serialize, Debug)]
+struct Location {
+    file: String,
+    dependency: Dependency,
+}
+
+impl Location {
+    fn new(file: &str, name: &str, version: &str) -> Self {
+        Self { file: file.into(), dependency: Dependency::new(name, version) }
+    }
+}
+
+#[derive(Serialize, Debug)]
+struct DependencyFile {
+    path: String,
+    package_manager: String,
+    dependencies: Vec<Dependency>,
+}
+
+#[derive(Serialize, Debug)]
+struct Dependency {
+    package: DependencyPackage,
+    version: String,
+}
+
+impl Dependency {
+    fn new(name: &str, version: &str) -> Self {
+        Self { package: DependencyPackage { name: name.into() }, version: version.into() }
+    }
+}
+
+#[derive(Serialize, Debug)]
+struct DependencyPackage {
+    name: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use phylum_types::types::package::{PackageDescriptor, PackageType};
+
+    use super::*;
+    use crate::types::{EvaluatedDependency, PolicyRejection, RejectionSource};
+
+    #[test]
+    fn rejections_as_vulnerabilities() {
+        let rejection = |tag: &str, suppressed| PolicyRejection {
+            title: format!("{tag} title"),
+            source: RejectionSource {
+                source_type: "issue".into(),
+                tag: Some(tag.into()),
+                domain: None,
+                severity: Some(RiskLevel::Critical),
+                description: Some("description".into()),
+                reason: None,
+            },
+            suppressed,
+        };
+        let status = PolicyEvaluationResponseRaw {
+            is_failure: true,
+            incomplete_packages_count: 0,
+            help: String::new(),
+            dependencies: vec![EvaluatedDependency {
+                purl: "pkg:gem/rails@7.0.0".into(),
+                registry: "rubygems".into(),
+                name: "rails".into(),
+                version: "7.0.0".into(),
+                rejections: vec![rejection("CVE-2023-1", false), rejection("HV0001", true)],
+            }],
+            job_link: None,
+        };
+        let depfile = Depfile {
+            path: "Gemfile.lock".into(),
+            format: "gem",
+            absolute_path: None,
+            contents: None,
+            packages: vec![PackageDescriptor {
+                name: "rails".into(),
+                version: "7.0.0".into(),
+                package_type: PackageType::RubyGems,
+            }],
+        };
+
+        let report =
+            AnalysisReport { status, depfiles: vec![depfile], dependency_confusion: Vec::new() };
+        let report = serde_json::to_value(GitlabReport::new(&report)).unwrap();
+
+        assert_eq!(report["scan"]["type"], "dependency_scanning");
+
+        let vulnerabilities = report["vulnerabilities"].as_array().unwrap();
+        assert_eq!(vulnerabilities.len(), 1);
+        assert_eq!(vulnerabilities[0]["severity"], "Critical");
+        assert_eq!(vulnerabilities[0]["location"]["file"], "Gemfile.lock");
+        assert_eq!(vulnerabilities[0]["location"]["dependency"]["package"]["name"], "rails");
+        assert_eq!(vulnerabilities[0]["identifiers"][1]["type"], "cve");
+
+        assert_eq!(report["dependency_files"][0]["package_manager"], "bundler");
+        assert_eq!(report["dependency_files"][0]["dependencies"][0]["version"], "7.0.0");
+    }
+}
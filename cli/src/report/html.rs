@@ -0,0 +1,176 @@
+//! Standalone HTML report for policy evaluation results.
+
+use std::fmt::Write;
+
+use crate::report::{domain_counts, domain_name, escape_xml, AnalysisReport};
+use crate::types::RiskLevel;
+
+/// Inline stylesheet, to keep the report self-contained.
+const STYLE: &str = r#"
+body { font-family: sans-serif; margin: 2em auto; max-width: 60em; color: #222; }
+table { border-collapse: collapse; margin-bottom: 1em; }
+th, td { border: 1px solid #ccc; padding: 0.3em 0.6em; text-align: left; }
+details { margin: 0.5em 0; }
+summary { cursor: pointer; font-weight: bold; }
+.failure, .critical, .high { color: #b00020; }
+.incomplete, .medium { color: #b06000; }
+.success, .low, .info { color: #1b7f3b; }
+"#;
+
+/// Render policy evaluation results as a standalone HTML document.
+pub fn render(report: &AnalysisReport) -> String {
+    let status = &report.status;
+    let mut html = String::new();
+
+    let _ = writeln!(html, "<!DOCTYPE html>");
+    let _ = writeln!(html, "<html lang=\"en\">\n<head>");
+    let _ = writeln!(html, "<meta charset=\"utf-8\">");
+    let _ = writeln!(html, "<title>Phylum Supply Chain Risk Analysis</title>");
+    let _ = writeln!(html, "<style>{STYLE}</style>");
+    let _ = writeln!(html, "</head>\n<body>");
+
+    let (class, result) = if status.is_failure {
+        ("failure", "FAILURE")
+    } else if status.incomplete_packages_count > 0 {
+        ("incomplete", "INCOMPLETE")
+    } else {
+        ("success", "SUCCESS")
+    };
+    let _ = writeln!(
+        html,
+        "<h1>Phylum Supply Chain Risk Analysis — <span class=\"{class}\">{result}</span></h1>"
+    );
+
+    if status.incomplete_packages_count > 0 {
+        let _ = writeln!(
+            html,
+            "<p>The analysis contains {} unprocessed package(s), preventing a complete risk \
+             analysis.</p>",
+            status.incomplete_packages_count
+        );
+    }
+
+    // Summarize rejections by risk domain.
+    let counts = domain_counts(status);
+    if counts.is_empty() && report.dependency_confusion.is_empty() {
+        let _ = writeln!(html, "<p>No policy rejections found.</p>");
+    } else if !counts.is_empty() {
+        let _ = writeln!(html, "<table>\n<tr><th>Risk domain</th><th>Rejections</th></tr>");
+        for (domain, count) in counts {
+            let _ = writeln!(html, "<tr><td>{domain}</td><td>{count}</td></tr>");
+        }
+        let _ = writeln!(html, "</table>");
+    }
+
+    // Add collapsible details for each dependency with rejections.
+    for dependency in &status.dependencies {
+        let rejections: Vec<_> =
+            dependency.rejections.iter().filter(|rejection| !rejection.suppressed).collect();
+        if rejections.is_empty() {
+            continue;
+        }
+
+        let _ = writeln!(
+            html,
+            "<details>\n<summary>[{}] {}@{} — {} rejection(s)</summary>",
+            escape_xml(&dependency.registry),
+            escape_xml(&dependency.name),
+            escape_xml(&dependency.version),
+            rejections.len(),
+        );
+        let _ =
+            writeln!(html, "<table>\n<tr><th>Severity</th><th>Domain</th><th>Rejection</th></tr>");
+        for rejection in rejections {
+            let severity = rejection.source.severity.map(|severity| severity.to_string());
+            let severity = severity.as_deref().unwrap_or("unknown");
+            let class = severity_class(rejection.source.severity);
+            let _ = writeln!(
+                html,
+                "<tr><td class=\"{class}\">{severity}</td><td>{}</td><td>{}</td></tr>",
+                domain_name(rejection.source.domain),
+                escape_xml(&rejection.title),
+            );
+        }
+        let _ = writeln!(html, "</table>\n</details>");
+    }
+
+    if !report.dependency_confusion.is_empty() {
+        let _ = writeln!(html, "<h2>Dependency confusion</h2>\n<ul>");
+        for finding in &report.dependency_confusion {
+            let _ = writeln!(
+                html,
+                "<li><b>[{}] {}@{}</b>: {}</li>",
+                finding.package_type,
+                escape_xml(&finding.name),
+                escape_xml(&finding.public_versions.join(", ")),
+                finding.kind.description(),
+            );
+        }
+        let _ = writeln!(html, "</ul>");
+    }
+
+    if let Some(job_link) = &status.job_link {
+        let job_link = escape_xml(job_link);
+        let _ = writeln!(html, "<p><a href=\"{job_link}\">View the interactive report</a></p>");
+    }
+
+    let _ = writeln!(html, "</body>\n</html>");
+
+    html
+}
+
+/// CSS class for a rejection's severity.
+fn severity_class(severity: Option<RiskLevel>) -> &'static str {
+    match severity {
+        Some(RiskLevel::Critical) | None => "critical",
+        Some(RiskLevel::High) => "high",
+        Some(RiskLevel::Medium) => "medium",
+        Some(RiskLevel::Low) => "low",
+        Some(RiskLevel::Info) => "info",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{EvaluatedDependency, PolicyRejection, RejectionSource, RiskDomain};
+
+    #[test]
+    fn render_document() {
+        let status = PolicyEvaluationResponseRaw {
+            is_failure: true,
+            incomplete_packages_count: 0,
+            help: String::new(),
+            dependencies: vec![EvaluatedDependency {
+                purl: "pkg:npm/evil@1.0.0".into(),
+                registry: "npm".into(),
+                name: "evil".into(),
+                version: "1.0.0".into(),
+                rejections: vec![PolicyRejection {
+                    title: "<script>alert(1)</script>".into(),
+                    source: RejectionSource {
+                        source_type: "issue".into(),
+                        tag: None,
+                        domain: Some(RiskDomain::Malicious),
+                        severity: Some(RiskLevel::High),
+                        description: None,
+                        reason: None,
+                    },
+                    suppressed: false,
+                }],
+            }],
+            job_link: Some("https://app.phylum.io/projects/1?a=1&b=2".into()),
+        };
+
+        let report =
+            AnalysisReport { status, depfiles: Vec::new(), dependency_confusion: Vec::new() };
+        let html = render(&report);
+
+        assert!(html.starts_with("<!DOCTYPE html>\n"));
+        assert!(html.contains("<span class=\"failure\">FAILURE</span>"));
+        assert!(html.contains("<tr><td>Malicious Code</td><td>1</td></tr>"));
+        assert!(html.contains("&lt;script&gt;alert(1)&lt;/script&gt;"));
+        assert!(!html.contains("<script>"));
+        assert!(html.contains("href=\"https://app.phylum.io/projects/1?a=1&amp;b=2\""));
+    }
+}
@@ -0,0 +1,166 @@
+//! JUnit XML output for policy evaluation results.
+//!
+//! Every dependency is reported as a test case, which fails if the dependency
+//! has any unsuppressed policy rejections.
+
+use std::io::{self, Write};
+
+use crate::report::{escape_xml, AnalysisReport};
+
+/// Write policy evaluation results as JUnit XML.
+pub fn write_report<W: Write>(writer: &mut W, report: &AnalysisReport) -> io::Result<()> {
+    let depfiles = &report.depfiles;
+    let mut cases = Vec::new();
+
+    for dependency in &report.status.dependencies {
+        let failures: Vec<_> = dependency
+            .rejections
+            .iter()
+            .filter(|rejection| !rejection.suppressed)
+            .map(|rejection| match rejection.source.severity {
+                Some(severity) => format!("[{severity}] {}", rejection.title),
+                None => rejection.title.clone(),
+            })
+            .collect();
+
+        let file = depfiles
+            .iter()
+            .find(|depfile| depfile.contains(&dependency.name, &dependency.version))
+            .map(|depfile| depfile.path.as_str());
+
+        cases.push(TestCase {
+            name: format!("{}@{}", dependency.name, dependency.version),
+            classname: dependency.registry.clone(),
+            file,
+            failures,
+        });
+    }
+
+    for finding in &report.dependency_confusion {
+        for version in &finding.public_versions {
+            let file = depfiles
+                .iter()
+                .find(|depfile| depfile.contains(&finding.name, version))
+                .map(|depfile| depfile.path.as_str());
+
+            cases.push(TestCase {
+                name: format!("{}@{}", finding.name, version),
+                classname: finding.package_type.to_string(),
+                file,
+                failures: vec![finding.kind.description().into()],
+            });
+        }
+    }
+
+    let tests = cases.len();
+    let failures = cases.iter().filter(|case| !case.failures.is_empty()).count();
+
+    writeln!(writer, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+    writeln!(writer, r#"<testsuites name="Phylum" tests="{tests}" failures="{failures}">"#)?;
+    writeln!(
+        writer,
+        r#"  <testsuite name="Phylum policy evaluation" tests="{tests}" failures="{failures}" errors="0" skipped="0">"#
+    )?;
+
+    for case in &cases {
+        case.write(writer)?;
+    }
+
+    writeln!(writer, "  </testsuite>")?;
+    writeln!(writer, "</testsuites>")?;
+
+    Ok(())
+}
+
+/// Test case for a single dependency.
+struct TestCase<'a> {
+    name: String,
+    classname: String,
+    file: Option<&'a str>,
+    failures: Vec<String>,
+}
+
+impl TestCase<'_> {
+    fn write<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        let file =
+            self.file.map(|file| format!(r#" file="{}""#, escape_xml(file))).unwrap_or_default();
+        write!(
+            writer,
+            r#"    <testcase name="{}" classname="{}"{file}"#,
+            escape_xml(&self.name),
+            escape_xml(&self.classname),
+        )?;
+
+        if self.failures.is_empty() {
+            return writeln!(writer, " />");
+        }
+
+        let pluralization = if self.failures.len() == 1 { "" } else { "s" };
+        writeln!(writer, ">")?;
+        writeln!(
+            writer,
+            r#"      <failure message="{} policy rejection{pluralization}" type="PolicyRejection">{}</failure>"#,
+            self.failures.len(),
+            escape_xml(&self.failures.join("\n")),
+        )?;
+        writeln!(writer, "    </testcase>")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{EvaluatedDependency, PolicyRejection, RejectionSource, RiskLevel};
+
+    fn dependency(name: &str, rejections: &[(&str, bool)]) -> EvaluatedDependency {
+        EvaluatedDependency {
+            purl: format!("pkg:npm/{name}@1.0.0"),
+            registry: "npm".into(),
+            name: name.into(),
+            version: "1.0.0".into(),
+            rejections: rejections
+                .iter()
+                .map(|(title, suppressed)| PolicyRejection {
+                    title: (*title).into(),
+                    source: RejectionSource {
+                        source_type: "issue".into(),
+                        tag: None,
+                        domain: None,
+                        severity: Some(RiskLevel::High),
+                        description: None,
+                        reason: None,
+                    },
+                    suppressed: *suppressed,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn dependencies_as_test_cases() {
+        let status = PolicyEvaluationResponseRaw {
+            is_failure: true,
+            incomplete_packages_count: 0,
+            help: String::new(),
+            dependencies: vec![
+                dependency("good", &[]),
+                dependency("bad", &[("Malware <script>", false)]),
+                dependency("ignored", &[("Suppressed", true)]),
+            ],
+            job_link: None,
+        };
+
+        let mut output = Vec::new();
+        let report =
+            AnalysisReport { status, depfiles: Vec::new(), dependency_confusion: Vec::new() };
+        write_report(&mut output, &report).unwrap();
+        let output = String::from_utf8(output).unwrap();
+
+        assert!(output.contains(r#"<testsuites name="Phylum" tests="3" failures="1">"#));
+        assert!(output.contains(r#"<testcase name="good@1.0.0" classname="npm" />"#));
+        assert!(output.contains(r#"<testcase name="ignored@1.0.0" classname="npm" />"#));
+        assert!(output.contains(
+            r#"<failure message="1 policy rejection" type="PolicyRejection">[high] Malware &lt;script&gt;</failure>"#
+        ));
+    }
+}
@@ -0,0 +1,207 @@
+//! Markdown output for pull request comments.
+
+use std::fmt::Write;
+
+use crate::report::{domain_counts, domain_name, escape_xml, AnalysisReport};
+
+/// Maximum length of the Markdown report in bytes.
+///
+/// This matches GitHub's limit for comment bodies, which is the lowest limit
+/// among the commonly used pull request comment APIs.
+pub const MAX_LENGTH: usize = 65_536;
+
+/// Space reserved for the truncation summary.
+const TRUNCATION_RESERVE: usize = 256;
+
+/// Render policy evaluation results as Markdown.
+pub fn render(report: &AnalysisReport) -> String {
+    render_with_limit(report, MAX_LENGTH)
+}
+
+/// Render Markdown, truncating dependency details to stay below `max_length`.
+fn render_with_limit(report: &AnalysisReport, max_length: usize) -> String {
+    let status = &report.status;
+    let mut header = String::new();
+
+    let result = if status.is_failure {
+        "FAILURE"
+    } else if status.incomplete_packages_count > 0 {
+        "INCOMPLETE"
+    } else {
+        "SUCCESS"
+    };
+    let _ = writeln!(header, "## Phylum Supply Chain Risk Analysis — {result}\n");
+
+    if status.incomplete_packages_count > 0 {
+        let pluralization = if status.incomplete_packages_count == 1 { "" } else { "s" };
+        let _ = writeln!(
+            header,
+            "The analysis contains {} unprocessed package{pluralization}, preventing a complete \
+             risk analysis.\n",
+            status.incomplete_packages_count,
+        );
+    }
+
+    // Summarize rejections by risk domain.
+    let counts = domain_counts(status);
+    if counts.is_empty() && report.dependency_confusion.is_empty() {
+        let _ = writeln!(header, "No policy rejections found.\n");
+    } else if !counts.is_empty() {
+        let _ = writeln!(header, "| Risk domain | Rejections |");
+        let _ = writeln!(header, "| --- | ---: |");
+        for (domain, count) in counts {
+            let _ = writeln!(header, "| {domain} | {count} |");
+        }
+        let _ = writeln!(header);
+    }
+
+    let mut sections = Vec::new();
+
+    // Add collapsible details for each dependency with rejections.
+    for dependency in &status.dependencies {
+        let rejections: Vec<_> =
+            dependency.rejections.iter().filter(|rejection| !rejection.suppressed).collect();
+        if rejections.is_empty() {
+            continue;
+        }
+
+        let mut section = String::new();
+        let pluralization = if rejections.len() == 1 { "" } else { "s" };
+        let _ = writeln!(section, "<details>");
+        let _ = writeln!(
+            section,
+            "<summary><b>[{}] {}@{}</b> — {} rejection{pluralization}</summary>\n",
+            escape_xml(&dependency.registry),
+            escape_xml(&dependency.name),
+            escape_xml(&dependency.version),
+            rejections.len(),
+        );
+        for rejection in rejections {
+            let severity = rejection
+                .source
+                .severity
+                .map(|severity| format!("**{severity}** "))
+                .unwrap_or_default();
+            let domain = domain_name(rejection.source.domain);
+            let _ = writeln!(section, "- {severity}[{domain}] {}", escape_xml(&rejection.title));
+        }
+        let _ = writeln!(section, "\n</details>\n");
+
+        sections.push(section);
+    }
+
+    if !report.dependency_confusion.is_empty() {
+        let mut section = String::from("### Dependency confusion\n\n");
+        for finding in &report.dependency_confusion {
+            let _ = writeln!(
+                section,
+                "- **[{}] {}@{}**: {}",
+                finding.package_type,
+                escape_xml(&finding.name),
+                escape_xml(&finding.public_versions.join(", ")),
+                finding.kind.description(),
+            );
+        }
+        let _ = writeln!(section);
+
+        sections.push(section);
+    }
+
+    let footer = match &status.job_link {
+        Some(job_link) => format!("[View the interactive report]({job_link})\n"),
+        None => String::new(),
+    };
+
+    // Add as many sections as possible without exceeding the size limit.
+    let budget = max_length.saturating_sub(header.len() + footer.len() + TRUNCATION_RESERVE);
+    let mut markdown = header;
+    let mut used = 0;
+    let mut omitted = 0;
+    for section in &sections {
+        if omitted == 0 && used + section.len() <= budget {
+            markdown.push_str(section);
+            used += section.len();
+        } else {
+            omitted += 1;
+        }
+    }
+
+    if omitted > 0 {
+        let pluralization = if omitted == 1 { "" } else { "s" };
+        let _ = writeln!(
+            markdown,
+            "> **Note:** The report was truncated, {omitted} section{pluralization} with policy \
+             rejections could not be shown.\n"
+        );
+    }
+
+    markdown.push_str(&footer);
+
+    markdown
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{
+        EvaluatedDependency, PolicyEvaluationResponseRaw, PolicyRejection, RejectionSource,
+        RiskDomain, RiskLevel,
+    };
+
+    fn report(dependency_count: usize) -> AnalysisReport {
+        let dependencies = (0..dependency_count)
+            .map(|i| EvaluatedDependency {
+                purl: format!("pkg:npm/pkg{i}@1.0.0"),
+                registry: "npm".into(),
+                name: format!("pkg{i}"),
+                version: "1.0.0".into(),
+                rejections: vec![PolicyRejection {
+                    title: "Malicious <code>".into(),
+                    source: RejectionSource {
+                        source_type: "issue".into(),
+                        tag: None,
+                        domain: Some(RiskDomain::Malicious),
+                        severity: Some(RiskLevel::Critical),
+                        description: None,
+                        reason: None,
+                    },
+                    suppressed: false,
+                }],
+            })
+            .collect();
+
+        let status = PolicyEvaluationResponseRaw {
+            is_failure: true,
+            incomplete_packages_count: 0,
+            help: String::new(),
+            dependencies,
+            job_link: Some("https://app.phylum.io/projects/1".into()),
+        };
+
+        AnalysisReport { status, depfiles: Vec::new(), dependency_confusion: Vec::new() }
+    }
+
+    #[test]
+    fn render_details() {
+        let markdown = render(&report(2));
+
+        assert!(markdown.starts_with("## Phylum Supply Chain Risk Analysis — FAILURE\n"));
+        assert!(markdown.contains("| Malicious Code | 2 |\n"));
+        assert!(markdown.contains("<summary><b>[npm] pkg1@1.0.0</b> — 1 rejection</summary>"));
+        assert!(markdown.contains("- **critical** [Malicious Code] Malicious &lt;code&gt;\n"));
+        assert!(
+            markdown.ends_with("[View the interactive report](https://app.phylum.io/projects/1)\n")
+        );
+        assert!(!markdown.contains("truncated"));
+    }
+
+    #[test]
+    fn truncate_long_reports() {
+        let markdown = render_with_limit(&report(100), 2048);
+
+        assert!(markdown.len() <= 2048);
+        assert!(markdown.contains("| Malicious Code | 100 |\n"));
+        assert!(markdown.contains("with policy rejections could not be shown"));
+        assert!(markdown.contains("[View the interactive report]"));
+    }
+}
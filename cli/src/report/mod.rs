@@ -0,0 +1,202 @@
+//! Analysis reports for external tooling.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use phylum_lockfile::confusion::ConfusionFinding;
+use phylum_lockfile::ParsedLockfile;
+use phylum_types::types::package::PackageDescriptor;
+use serde::Serialize;
+
+use crate::types::{PolicyEvaluationResponseRaw, RiskDomain};
+
+pub mod annotations;
+pub mod gitlab;
+pub mod html;
+pub mod junit;
+pub mod markdown;
+pub mod sarif;
+
+/// Risk domains in report order.
+const DOMAINS: [Option<RiskDomain>; 6] = [
+    Some(RiskDomain::Malicious),
+    Some(RiskDomain::Vulnerabilities),
+    Some(RiskDomain::EngineeringRisk),
+    Some(RiskDomain::AuthorRisk),
+    Some(RiskDomain::LicenseRisk),
+    None,
+];
+
+/// Policy evaluation results of an analysis job.
+#[derive(Serialize, Debug)]
+pub struct AnalysisReport {
+    #[serde(flatten)]
+    pub status: PolicyEvaluationResponseRaw,
+    /// Submitted dependency files, used to locate findings.
+    #[serde(skip)]
+    pub depfiles: Vec<Depfile>,
+    /// Local dependency confusion findings, reported as policy failures.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub dependency_confusion: Vec<ConfusionFinding>,
+}
+
+/// Dependency file submitted for analysis.
+///
+/// Used to point report findings at the file declaring a package.
+#[derive(Clone, Debug)]
+pub struct Depfile {
+    /// Path relative to the project root.
+    pub path: String,
+    /// Dependency file format name.
+    pub format: &'static str,
+    /// Canonical filesystem path, if it could be resolved.
+    pub absolute_path: Option<PathBuf>,
+    contents: Option<String>,
+    packages: Vec<PackageDescriptor>,
+}
+
+impl Depfile {
+    /// Create a depfile from its parsed representation.
+    ///
+    /// The `fs_path` is used to load the file contents for line lookups.
+    pub fn new(parsed: &ParsedLockfile, fs_path: &Path) -> Self {
+        Self {
+            path: parsed.path.replace('\\', "/"),
+            format: parsed.format.name(),
+            absolute_path: fs_path.canonicalize().ok(),
+            contents: fs::read_to_string(fs_path).ok(),
+            packages: parsed.packages.clone(),
+        }
+    }
+
+    /// Check if this file contains a package.
+    pub fn contains(&self, name: &str, version: &str) -> bool {
+        self.packages.iter().any(|package| package.name == name && package.version == version)
+    }
+
+    /// Find the first line declaring a package.
+    ///
+    /// Lockfiles frequently put the name and version on separate lines, so
+    /// the version is searched in the lines directly following the name.
+    pub fn line(&self, name: &str, version: &str) -> Option<usize> {
+        const MAX_DISTANCE: usize = 4;
+
+        let lines: Vec<_> = self.contents.as_ref()?.lines().collect();
+        lines.iter().enumerate().find_map(|(i, line)| {
+            if !contains_word(line, name) {
+                return None;
+            }
+
+            let end = (i + MAX_DISTANCE).min(lines.len());
+            lines[i..end].iter().any(|line| line.contains(version)).then_some(i + 1)
+        })
+    }
+}
+
+/// Count unsuppressed policy rejections for each risk domain.
+///
+/// Domains without any rejections are omitted.
+pub fn domain_counts(status: &PolicyEvaluationResponseRaw) -> Vec<(&'static str, usize)> {
+    let rejections: Vec<_> = status
+        .dependencies
+        .iter()
+        .flat_map(|dependency| &dependency.rejections)
+        .filter(|rejection| !rejection.suppressed)
+        .collect();
+
+    DOMAINS
+        .iter()
+        .map(|domain| {
+            let count =
+                rejections.iter().filter(|rejection| rejection.source.domain == *domain).count();
+            (domain_name(*domain), count)
+        })
+        .filter(|(_, count)| *count > 0)
+        .collect()
+}
+
+/// Human-readable name of a risk domain.
+pub fn domain_name(domain: Option<RiskDomain>) -> &'static str {
+    match domain {
+        Some(RiskDomain::Malicious) => "Malicious Code",
+        Some(RiskDomain::Vulnerabilities) => "Vulnerabilities",
+        Some(RiskDomain::EngineeringRisk) => "Engineering",
+        Some(RiskDomain::AuthorRisk) => "Author",
+        Some(RiskDomain::LicenseRisk) => "License",
+        None => "Other",
+    }
+}
+
+/// Escape text for use in XML and HTML documents.
+pub fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Check if `text` contains `word` not surrounded by name characters.
+fn contains_word(text: &str, word: &str) -> bool {
+    let is_name_char = |c: char| c.is_alphanumeric() || c == '-' || c == '_';
+
+    text.match_indices(word).any(|(start, _)| {
+        let before = text[..start].chars().next_back();
+        let after = text[start + word.len()..].chars().next();
+        !before.is_some_and(is_name_char) && !after.is_some_and(is_name_char)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use phylum_types::types::package::PackageType;
+
+    use super::*;
+
+    fn depfile(contents: &str) -> Depfile {
+        Depfile {
+            path: "Cargo.lock".into(),
+            format: "cargo",
+            absolute_path: None,
+            contents: Some(contents.into()),
+            packages: vec![PackageDescriptor {
+                name: "serde".into(),
+                version: "1.0.0".into(),
+                package_type: PackageType::Cargo,
+            }],
+        }
+    }
+
+    #[test]
+    fn find_package_line() {
+        let depfile = depfile(
+            "[[package]]\nname = \"serde_json\"\nversion = \"1.0.0\"\n\n[[package]]\nname = \
+             \"serde\"\nversion = \"1.0.0\"\n",
+        );
+        assert_eq!(depfile.line("serde", "1.0.0"), Some(6));
+        assert_eq!(depfile.line("serde", "2.0.0"), None);
+        assert!(depfile.contains("serde", "1.0.0"));
+        assert!(!depfile.contains("serde_json", "1.0.0"));
+    }
+
+    #[test]
+    fn escape() {
+        assert_eq!(
+            escape_xml("<a href=\"x\">'&'</a>"),
+            "&lt;a href=&quot;x&quot;&gt;&apos;&amp;&apos;&lt;/a&gt;"
+        );
+    }
+
+    #[test]
+    fn find_single_line_package() {
+        let depfile = depfile("serde-derive==1.0.0\nserde==1.0.0\n");
+        assert_eq!(depfile.line("serde", "1.0.0"), Some(2));
+    }
+}
@@ -0,0 +1,425 @@
+//! SARIF 2.1.0 output for policy evaluation results.
+//!
+//! See <https://docs.oasis-open.org/sarif/sarif/v2.1.0/sarif-v2.1.0.html>.
+
+use std::collections::BTreeMap;
+
+use phylum_lockfile::confusion::ConfusionFinding;
+use serde::Serialize;
+
+use crate::report::{AnalysisReport, Depfile};
+use crate::types::{RejectionSource, RiskLevel};
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+const SARIF_VERSION: &str = "2.1.0";
+
+/// Top-level SARIF document.
+#[derive(Serialize, Debug)]
+pub struct SarifLog {
+    #[serde(rename = "$schema")]
+    schema: &'static str,
+    version: &'static str,
+    runs: Vec<Run>,
+}
+
+impl SarifLog {
+    /// Convert policy evaluation results to SARIF.
+    ///
+    /// Results are located in the `depfiles` containing the package, if any.
+    pub fn new(report: &AnalysisReport) -> Self {
+        let depfiles = &report.depfiles;
+        let mut rules = Rules::default();
+        let mut results = Vec::new();
+
+        for dependency in &report.status.dependencies {
+            for rejection in &dependency.rejections {
+                let rule_index = rules.index_for_rejection(&rejection.title, &rejection.source);
+                let rule = &rules.rules[rule_index];
+
+                let suppressions =
+                    rejection.suppressed.then(|| vec![Suppression { kind: "external" }]);
+
+                results.push(SarifResult {
+                    rule_id: rule.id.clone(),
+                    rule_index,
+                    level: rule.default_configuration.level,
+                    message: Message {
+                        text: format!(
+                            "{}@{}: {}",
+                            dependency.name, dependency.version, rejection.title
+                        ),
+                    },
+                    locations: locations(depfiles, &dependency.name, &dependency.version),
+                    partial_fingerprints: Fingerprints {
+                        package: format!("{}/{}", dependency.purl, rule.id),
+                    },
+                    suppressions,
+                });
+            }
+        }
+
+        for finding in &report.dependency_confusion {
+            let rule_index = rules.index_for_confusion(finding);
+            let rule = &rules.rules[rule_index];
+
+            for version in &finding.public_versions {
+                results.push(SarifResult {
+                    rule_id: rule.id.clone(),
+                    rule_index,
+                    level: rule.default_configuration.level,
+                    message: Message {
+                        text: format!(
+                            "{}@{}: {}",
+                            finding.name,
+                            version,
+                            finding.kind.description()
+                        ),
+                    },
+                    locations: locations(depfiles, &finding.name, version),
+                    partial_fingerprints: Fingerprints {
+                        package: format!(
+                            "{}/{}@{}/{}",
+                            finding.package_type, finding.name, version, rule.id
+                        ),
+                    },
+                    suppressions: None,
+                });
+            }
+        }
+
+        let driver = Driver {
+            name: "Phylum",
+            information_uri: "https://phylum.io",
+            version: env!("CARGO_PKG_VERSION"),
+            rules: rules.rules,
+        };
+
+        Self {
+            schema: SARIF_SCHEMA,
+            version: SARIF_VERSION,
+            runs: vec![Run { tool: Tool { driver }, results }],
+        }
+    }
+}
+
+/// Rule collection deduplicated by rule ID.
+#[derive(Default)]
+struct Rules {
+    rules: Vec<Rule>,
+    indices: BTreeMap<String, usize>,
+}
+
+impl Rules {
+    /// Get the rule index for a policy rejection, creating the rule if needed.
+    fn index_for_rejection(&mut self, title: &str, source: &RejectionSource) -> usize {
+        let id = match &source.tag {
+            Some(tag) => format!("{}/{tag}", source.source_type),
+            None => source.source_type.clone(),
+        };
+
+        self.index_or_insert(id, |id| {
+            let level = Level::from(source.severity);
+            let tags = source.domain.iter().map(serde_name).collect();
+
+            Rule {
+                id,
+                short_description: Message { text: title.into() },
+                full_description: source.description.clone().map(|text| Message { text }),
+                help: source.reason.clone().map(|text| Message { text }),
+                default_configuration: Configuration { level },
+                properties: RuleProperties {
+                    tags,
+                    security_severity: source.severity.map(security_severity),
+                },
+            }
+        })
+    }
+
+    /// Get the rule index for a dependency confusion finding.
+    fn index_for_confusion(&mut self, finding: &ConfusionFinding) -> usize {
+        let id = format!("dependency-confusion/{}", serde_name(&finding.kind));
+
+        self.index_or_insert(id, |id| Rule {
+            id,
+            short_description: Message { text: finding.kind.description().into() },
+            full_description: None,
+            help: None,
+            default_configuration: Configuration { level: Level::Error },
+            properties: RuleProperties {
+                tags: vec!["dependency_confusion".into()],
+                security_severity: Some(security_severity(RiskLevel::High)),
+            },
+        })
+    }
+
+    fn index_or_insert(&mut self, id: String, rule: impl FnOnce(String) -> Rule) -> usize {
+        if let Some(index) = self.indices.get(&id) {
+            return *index;
+        }
+
+        let index = self.rules.len();
+        self.indices.insert(id.clone(), index);
+        self.rules.push(rule(id));
+        index
+    }
+}
+
+/// Get the locations of a package in all depfiles.
+///
+/// Code scanning tools reject results without a location, so packages which
+/// can't be found in any depfile are located at the start of the first one.
+/// Locations are only omitted if there are no depfiles at all.
+fn locations(depfiles: &[Depfile], name: &str, version: &str) -> Vec<Location> {
+    let location = |depfile: &Depfile, start_line| Location {
+        physical_location: PhysicalLocation {
+            artifact_location: ArtifactLocation { uri: depfile.path.clone() },
+            region: Region { start_line },
+        },
+    };
+
+    let locations: Vec<_> = depfiles
+        .iter()
+        .filter(|depfile| depfile.contains(name, version))
+        .map(|depfile| location(depfile, depfile.line(name, version).unwrap_or(1)))
+        .collect();
+
+    match depfiles.first() {
+        Some(depfile) if locations.is_empty() => vec![location(depfile, 1)],
+        _ => locations,
+    }
+}
+
+/// Get the serialized name of a unit enum variant.
+fn serde_name<T: Serialize>(value: &T) -> String {
+    match serde_json::to_value(value) {
+        Ok(serde_json::Value::String(name)) => name,
+        _ => String::new(),
+    }
+}
+
+/// GitHub's numeric `security-severity` for a risk level.
+fn security_severity(level: RiskLevel) -> String {
+    let severity = match level {
+        RiskLevel::Critical => "9.5",
+        RiskLevel::High => "8.0",
+        RiskLevel::Medium => "5.5",
+        RiskLevel::Low => "2.0",
+        RiskLevel::Info => "0.0",
+    };
+    severity.into()
+}
+
+#[derive(Serialize, Debug)]
+struct Run {
+    tool: Tool,
+    results: Vec<SarifResult>,
+}
+
+#[derive(Serialize, Debug)]
+struct Tool {
+    driver: Driver,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct Driver {
+    name: &'static str,
+    information_uri: &'static str,
+    version: &'static str,
+    rules: Vec<Rule>,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct Rule {
+    id: String,
+    short_description: Message,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    full_description: Option<Message>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    help: Option<Message>,
+    default_configuration: Configuration,
+    properties: RuleProperties,
+}
+
+#[derive(Serialize, Debug)]
+struct Configuration {
+    level: Level,
+}
+
+#[derive(Serialize, Debug)]
+struct RuleProperties {
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    tags: Vec<String>,
+    #[serde(rename = "security-severity", skip_serializing_if = "Option::is_none")]
+    security_severity: Option<String>,
+}
+
+/// SARIF result severity.
+#[derive(Serialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "lowercase")]
+enum Level {
+    Note,
+    Warning,
+    Error,
+}
+
+impl From<Option<RiskLevel>> for Level {
+    fn from(severity: Option<RiskLevel>) -> Self {
+        match severity {
+            Some(RiskLevel::Info) | Some(RiskLevel::Low) => Self::Note,
+            Some(RiskLevel::Medium) => Self::Warning,
+            Some(RiskLevel::High) | Some(RiskLevel::Critical) | None => Self::Error,
+        }
+    }
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct SarifResult {
+    rule_id: String,
+    rule_index: usize,
+    level: Level,
+    message: Message,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    locations: Vec<Location>,
+    partial_fingerprints: Fingerprints,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    suppressions: Option<Vec<Suppression>>,
+}
+
+#[derive(Serialize, Debug)]
+struct Message {
+    text: String,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct Location {
+    physical_location: PhysicalLocation,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct PhysicalLocation {
+    artifact_location: ArtifactLocation,
+    region: Region,
+}
+
+#[derive(Serialize, Debug)]
+struct ArtifactLocation {
+    uri: String,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct Region {
+    start_line: usize,
+}
+
+#[derive(Serialize, Debug)]
+struct Fingerprints {
+    #[serde(rename = "phylumPackage/v1")]
+    package: String,
+}
+
+#[derive(Serialize, Debug)]
+struct Suppression {
+    kind: &'static str,
+}
+
+#[cfg(test)]
+mod tests {
+    use phylum_types::types::package::{PackageDescriptor, PackageType};
+    use serde_json::json;
+
+    use super::*;
+    use crate::types::{EvaluatedDependency, PolicyRejection, RiskDomain};
+
+    fn rejection(tag: &str, severity: RiskLevel, suppressed: bool) -> PolicyRejection {
+        PolicyRejection {
+            title: format!("{tag} title"),
+            source: RejectionSource {
+                source_type: "issue".into(),
+                tag: Some(tag.into()),
+                domain: Some(RiskDomain::Vulnerabilities),
+                severity: Some(severity),
+                description: None,
+                reason: None,
+            },
+            suppressed,
+        }
+    }
+
+    #[test]
+    fn rules_and_results() {
+        let status = PolicyEvaluationResponseRaw {
+            is_failure: true,
+            incomplete_packages_count: 0,
+            help: String::new(),
+            dependencies: vec![
+                EvaluatedDependency {
+                    purl: "pkg:npm/a@1.0.0".into(),
+                    registry: "npm".into(),
+                    name: "a".into(),
+                    version: "1.0.0".into(),
+                    rejections: vec![
+                        rejection("CVE-1", RiskLevel::Critical, false),
+                        rejection("CVE-2", RiskLevel::Medium, true),
+                    ],
+                },
+                EvaluatedDependency {
+                    purl: "pkg:npm/b@2.0.0".into(),
+                    registry: "npm".into(),
+                    name: "b".into(),
+                    version: "2.0.0".into(),
+                    rejections: vec![rejection("CVE-1", RiskLevel::Critical, false)],
+                },
+            ],
+            job_link: None,
+        };
+
+        let depfile = Depfile {
+            path: "package-lock.json".into(),
+            format: "npm",
+            absolute_path: None,
+            contents: Some("{\n  \"node_modules/b\": {\n    \"version\": \"2.0.0\"\n".into()),
+            packages: vec![PackageDescriptor {
+                name: "b".into(),
+                version: "2.0.0".into(),
+                package_type: PackageType::Npm,
+            }],
+        };
+
+        let report =
+            AnalysisReport { status, depfiles: vec![depfile], dependency_confusion: Vec::new() };
+        let sarif = serde_json::to_value(SarifLog::new(&report)).unwrap();
+
+        assert_eq!(sarif["version"], "2.1.0");
+        let run = &sarif["runs"][0];
+
+        let rules = run["tool"]["driver"]["rules"].as_array().unwrap();
+        assert_eq!(rules.len(), 2);
+        assert_eq!(rules[0]["id"], "issue/CVE-1");
+        assert_eq!(rules[0]["properties"]["tags"], json!(["vulnerability"]));
+        assert_eq!(rules[0]["defaultConfiguration"]["level"], "error");
+        assert_eq!(rules[0]["properties"]["security-severity"], "9.5");
+        assert_eq!(rules[1]["id"], "issue/CVE-2");
+        assert_eq!(rules[1]["defaultConfiguration"]["level"], "warning");
+
+        let results = run["results"].as_array().unwrap();
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[1]["ruleIndex"], 1);
+        assert_eq!(results[1]["suppressions"], json!([{ "kind": "external" }]));
+        assert_eq!(results[2]["ruleId"], "issue/CVE-1");
+        assert_eq!(results[2]["message"]["text"], "b@2.0.0: CVE-1 title");
+        assert_eq!(
+            results[0]["locations"][0]["physicalLocation"],
+            json!({ "artifactLocation": { "uri": "package-lock.json" }, "region": { "startLine": 1 } })
+        );
+        assert_eq!(
+            results[2]["locations"][0]["physicalLocation"],
+            json!({ "artifactLocation": { "uri": "package-lock.json" }, "region": { "startLine": 2 } })
+        );
+    }
+}
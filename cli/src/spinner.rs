@@ -1,4 +1,3 @@
-use std::io::{self, IsTerminal};
 use std::time;
 
 use futures::Future;
@@ -6,6 +5,8 @@ use tokio::sync::mpsc::error::TryRecvError;
 use tokio::sync::mpsc::{self, Receiver, Sender};
 use tokio::task::JoinHandle;
 
+use crate::progress;
+
 const SPINNER_DELAY: u64 = 40;
 const SPINNER_DOTS: [&str; 56] = [
     "⢀⠀", "⡀⠀", "⠄⠀", "⢂⠀", "⡂⠀", "⠅⠀", "⢃⠀", "⡃⠀", "⠍⠀", "⢋⠀", "⡋⠀", "⠍⠁", "⢋⠁", "⡋⠁", "⠍⠉", "⠋⠉",
@@ -15,11 +16,12 @@ const SPINNER_DOTS: [&str; 56] = [
 ];
 
 /// A CLI spinner. All public constructors are gated behind a
-/// check that stderr is a TTY. If this is not the case, every method is a
-/// no-op.
+/// check that stderr is a TTY. If this is not the case, messages are printed
+/// as plain text lines or ignored, depending on the progress mode.
 pub struct Spinner {
     tx: Option<Sender<Command>>,
     handle: Option<JoinHandle<()>>,
+    text_lines: bool,
 }
 
 enum Command {
@@ -40,12 +42,16 @@ impl Spinner {
     }
 
     fn new_inner(message: Option<String>) -> Self {
-        if io::stderr().is_terminal() {
+        if progress::animate() {
             let (tx, rx) = mpsc::channel(10);
             let handle = tokio::spawn(Self::spin(rx, message));
-            Self { tx: Some(tx), handle: Some(handle) }
+            Self { tx: Some(tx), handle: Some(handle), text_lines: false }
         } else {
-            Self { tx: None, handle: None }
+            let text_lines = progress::text_lines();
+            if let Some(message) = message.filter(|_| text_lines) {
+                eprintln!("{message}");
+            }
+            Self { tx: None, handle: None, text_lines }
         }
     }
 
@@ -80,6 +86,8 @@ impl Spinner {
     pub async fn set_message(&self, message: impl Into<String>) {
         if let Some(tx) = &self.tx {
             tx.send(Command::Message(Some(message.into()))).await.ok();
+        } else if self.text_lines {
+            eprintln!("{}", message.into());
         }
     }
 
@@ -0,0 +1,407 @@
+//! Local suppressions of policy rejections.
+//!
+//! Suppressions are read from a `.phylum-suppressions.toml` file in the
+//! project root and applied to the policy evaluation results before they are
+//! reported:
+//!
+//! ```toml
+//! [[suppression]]
+//! purl = "pkg:npm/lodash"
+//! version = "<4.17.21"
+//! tag = "CVE-2021-23337"
+//! justification = "Template compilation is never used with user input"
+//! expires = 2025-06-30
+//! ```
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use anyhow::{anyhow, Context, Result};
+use chrono::NaiveDate;
+use phylum_lockfile::version::{Version, VersionReq};
+use phylum_types::types::package::PackageType;
+use serde::{Deserialize, Deserializer, Serialize};
+
+use crate::types::{EvaluatedDependency, PolicyEvaluationResponseRaw, PolicyRejection};
+
+/// Name of the suppressions file.
+pub const SUPPRESSIONS_FILE: &str = ".phylum-suppressions.toml";
+
+/// Suppressions loaded from a suppressions file.
+#[derive(Deserialize, Serialize, Default, Debug)]
+pub struct Suppressions {
+    #[serde(default, rename = "suppression")]
+    pub entries: Vec<Suppression>,
+}
+
+/// Suppression of matching policy rejections.
+///
+/// All specified criteria must match for a rejection to be suppressed.
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct Suppression {
+    /// Package URL, with or without version.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub purl: Option<String>,
+    /// Version requirement in the syntax of the package's ecosystem.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    /// Rejection tag, like a CVE or Phylum issue ID.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tag: Option<String>,
+    /// Rejection title.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// Reason for accepting the rejection.
+    pub justification: String,
+    /// Last day on which the suppression is applied.
+    #[serde(
+        default,
+        deserialize_with = "deserialize_date",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub expires: Option<NaiveDate>,
+}
+
+impl Suppressions {
+    /// Load suppressions for the current project.
+    ///
+    /// The suppressions file is searched in the project root, falling back
+    /// to the current directory. A missing file results in no suppressions.
+    pub fn load_current() -> Result<Self> {
+        match find_file() {
+            Some(path) => Self::load(&path),
+            None => Ok(Self::default()),
+        }
+    }
+
+    /// Load suppressions from a file.
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Could not read suppressions {:?}", path.display()))?;
+        Self::from_toml(&content)
+            .with_context(|| format!("Invalid suppressions {:?}", path.display()))
+    }
+
+    /// Parse and validate suppressions.
+    fn from_toml(content: &str) -> Result<Self> {
+        let suppressions: Self = toml::from_str(content)?;
+
+        for (i, entry) in suppressions.entries.iter().enumerate() {
+            entry.validate().with_context(|| format!("Invalid suppression #{}", i + 1))?;
+        }
+
+        Ok(suppressions)
+    }
+
+    /// Check if there are no suppressions.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Suppressions which are no longer applied.
+    pub fn expired(&self, today: NaiveDate) -> impl Iterator<Item = &Suppression> {
+        self.entries.iter().filter(move |entry| entry.is_expired(today))
+    }
+
+    /// Suppress all matching rejections.
+    ///
+    /// Expired suppressions are ignored. Returns the number of suppressed
+    /// rejections.
+    pub fn apply(&self, status: &mut PolicyEvaluationResponseRaw, today: NaiveDate) -> usize {
+        let active: Vec<_> = self.entries.iter().filter(|entry| !entry.is_expired(today)).collect();
+        status.suppress_rejections(|dependency, rejection| {
+            active.iter().any(|entry| entry.matches(dependency, rejection))
+        })
+    }
+}
+
+impl Suppression {
+    /// Check if the suppression is no longer applied.
+    pub fn is_expired(&self, today: NaiveDate) -> bool {
+        self.expires.is_some_and(|expires| expires < today)
+    }
+
+    /// Human-readable description of the suppression's criteria.
+    pub fn description(&self) -> String {
+        let mut criteria = Vec::new();
+        if let Some(purl) = &self.purl {
+            criteria.push(purl.clone());
+        }
+        if let Some(version) = &self.version {
+            criteria.push(format!("version {version}"));
+        }
+        if let Some(tag) = &self.tag {
+            criteria.push(tag.clone());
+        }
+        if let Some(title) = &self.title {
+            criteria.push(format!("{title:?}"));
+        }
+        criteria.join(" ")
+    }
+
+    /// Ensure the suppression can be applied.
+    fn validate(&self) -> Result<()> {
+        if self.purl.is_none()
+            && self.version.is_none()
+            && self.tag.is_none()
+            && self.title.is_none()
+        {
+            return Err(anyhow!("at least one of purl, version, tag or title is required"));
+        }
+
+        // Version requirements are only meaningful for a specific package.
+        if self.version.is_some() && self.purl.is_none() {
+            return Err(anyhow!("version requires a purl"));
+        }
+
+        if self.justification.trim().is_empty() {
+            return Err(anyhow!("justification must not be empty"));
+        }
+
+        if let Some(purl) = &self.purl {
+            let parsed =
+                Purl::parse(purl).ok_or_else(|| anyhow!("invalid package URL {purl:?}"))?;
+
+            // Version ranges can only be checked for a known ecosystem.
+            if let Some(version) = &self.version {
+                let package_type = parsed
+                    .package_type()
+                    .ok_or_else(|| anyhow!("unsupported package URL {purl:?}"))?;
+                VersionReq::parse(&package_type, version)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Check if this suppression matches a package's rejection.
+    fn matches(&self, dependency: &EvaluatedDependency, rejection: &PolicyRejection) -> bool {
+        let dependency_purl = match Purl::parse(&dependency.purl) {
+            Some(purl) => purl,
+            None => return false,
+        };
+
+        if let Some(purl) = self.purl.as_deref().and_then(Purl::parse) {
+            if purl.package != dependency_purl.package {
+                return false;
+            }
+
+            if purl.version.is_some() && purl.version != dependency_purl.version {
+                return false;
+            }
+        }
+
+        if let Some(requirement) = &self.version {
+            let package_type = match dependency_purl.package_type() {
+                Some(package_type) => package_type,
+                None => return false,
+            };
+            let requirement = VersionReq::parse(&package_type, requirement);
+            let version = Version::parse(&package_type, &dependency.version);
+            match (requirement, version) {
+                (Ok(requirement), Ok(version)) if requirement.matches(&version) => (),
+                _ => return false,
+            }
+        }
+
+        if let Some(tag) = &self.tag {
+            if !rejection.source.tag.as_ref().is_some_and(|other| tag.eq_ignore_ascii_case(other)) {
+                return false;
+            }
+        }
+
+        match &self.title {
+            Some(title) => title == &rejection.title,
+            None => true,
+        }
+    }
+}
+
+/// Package URL split into its package and version.
+struct Purl<'a> {
+    /// Package URL without version, qualifiers or subpath.
+    package: &'a str,
+    version: Option<&'a str>,
+}
+
+impl<'a> Purl<'a> {
+    fn parse(purl: &'a str) -> Option<Self> {
+        let purl = purl.split(['?', '#']).next()?;
+        if !purl.starts_with("pkg:") {
+            return None;
+        }
+
+        // The version always follows the name, which is the last path segment. This
+        // avoids treating an unencoded `@` in scoped npm namespaces as a version.
+        let name_start = purl.rfind('/').map_or(0, |index| index + 1);
+        Some(match purl[name_start..].rfind('@') {
+            Some(index) => {
+                let (package, version) = purl.split_at(name_start + index);
+                Self { package, version: Some(&version[1..]) }
+            },
+            None => Self { package: purl, version: None },
+        })
+    }
+
+    /// Ecosystem of the package.
+    fn package_type(&self) -> Option<PackageType> {
+        let purl_type = self.package.strip_prefix("pkg:")?.split('/').next()?;
+        PackageType::from_str(purl_type).ok()
+    }
+}
+
+/// Find the suppressions file for the current project.
+pub fn find_file() -> Option<PathBuf> {
+    let project_root = phylum_project::get_current_project().map(|project| project.root().clone());
+    let path = project_root.unwrap_or_default().join(SUPPRESSIONS_FILE);
+    path.exists().then_some(path)
+}
+
+/// Deserialize a date from a TOML date or `YYYY-MM-DD` string.
+fn deserialize_date<'de, D>(deserializer: D) -> Result<Option<NaiveDate>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum DateValue {
+        Toml(toml::value::Datetime),
+        Text(String),
+    }
+
+    let date = match DateValue::deserialize(deserializer)? {
+        DateValue::Toml(toml::value::Datetime { date: Some(date), time: None, offset: None }) => {
+            NaiveDate::from_ymd_opt(date.year.into(), date.month.into(), date.day.into())
+        },
+        DateValue::Toml(_) => None,
+        DateValue::Text(text) => NaiveDate::parse_from_str(&text, "%Y-%m-%d").ok(),
+    };
+
+    date.map(Some).ok_or_else(|| serde::de::Error::custom("expected date in YYYY-MM-DD format"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{RejectionSource, RiskLevel};
+
+    fn status() -> PolicyEvaluationResponseRaw {
+        let rejection = |tag: &str, title: &str| PolicyRejection {
+            title: title.into(),
+            source: RejectionSource {
+                source_type: "vulnerability".into(),
+                tag: Some(tag.into()),
+                domain: None,
+                severity: Some(RiskLevel::High),
+                description: None,
+                reason: None,
+            },
+            suppressed: false,
+        };
+
+        PolicyEvaluationResponseRaw {
+            is_failure: true,
+            incomplete_packages_count: 0,
+            help: String::new(),
+            dependencies: vec![EvaluatedDependency {
+                purl: "pkg:npm/lodash@4.17.20".into(),
+                registry: "npm".into(),
+                name: "lodash".into(),
+                version: "4.17.20".into(),
+                rejections: vec![
+                    rejection("CVE-2021-23337", "Command injection"),
+                    rejection("CVE-2020-28500", "ReDoS"),
+                ],
+            }],
+            job_link: None,
+        }
+    }
+
+    fn today() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 6, 1).unwrap()
+    }
+
+    #[test]
+    fn suppress_matching_rejections() {
+        let suppressions = Suppressions::from_toml(
+            r#"
+            [[suppression]]
+            purl = "pkg:npm/lodash"
+            version = "<4.17.21"
+            tag = "CVE-2021-23337"
+            justification = "Not reachable"
+            expires = 2024-12-31
+
+            [[suppression]]
+            title = "ReDoS"
+            justification = "Only used with trusted input"
+            "#,
+        )
+        .unwrap();
+
+        let mut status = status();
+        assert_eq!(suppressions.apply(&mut status, today()), 2);
+        assert!(!status.is_failure);
+    }
+
+    #[test]
+    fn skip_non_matching() {
+        let suppressions = Suppressions::from_toml(
+            r#"
+            [[suppression]]
+            purl = "pkg:npm/lodash@4.17.21"
+            justification = "Different version"
+
+            [[suppression]]
+            purl = "pkg:npm/lodash"
+            version = ">=4.17.21"
+            justification = "Different range"
+
+            [[suppression]]
+            tag = "CVE-2021-23337"
+            justification = "Expired"
+            expires = "2024-05-31"
+            "#,
+        )
+        .unwrap();
+
+        let mut status = status();
+        assert_eq!(suppressions.apply(&mut status, today()), 0);
+        assert!(status.is_failure);
+        assert_eq!(suppressions.expired(today()).count(), 1);
+    }
+
+    #[test]
+    fn require_justification() {
+        let missing = "[[suppression]]\ntag = \"CVE-2021-23337\"\n";
+        assert!(Suppressions::from_toml(missing).is_err());
+
+        let empty = "[[suppression]]\ntag = \"CVE-2021-23337\"\njustification = \" \"\n";
+        assert!(Suppressions::from_toml(empty).is_err());
+
+        let unrestricted = "[[suppression]]\njustification = \"Everything\"\n";
+        assert!(Suppressions::from_toml(unrestricted).is_err());
+
+        let invalid_purl = "[[suppression]]\npurl = \"npm/lodash\"\njustification = \"Typo\"\n";
+        assert!(Suppressions::from_toml(invalid_purl).is_err());
+
+        let version_only = "[[suppression]]\nversion = \"<1.0.0\"\njustification = \"All\"\n";
+        assert!(Suppressions::from_toml(version_only).is_err());
+    }
+
+    #[test]
+    fn parse_scoped_purl() {
+        let purl = Purl::parse("pkg:npm/@scope/name").unwrap();
+        assert_eq!(purl.package, "pkg:npm/@scope/name");
+        assert_eq!(purl.version, None);
+
+        let purl = Purl::parse("pkg:npm/@scope/name@1.0.0?arch=x86").unwrap();
+        assert_eq!(purl.package, "pkg:npm/@scope/name");
+        assert_eq!(purl.version, Some("1.0.0"));
+
+        let purl = Purl::parse("pkg:npm/%40scope/name@1.0.0").unwrap();
+        assert_eq!(purl.package, "pkg:npm/%40scope/name");
+        assert_eq!(purl.version, Some("1.0.0"));
+    }
+}
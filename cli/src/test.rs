@@ -24,7 +24,7 @@ pub mod mockito {
     use wiremock::matchers::{method, path};
     use wiremock::{Mock, MockBuilder, MockServer, Request, Respond, ResponseTemplate};
 
-    use crate::api::{PhylumApi, PhylumApiError};
+    use crate::api::PhylumApi;
     use crate::auth::{LocksmithServerSettings, OidcServerSettings};
     use crate::config::{AuthInfo, Config, ConnectionInfo};
 
@@ -33,12 +33,15 @@ pub mod mockito {
     pub const DUMMY_ACCESS_TOKEN: &str = "DUMMY_ACCESS_TOKEN";
     pub const DUMMY_ID_TOKEN: &str = "DUMMY_ID_TOKEN";
     pub const DUMMY_AUTH_CODE: &str = "DUMMY_AUTH_CODE";
+    pub const DUMMY_DEVICE_CODE: &str = "DUMMY_DEVICE_CODE";
+    pub const DUMMY_USER_CODE: &str = "DUMMY-CODE";
 
     pub const OIDC_URI: &str = "api/v0/.well-known/openid-configuration";
     pub const LOCKSMITH_URI: &str = "locksmith/v1/.well-known/locksmith-configuration";
     pub const AUTH_URI: &str = "auth";
     pub const USER_URI: &str = "user";
     pub const TOKEN_URI: &str = "token";
+    pub const DEVICE_AUTH_URI: &str = "device";
     pub const LOCKSMITH_TOKEN_URI: &str = "locksmith-token";
 
     pub struct ResponderFn<F>(F)
@@ -71,7 +74,10 @@ pub mod mockito {
 
     pub fn build_oidc_server_settings_mock_response(base_uri: &str) -> OidcServerSettings {
         let base_url = Url::from_str(base_uri).expect("Failed to parse base url");
-        OidcServerSettings { token_endpoint: base_url.join(TOKEN_URI).unwrap() }
+        OidcServerSettings {
+            token_endpoint: base_url.join(TOKEN_URI).unwrap(),
+            device_authorization_endpoint: Some(base_url.join(DEVICE_AUTH_URI).unwrap()),
+        }
     }
 
     pub fn build_locksmith_server_settings_mock_response(
@@ -127,6 +133,23 @@ pub mod mockito {
             .mount(&mock_server)
             .await;
 
+        let base_url = mock_server.uri();
+
+        // Set device authorization endpoint response
+        Mock::given(method("POST"))
+            .and(path(DEVICE_AUTH_URI))
+            .respond_with_fn(move |_| {
+                ResponseTemplate::new(200).set_body_json(json!({
+                    "device_code": DUMMY_DEVICE_CODE,
+                    "user_code": DUMMY_USER_CODE,
+                    "verification_uri": format!("{base_url}/{DEVICE_AUTH_URI}"),
+                    "expires_in": 600,
+                    "interval": 0,
+                }))
+            })
+            .mount(&mock_server)
+            .await;
+
         // Set token endpoint response
         Mock::given(method("POST"))
             .and(path(TOKEN_URI))
@@ -163,9 +186,9 @@ pub mod mockito {
         AuthInfo::default()
     }
 
-    pub async fn build_phylum_api(mock_server: &MockServer) -> Result<PhylumApi, PhylumApiError> {
+    pub async fn build_phylum_api(mock_server: &MockServer) -> anyhow::Result<PhylumApi> {
         let mut config = Config::default();
-        config.connection = ConnectionInfo { uri: mock_server.uri() };
+        config.connection = ConnectionInfo { uri: mock_server.uri(), ..Default::default() };
         config.auth_info = build_authenticated_auth_info();
         let phylum = PhylumApi::new(config, None).await?;
         Ok(phylum)
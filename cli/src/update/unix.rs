@@ -4,7 +4,6 @@ use std::str;
 
 use anyhow::{anyhow, Context};
 use log::debug;
-use reqwest::Client;
 use rsa::pkcs1v15::{Signature, VerifyingKey};
 use rsa::pkcs8::DecodePublicKey;
 use rsa::sha2::Sha256;
@@ -15,7 +14,8 @@ use serde::de::DeserializeOwned;
 use wiremock::MockServer;
 use zip::ZipArchive;
 
-use crate::app::USER_AGENT;
+use crate::config::ConnectionInfo;
+use crate::http;
 use crate::spinner::Spinner;
 use crate::types::{GithubRelease, GithubReleaseAsset};
 
@@ -28,8 +28,8 @@ const GITHUB_URI: &str = "https://api.github.com";
 const CURRENT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
 /// Check if a newer version of the client is available
-pub async fn needs_update(prerelease: bool) -> bool {
-    let updater = ApplicationUpdater::default();
+pub async fn needs_update(prerelease: bool, connection: &ConnectionInfo) -> bool {
+    let updater = ApplicationUpdater::new(connection.clone(), false);
     match updater.get_latest_version(prerelease).await {
         Ok(latest) => updater.needs_update(CURRENT_VERSION, &latest),
         Err(e) => {
@@ -40,8 +40,12 @@ pub async fn needs_update(prerelease: bool) -> bool {
 }
 
 /// Perform a self-update to the latest version
-pub async fn do_update(prerelease: bool, ignore_certs: bool) -> anyhow::Result<String> {
-    let updater = ApplicationUpdater::default().with_ignore_certs(ignore_certs);
+pub async fn do_update(
+    prerelease: bool,
+    connection: &ConnectionInfo,
+    ignore_certs: bool,
+) -> anyhow::Result<String> {
+    let updater = ApplicationUpdater::new(connection.clone(), ignore_certs);
     let ver =
         updater.get_latest_version(prerelease).await.context("Failed to get the latest version")?;
     updater.do_update(ver).await.map(|ver| format!("Successfully updated to {}!", ver.tag_name))
@@ -50,27 +54,18 @@ pub async fn do_update(prerelease: bool, ignore_certs: bool) -> anyhow::Result<S
 #[derive(Debug)]
 struct ApplicationUpdater {
     github_uri: String,
+    connection: ConnectionInfo,
     ignore_certs: bool,
 }
 
-impl Default for ApplicationUpdater {
-    fn default() -> Self {
-        ApplicationUpdater { github_uri: GITHUB_URI.to_owned(), ignore_certs: false }
-    }
-}
-
 impl ApplicationUpdater {
-    fn with_ignore_certs(mut self, ignore_certs: bool) -> Self {
-        self.ignore_certs = ignore_certs;
-        self
+    fn new(connection: ConnectionInfo, ignore_certs: bool) -> Self {
+        ApplicationUpdater { github_uri: GITHUB_URI.to_owned(), connection, ignore_certs }
     }
 
     /// Generic function for fetching data via HTTP GET.
     async fn http_get(&self, url: &str) -> anyhow::Result<reqwest::Response> {
-        let client = Client::builder()
-            .user_agent(USER_AGENT.as_str())
-            .danger_accept_invalid_certs(self.ignore_certs)
-            .build()?;
+        let client = http::client_builder(&self.connection, self.ignore_certs)?.build()?;
         let response = client.get(url).send().await?;
         Ok(response)
     }
@@ -135,7 +130,11 @@ impl ApplicationUpdater {
     /// Build a instance for use in tests
     #[cfg(test)]
     fn build_test_instance(mock_server: MockServer) -> Self {
-        ApplicationUpdater { github_uri: mock_server.uri(), ignore_certs: false }
+        ApplicationUpdater {
+            github_uri: mock_server.uri(),
+            connection: ConnectionInfo::default(),
+            ignore_certs: false,
+        }
     }
 
     /// Check for an update by querying the Github releases page.
@@ -275,7 +274,7 @@ mod tests {
         let data = include_bytes!("hello.txt");
         let sig = include_bytes!("hello.txt.signature");
 
-        let updater = ApplicationUpdater::default();
+        let updater = ApplicationUpdater::new(ConnectionInfo::default(), false);
         assert!(updater.has_valid_signature(data, sig));
 
         // Flip some bits and make sure it fails
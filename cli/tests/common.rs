@@ -8,7 +8,8 @@ use std::{env, fs};
 
 use assert_cmd::assert::Assert;
 use assert_cmd::Command;
-use phylum_cli::api::{PhylumApi, PhylumApiError, ResponseError};
+use phylum_api::ApiError;
+use phylum_cli::api::PhylumApi;
 use phylum_cli::config::{AuthInfo, Config, ConnectionInfo};
 #[cfg(feature = "extensions")]
 use phylum_cli::permissions::Permissions;
@@ -52,7 +53,7 @@ impl TestCliBuilder {
     pub fn with_config(mut self, config: impl Into<Option<Config>>) -> Self {
         self.config = Some(config.into().unwrap_or_else(|| {
             let mut config = Config::default();
-            config.connection = ConnectionInfo { uri: API_URL.into() };
+            config.connection = ConnectionInfo { uri: API_URL.into(), ..Default::default() };
             config
         }));
         self
@@ -234,14 +235,13 @@ pub fn create_lockfile(dir: &Path) -> PathBuf {
 pub async fn create_project() -> &'static str {
     let offline_access = Some(RefreshToken::new(env::var("PHYLUM_API_KEY").unwrap()));
     let mut config = Config::default();
-    config.connection = ConnectionInfo { uri: API_URL.into() };
+    config.connection = ConnectionInfo { uri: API_URL.into(), ..Default::default() };
     config.auth_info = AuthInfo::new(offline_access);
 
     // Attempt to create the project, ignoring conflicts.
     let api = PhylumApi::new(config, None).await.unwrap();
     match api.create_project(PROJECT_NAME, None, None, None).await {
-        Ok(_) | Err(PhylumApiError::Response(ResponseError { code: StatusCode::CONFLICT, .. })) => {
-        },
+        Ok(_) | Err(phylum_api::Error::Api(ApiError { status: StatusCode::CONFLICT, .. })) => {},
         err @ Err(_) => {
             err.unwrap();
         },
@@ -23,7 +23,7 @@ fn ignore_empty_token() {
     const CONFIG_TOKEN: &str = "CONFIGTOKEN";
 
     let mut config = Config::default();
-    config.connection = ConnectionInfo { uri: API_URL.into() };
+    config.connection = ConnectionInfo { uri: API_URL.into(), ..Default::default() };
     config.auth_info = AuthInfo::new(Some(RefreshToken::new(CONFIG_TOKEN)));
 
     TestCli::builder()
@@ -6,6 +6,7 @@ mod end_to_end;
 mod config;
 #[cfg(feature = "extensions")]
 mod extensions;
+mod offline;
 mod parse;
 #[cfg(unix)]
 mod sandbox;
@@ -0,0 +1,62 @@
+//! End-to-end tests against the in-memory mock API server.
+
+use phylum_cli::config::{AuthInfo, Config, ConnectionInfo};
+use phylum_mock_server::{MockServer, Policy, API_KEY};
+use phylum_types::types::auth::RefreshToken;
+use predicates::prelude::*;
+use serde_json::json;
+
+use crate::common::{create_lockfile, TestCli};
+
+/// Create a CLI config authenticated with the mock server.
+fn config(server: &MockServer) -> Config {
+    let mut config = Config::default();
+    config.connection = ConnectionInfo { uri: server.uri(), ..Default::default() };
+    config.auth_info = AuthInfo::new(Some(RefreshToken::new(API_KEY)));
+    config
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn create_and_list_project() {
+    let server = MockServer::start().await.unwrap();
+    let test_cli = TestCli::builder().with_config(config(&server)).cwd_temp().build();
+
+    test_cli.run(["project", "create", "offline"]).success();
+    test_cli.run(["project", "create", "offline"]).failure();
+
+    test_cli
+        .run(["project", "list", "--json"])
+        .success()
+        .stdout(predicate::str::contains("\"name\": \"offline\""));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn analyze_scripted_policy() {
+    let policy: Policy = serde_json::from_value(json!({
+        "rules": [{ "name": "accepts", "outcome": "reject", "title": "Scripted rejection" }],
+    }))
+    .unwrap();
+    let server = MockServer::builder().policy(policy).start().await.unwrap();
+    let test_cli = TestCli::builder().with_config(config(&server)).cwd_temp().build();
+
+    let lockfile = create_lockfile(test_cli.temp_path());
+    let lockfile = lockfile.to_str().unwrap();
+
+    test_cli.run(["project", "create", "offline"]).success();
+    test_cli
+        .run(["analyze", "--skip-sandbox", "-p", "offline", lockfile])
+        .code(100)
+        .stdout(predicate::str::contains("Scripted rejection"));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn analyze_without_rejections() {
+    let server = MockServer::start().await.unwrap();
+    let test_cli = TestCli::builder().with_config(config(&server)).cwd_temp().build();
+
+    let lockfile = create_lockfile(test_cli.temp_path());
+    let lockfile = lockfile.to_str().unwrap();
+
+    test_cli.run(["project", "create", "offline"]).success();
+    test_cli.run(["analyze", "--skip-sandbox", "-p", "offline", lockfile]).success();
+}
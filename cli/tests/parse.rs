@@ -47,3 +47,26 @@ fn parse_with_project_lockfile_relative_paths() {
     let not_sensitive_dir = predicate::str::contains("sensitive_dir_name").not();
     test_cli.cmd().args(["parse"]).assert().success().stdout(not_sensitive_dir);
 }
+
+#[test]
+fn parse_manifest_without_generation_json_error() {
+    // Setup CLI with temp dir.
+    let test_cli = TestCli::builder().cwd_temp().build();
+    let temp_path = test_cli.temp_path();
+
+    fs::write(temp_path.join("package.json"), "{}").unwrap();
+
+    let output = test_cli
+        .cmd()
+        .args(["parse", "--error-format", "json", "--no-generation", "-t", "npm", "package.json"])
+        .assert()
+        .code(20)
+        .get_output()
+        .stderr
+        .clone();
+
+    let error: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    assert_eq!(error["code"], "manifest_without_generation");
+    assert_eq!(error["exit_code"], 20);
+    assert_eq!(error["kind"], "parse");
+}
@@ -103,7 +103,7 @@ declare namespace Phylum {
    * @param packages - List of packages to analyze
    * @param project - Project name. If undefined, the `.phylum_project` file will be used
    * @param group - Group name
-   * @param label - Analysis label for this job
+   * @param label - Analysis label for this job (default: current git branch)
    * @param organization - Phylum organization which owns the group
    *
    * @returns Analyze Job ID, which can later be queried with `getJobStatus`.
@@ -0,0 +1,809 @@
+//! Drift detection between manifests and their lockfiles.
+//!
+//! A lockfile drifts from its manifest when dependencies are added, removed
+//! or have their requirements changed in the manifest without regenerating
+//! the lockfile.
+
+use std::collections::{BTreeMap, HashSet};
+use std::ffi::OsStr;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, Context};
+use phylum_types::types::package::PackageType;
+use serde::Serialize;
+use serde_json::Value as JsonValue;
+use serde_yaml::Value as YamlValue;
+use toml::{Table as TomlTable, Value as TomlValue};
+
+use crate::version::VersionReq;
+use crate::{find_manifest_lockfile, normalize_name, LockfileFormat, Package};
+
+/// Manifest file names supported for drift detection.
+const MANIFEST_NAMES: [&str; 4] = ["package.json", "pyproject.toml", "Cargo.toml", "Gemfile"];
+
+/// Check if drift detection is supported for a manifest.
+///
+/// The file does not need to exist.
+pub fn is_supported_manifest(path: &Path) -> bool {
+    path.file_name().and_then(OsStr::to_str).is_some_and(|name| MANIFEST_NAMES.contains(&name))
+}
+
+/// Dependency declared in a manifest.
+#[derive(Serialize, Clone, PartialEq, Eq, Debug)]
+pub struct DeclaredDependency {
+    pub name: String,
+    /// Declared version requirement.
+    ///
+    /// This is `None` for dependencies which are not installed from a package
+    /// registry, like git or path dependencies.
+    pub requirement: Option<String>,
+}
+
+impl DeclaredDependency {
+    fn new(name: impl Into<String>, requirement: Option<String>) -> Self {
+        Self { name: name.into(), requirement }
+    }
+}
+
+/// Direct dependencies declared in a manifest.
+#[derive(Clone, Debug)]
+pub struct Manifest {
+    /// Name of the manifest's package, if it declares one.
+    pub name: Option<String>,
+    pub package_type: PackageType,
+    pub dependencies: Vec<DeclaredDependency>,
+    /// Whether dependencies are also declared outside of the manifest, like
+    /// by a Gemfile's `gemspec`.
+    pub external_dependencies: bool,
+}
+
+impl Manifest {
+    /// Parse the declared dependencies of a manifest.
+    ///
+    /// The manifest format is identified by the file name of `path`.
+    pub fn parse(path: &Path, data: &str) -> anyhow::Result<Self> {
+        match path.file_name().and_then(OsStr::to_str) {
+            Some("package.json") => Self::parse_package_json(data),
+            Some("pyproject.toml") => Self::parse_pyproject(data),
+            Some("Cargo.toml") => Self::parse_cargo_toml(data),
+            Some("Gemfile") => Ok(Self::parse_gemfile(data)),
+            _ => Err(anyhow!("Unsupported manifest: {path:?}")),
+        }
+    }
+
+    /// Parse npm's `package.json`.
+    fn parse_package_json(data: &str) -> anyhow::Result<Self> {
+        let manifest: JsonValue = serde_json::from_str(data)?;
+
+        let mut dependencies = Vec::new();
+        for key in ["dependencies", "devDependencies", "optionalDependencies"] {
+            let deps = match manifest.get(key).and_then(|deps| deps.as_object()) {
+                Some(deps) => deps,
+                None => continue,
+            };
+
+            for (name, specifier) in deps {
+                let specifier = specifier.as_str().unwrap_or_default();
+
+                // Lockfiles record aliased packages under their real name.
+                let dependency = match specifier.strip_prefix("npm:") {
+                    Some(alias) => {
+                        match alias.rsplit_once('@').filter(|(name, _)| !name.is_empty()) {
+                            Some((name, range)) => {
+                                DeclaredDependency::new(name, Some(range.into()))
+                            },
+                            None => DeclaredDependency::new(alias, None),
+                        }
+                    },
+                    None => DeclaredDependency::new(name, npm_registry_range(specifier)),
+                };
+                dependencies.push(dependency);
+            }
+        }
+
+        let name = manifest.get("name").and_then(|name| name.as_str()).map(String::from);
+
+        Ok(Self {
+            name,
+            package_type: PackageType::Npm,
+            dependencies,
+            external_dependencies: false,
+        })
+    }
+
+    /// Parse Python's `pyproject.toml`.
+    ///
+    /// This supports both PEP 621 project metadata and Poetry's dependency
+    /// tables.
+    fn parse_pyproject(data: &str) -> anyhow::Result<Self> {
+        let manifest: TomlTable = toml::from_str(data)?;
+
+        let mut dependencies = Vec::new();
+
+        // PEP 621 dependencies.
+        let project = manifest.get("project");
+        let project_deps = project.and_then(|project| project.get("dependencies"));
+        let optional_deps = project
+            .and_then(|project| project.get("optional-dependencies"))
+            .and_then(|deps| deps.as_table())
+            .into_iter()
+            .flat_map(|groups| groups.values());
+        for deps in project_deps.into_iter().chain(optional_deps) {
+            let specs = deps.as_array().into_iter().flatten().filter_map(|spec| spec.as_str());
+            dependencies.extend(specs.filter_map(parse_pep508));
+        }
+
+        // Poetry dependencies.
+        let poetry = manifest.get("tool").and_then(|tool| tool.get("poetry"));
+        let groups = poetry
+            .and_then(|poetry| poetry.get("group"))
+            .and_then(|groups| groups.as_table())
+            .into_iter()
+            .flat_map(|groups| groups.values())
+            .filter_map(|group| group.get("dependencies"));
+        let poetry_deps = poetry
+            .into_iter()
+            .flat_map(|poetry| [poetry.get("dependencies"), poetry.get("dev-dependencies")])
+            .flatten()
+            .chain(groups)
+            .filter_map(|deps| deps.as_table());
+        for deps in poetry_deps {
+            for (name, spec) in deps {
+                // Python itself is declared like a dependency.
+                if name.eq_ignore_ascii_case("python") {
+                    continue;
+                }
+
+                let requirement = match spec {
+                    TomlValue::String(version) => Some(version.clone()),
+                    TomlValue::Table(table) if is_registry_table(table) => {
+                        table.get("version").and_then(|version| version.as_str()).map(String::from)
+                    },
+                    _ => None,
+                };
+                dependencies.push(DeclaredDependency::new(name, requirement));
+            }
+        }
+
+        let name = project
+            .or(poetry)
+            .and_then(|package| package.get("name"))
+            .and_then(|name| name.as_str())
+            .map(String::from);
+
+        Ok(Self {
+            name,
+            package_type: PackageType::PyPi,
+            dependencies,
+            external_dependencies: false,
+        })
+    }
+
+    /// Parse Cargo's `Cargo.toml`.
+    fn parse_cargo_toml(data: &str) -> anyhow::Result<Self> {
+        let manifest: TomlTable = toml::from_str(data)?;
+
+        // Collect dependency tables, including platform-specific ones.
+        let targets = manifest
+            .get("target")
+            .and_then(|targets| targets.as_table())
+            .into_iter()
+            .flat_map(|targets| targets.values())
+            .filter_map(|target| target.as_table());
+        let tables = [&manifest].into_iter().chain(targets).flat_map(|table| {
+            ["dependencies", "dev-dependencies", "build-dependencies"]
+                .into_iter()
+                .filter_map(|key| table.get(key)?.as_table())
+        });
+
+        let mut dependencies = Vec::new();
+        for deps in tables {
+            for (name, spec) in deps {
+                let dependency = match spec {
+                    TomlValue::String(version) => {
+                        DeclaredDependency::new(name, Some(version.clone()))
+                    },
+                    TomlValue::Table(table) => {
+                        // Lockfiles record renamed dependencies under their real name.
+                        let name =
+                            table.get("package").and_then(|name| name.as_str()).unwrap_or(name);
+
+                        let requirement = match is_registry_table(table) {
+                            true => table.get("version").and_then(|v| v.as_str()).map(String::from),
+                            false => None,
+                        };
+
+                        DeclaredDependency::new(name, requirement)
+                    },
+                    _ => continue,
+                };
+                dependencies.push(dependency);
+            }
+        }
+
+        let name = manifest
+            .get("package")
+            .and_then(|package| package.get("name"))
+            .and_then(|name| name.as_str())
+            .map(String::from);
+
+        Ok(Self {
+            name,
+            package_type: PackageType::Cargo,
+            dependencies,
+            external_dependencies: false,
+        })
+    }
+
+    /// Parse Bundler's `Gemfile`.
+    ///
+    /// Since Gemfiles are Ruby code, only literal `gem` declarations are
+    /// recognized. Dependencies pulled in through `gemspec` are not resolved.
+    fn parse_gemfile(data: &str) -> Self {
+        let mut dependencies = Vec::new();
+        let mut external_dependencies = false;
+        for line in data.lines() {
+            let line = strip_ruby_comment(line).trim();
+
+            if line == "gemspec" || line.starts_with("gemspec ") || line.starts_with("gemspec(") {
+                external_dependencies = true;
+                continue;
+            }
+
+            let args = match line.strip_prefix("gem") {
+                Some(args) if args.starts_with([' ', '(']) => args.trim_start_matches([' ', '(']),
+                _ => continue,
+            };
+
+            let mut args = args.split(',').map(|arg| arg.trim().trim_end_matches(')'));
+
+            let name = match args.next().and_then(ruby_string) {
+                Some(name) => name,
+                None => continue,
+            };
+
+            let mut requirements = Vec::new();
+            let mut from_registry = true;
+            for arg in args {
+                match ruby_string(arg) {
+                    Some(requirement) => requirements.push(requirement),
+                    None => {
+                        let option = arg.trim_start_matches(':');
+                        from_registry &= !["git", "github", "path"]
+                            .iter()
+                            .any(|source| option.starts_with(source));
+                    },
+                }
+            }
+
+            let requirement =
+                (from_registry && !requirements.is_empty()).then(|| requirements.join(", "));
+            dependencies.push(DeclaredDependency::new(name, requirement));
+        }
+
+        Self {
+            name: None,
+            package_type: PackageType::RubyGems,
+            dependencies,
+            external_dependencies,
+        }
+    }
+}
+
+/// Locked version outside of the range declared in the manifest.
+#[derive(Serialize, Clone, PartialEq, Eq, Debug)]
+pub struct OutOfRange {
+    pub name: String,
+    pub requirement: String,
+    /// All locked versions of the package.
+    pub locked: Vec<String>,
+}
+
+/// Differences between a manifest and its lockfile.
+#[derive(Serialize, Clone, Debug)]
+pub struct LockfileDrift {
+    pub manifest: PathBuf,
+    pub lockfile: PathBuf,
+    pub format: Option<LockfileFormat>,
+    /// Declared dependencies missing from the lockfile.
+    pub missing: Vec<DeclaredDependency>,
+    /// Dependencies whose locked version does not satisfy the manifest.
+    pub out_of_range: Vec<OutOfRange>,
+    /// Direct dependencies in the lockfile which are no longer declared.
+    ///
+    /// This is `None` if the lockfile format does not record its direct
+    /// dependencies.
+    pub no_longer_required: Option<Vec<String>>,
+}
+
+impl LockfileDrift {
+    /// Check if the lockfile is in sync with its manifest.
+    pub fn is_empty(&self) -> bool {
+        self.missing.is_empty()
+            && self.out_of_range.is_empty()
+            && self.no_longer_required.iter().all(Vec::is_empty)
+    }
+}
+
+/// Compare a manifest against the lockfile found by [`find_manifest_lockfile`].
+pub fn check_lockfile_drift(manifest_path: impl AsRef<Path>) -> anyhow::Result<LockfileDrift> {
+    let manifest_path = manifest_path.as_ref();
+
+    let manifest_data = fs::read_to_string(manifest_path)
+        .with_context(|| format!("Could not read manifest {manifest_path:?}"))?;
+    let manifest = Manifest::parse(manifest_path, &manifest_data)
+        .with_context(|| format!("Could not parse manifest {manifest_path:?}"))?;
+
+    let (lockfile_path, format) = find_manifest_lockfile(manifest_path)
+        .ok_or_else(|| anyhow!("Could not find lockfile for manifest {manifest_path:?}"))?;
+
+    let lockfile_data = fs::read_to_string(&lockfile_path)
+        .with_context(|| format!("Could not read lockfile {lockfile_path:?}"))?;
+    let packages = format
+        .parser()
+        .parse(&lockfile_data)
+        .with_context(|| format!("Could not parse lockfile {lockfile_path:?}"))?;
+    let direct = locked_direct_dependencies(format, &lockfile_data, &manifest);
+
+    let mut drift = compare(&manifest, &packages, direct.as_deref());
+    drift.manifest = manifest_path.into();
+    drift.lockfile = lockfile_path;
+    drift.format = Some(format);
+
+    Ok(drift)
+}
+
+/// Compare a manifest's declared dependencies to the locked packages.
+fn compare(manifest: &Manifest, packages: &[Package], direct: Option<&[String]>) -> LockfileDrift {
+    let package_type = manifest.package_type;
+
+    // Group locked packages by their normalized name.
+    let mut locked: BTreeMap<String, Vec<&Package>> = BTreeMap::new();
+    for package in packages.iter().filter(|package| package.package_type == package_type) {
+        locked.entry(normalize_name(package_type, &package.name)).or_default().push(package);
+    }
+
+    let mut missing = Vec::new();
+    let mut out_of_range = Vec::new();
+    let mut declared = HashSet::new();
+    for dependency in &manifest.dependencies {
+        let name = normalize_name(package_type, &dependency.name);
+        if !declared.insert(name.clone()) {
+            continue;
+        }
+
+        let candidates = match locked.get(&name) {
+            Some(candidates) => candidates,
+            None => {
+                missing.push(dependency.clone());
+                continue;
+            },
+        };
+
+        let requirement = match &dependency.requirement {
+            Some(requirement) => requirement,
+            None => continue,
+        };
+        let req = match VersionReq::parse(&package_type, requirement) {
+            Ok(req) => req,
+            Err(err) => {
+                log::debug!("Skipping range check for {:?}: {err}", dependency.name);
+                continue;
+            },
+        };
+
+        // Ignore packages which are not installed from a registry.
+        let versions: Vec<_> = candidates
+            .iter()
+            .filter_map(|package| package.registry_version())
+            .filter_map(Result::ok)
+            .collect();
+        if !versions.is_empty() && !versions.iter().any(|version| req.matches(version)) {
+            out_of_range.push(OutOfRange {
+                name: dependency.name.clone(),
+                requirement: requirement.clone(),
+                locked: versions.iter().map(|version| version.to_string()).collect(),
+            });
+        }
+    }
+
+    let no_longer_required = direct.map(|direct| {
+        let mut unneeded: Vec<_> = direct
+            .iter()
+            .filter(|name| !declared.contains(&normalize_name(package_type, name)))
+            .cloned()
+            .collect();
+        unneeded.sort();
+        unneeded.dedup();
+        unneeded
+    });
+
+    LockfileDrift {
+        manifest: PathBuf::new(),
+        lockfile: PathBuf::new(),
+        format: None,
+        missing,
+        out_of_range,
+        no_longer_required,
+    }
+}
+
+/// Get the direct dependencies recorded in a lockfile.
+///
+/// Returns `None` if the lockfile format does not distinguish direct
+/// dependencies.
+fn locked_direct_dependencies(
+    format: LockfileFormat,
+    data: &str,
+    manifest: &Manifest,
+) -> Option<Vec<String>> {
+    const NPM_KEYS: [&str; 3] = ["dependencies", "devDependencies", "optionalDependencies"];
+
+    // Externally declared dependencies can't be told apart from stale ones.
+    if manifest.external_dependencies {
+        return None;
+    }
+
+    match format {
+        // The root package is only recorded in `package-lock.json` v2 and newer.
+        LockfileFormat::Npm => {
+            let lockfile: JsonValue = serde_json::from_str(data).ok()?;
+            let root = lockfile.get("packages")?.get("")?;
+            let names = NPM_KEYS
+                .iter()
+                .filter_map(|key| root.get(key)?.as_object())
+                .flat_map(|deps| deps.keys().cloned());
+            Some(names.collect())
+        },
+        // Workspaces record the root under `importers`, otherwise it's top-level.
+        LockfileFormat::Pnpm => {
+            let lockfile: YamlValue = serde_yaml::from_str(data).ok()?;
+            let root = lockfile.get("importers").and_then(|importers| importers.get("."));
+            let root = root.unwrap_or(&lockfile);
+            let names = NPM_KEYS
+                .iter()
+                .filter_map(|key| root.get(key)?.as_mapping())
+                .flat_map(|deps| deps.keys().filter_map(|name| name.as_str()))
+                .map(String::from);
+            Some(names.collect())
+        },
+        // Cargo records the dependencies of the local package itself.
+        LockfileFormat::Cargo => {
+            let lockfile: TomlTable = toml::from_str(data).ok()?;
+            let name = manifest.name.as_deref()?;
+            let root = lockfile.get("package")?.as_array()?.iter().find(|package| {
+                package.get("name").and_then(|n| n.as_str()) == Some(name)
+                    && package.get("source").is_none()
+            })?;
+            let names = root
+                .get("dependencies")
+                .and_then(|deps| deps.as_array())
+                .into_iter()
+                .flatten()
+                .filter_map(|dep| dep.as_str()?.split_whitespace().next())
+                .map(String::from);
+            Some(names.collect())
+        },
+        // Bundler lists the Gemfile's dependencies in a `DEPENDENCIES` section.
+        LockfileFormat::Gem => {
+            let section = data.lines().skip_while(|line| *line != "DEPENDENCIES").skip(1);
+            let names = section
+                .take_while(|line| line.starts_with("  "))
+                .filter_map(|line| line.split_whitespace().next())
+                .map(|name| name.trim_end_matches('!').into());
+            Some(names.collect())
+        },
+        _ => None,
+    }
+}
+
+/// Get the registry range of an npm dependency specifier.
+fn npm_registry_range(specifier: &str) -> Option<String> {
+    let is_registry = !specifier.contains(':') && !specifier.contains('/');
+    is_registry.then(|| specifier.into())
+}
+
+/// Check if a dependency table describes a registry dependency.
+fn is_registry_table(table: &TomlTable) -> bool {
+    ["git", "path", "url", "workspace"].iter().all(|key| !table.contains_key(*key))
+}
+
+/// Parse a PEP 508 dependency specification.
+///
+/// Returns `None` for invalid specifications.
+fn parse_pep508(spec: &str) -> Option<DeclaredDependency> {
+    // Environment markers do not affect the version.
+    let spec = spec.split_once(';').map_or(spec, |(spec, _)| spec).trim();
+
+    let name_len = spec
+        .find(|c: char| !c.is_ascii_alphanumeric() && !matches!(c, '-' | '_' | '.'))
+        .unwrap_or(spec.len());
+    let (name, mut rest) = spec.split_at(name_len);
+    if name.is_empty() {
+        return None;
+    }
+
+    // Skip extras.
+    rest = rest.trim_start();
+    if let Some(extras) = rest.strip_prefix('[') {
+        rest = extras.split_once(']')?.1.trim_start();
+    }
+
+    // Direct URL references are not resolved from the registry.
+    if rest.starts_with('@') {
+        return Some(DeclaredDependency::new(name, None));
+    }
+
+    let rest = rest.strip_prefix('(').and_then(|rest| rest.strip_suffix(')')).unwrap_or(rest);
+    let requirement = Some(rest.trim()).filter(|rest| !rest.is_empty()).map(String::from);
+
+    Some(DeclaredDependency::new(name, requirement))
+}
+
+/// Remove a trailing `# comment` outside of string literals from a line of
+/// Ruby code.
+fn strip_ruby_comment(line: &str) -> &str {
+    let mut quote = None;
+    for (i, c) in line.char_indices() {
+        match quote {
+            None if c == '#' => return &line[..i],
+            None if c == '"' || c == '\'' => quote = Some(c),
+            Some(open) if c == open => quote = None,
+            _ => (),
+        }
+    }
+    line
+}
+
+/// Parse a quoted Ruby string literal.
+fn ruby_string(literal: &str) -> Option<String> {
+    let literal = literal.trim();
+    let unquoted = literal
+        .strip_prefix('"')
+        .and_then(|literal| literal.strip_suffix('"'))
+        .or_else(|| literal.strip_prefix('\'').and_then(|literal| literal.strip_suffix('\'')))?;
+    Some(unquoted.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{PackageVersion, ThirdPartyVersion};
+
+    fn package(package_type: PackageType, name: &str, version: PackageVersion) -> Package {
+        Package { name: name.into(), version, package_type }
+    }
+
+    fn dependency(name: &str, requirement: Option<&str>) -> DeclaredDependency {
+        DeclaredDependency::new(name, requirement.map(String::from))
+    }
+
+    #[test]
+    fn package_json() {
+        let manifest = Manifest::parse(
+            Path::new("package.json"),
+            r#"{
+                "name": "demo",
+                "dependencies": { "left-pad": "^1.3.0", "local": "file:../local" },
+                "devDependencies": { "aliased": "npm:typescript@^5.0.0" },
+                "peerDependencies": { "react": "^18" }
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(manifest.name.as_deref(), Some("demo"));
+        assert_eq!(manifest.dependencies, [
+            dependency("left-pad", Some("^1.3.0")),
+            dependency("local", None),
+            dependency("typescript", Some("^5.0.0")),
+        ]);
+    }
+
+    #[test]
+    fn pyproject() {
+        let manifest = Manifest::parse(
+            Path::new("pyproject.toml"),
+            r#"
+                [project]
+                name = "demo"
+                dependencies = [
+                    "Requests[socks] >=2.8.1, ==2.8.* ; python_version < '3.8'",
+                    "pip @ https://github.com/pypa/pip/archive/1.3.1.zip",
+                ]
+
+                [project.optional-dependencies]
+                test = ["pytest (>=7)"]
+
+                [tool.poetry.dependencies]
+                python = "^3.8"
+                numpy = { version = "^1.24", optional = true }
+                internal = { git = "https://example.com/internal.git" }
+
+                [tool.poetry.group.dev.dependencies]
+                black = "*"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(manifest.name.as_deref(), Some("demo"));
+        assert_eq!(manifest.dependencies, [
+            dependency("Requests", Some(">=2.8.1, ==2.8.*")),
+            dependency("pip", None),
+            dependency("pytest", Some(">=7")),
+            dependency("internal", None),
+            dependency("numpy", Some("^1.24")),
+            dependency("black", Some("*")),
+        ]);
+    }
+
+    #[test]
+    fn cargo_toml() {
+        let manifest = Manifest::parse(
+            Path::new("Cargo.toml"),
+            r#"
+                [package]
+                name = "demo"
+
+                [dependencies]
+                serde = "1.0"
+                json = { package = "serde_json", version = "1.0.85" }
+                local = { path = "../local", version = "0.1" }
+
+                [target.'cfg(unix)'.dev-dependencies]
+                libc = { version = "0.2" }
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(manifest.name.as_deref(), Some("demo"));
+        assert_eq!(manifest.dependencies, [
+            dependency("serde_json", Some("1.0.85")),
+            dependency("local", None),
+            dependency("serde", Some("1.0")),
+            dependency("libc", Some("0.2")),
+        ]);
+    }
+
+    #[test]
+    fn gemfile() {
+        let manifest = Manifest::parse(
+            Path::new("Gemfile"),
+            r#"
+                source "https://rubygems.org"
+
+                gem "rails", "~> 7.0", ">= 7.0.1"
+                gem 'pg', group: :production # Database
+                gem "engine", path: "engines/engine"
+                gem("puma", "~> 6.0")
+                gem "nokogiri" # , "~> 1.15"
+                gem "sass", "~> 3.0" # "~> 4.0" breaks the build
+                gem "rack", "~> 2.2", require: "rack#lite"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(manifest.dependencies, [
+            dependency("rails", Some("~> 7.0, >= 7.0.1")),
+            dependency("pg", None),
+            dependency("engine", None),
+            dependency("puma", Some("~> 6.0")),
+            dependency("nokogiri", None),
+            dependency("sass", Some("~> 3.0")),
+            dependency("rack", Some("~> 2.2")),
+        ]);
+        assert!(!manifest.external_dependencies);
+    }
+
+    #[test]
+    fn gemfile_gemspec() {
+        let gemfile =
+            "source \"https://rubygems.org\"\n\ngemspec # Runtime dependencies\ngem \"rake\"\n";
+        let manifest = Manifest::parse(Path::new("Gemfile"), gemfile).unwrap();
+
+        assert_eq!(manifest.dependencies, [dependency("rake", None)]);
+        assert!(manifest.external_dependencies);
+
+        // The gemspec's dependencies are locked as direct dependencies.
+        let gem_lock = "PATH\n  remote: .\n  specs:\n    demo (0.1.0)\n      rack (~> \
+                        2.2)\n\nGEM\n  specs:\n    rack (2.2.8)\n    rake (13.0.6)\n    rspec \
+                        (3.12.0)\n\nDEPENDENCIES\n  demo!\n  rake\n  rspec (~> 3.0)\n";
+        assert_eq!(locked_direct_dependencies(LockfileFormat::Gem, gem_lock, &manifest), None);
+
+        let packages = [
+            package(PackageType::RubyGems, "rack", PackageVersion::FirstParty("2.2.8".into())),
+            package(PackageType::RubyGems, "rake", PackageVersion::FirstParty("13.0.6".into())),
+        ];
+        assert!(compare(&manifest, &packages, None).is_empty());
+    }
+
+    #[test]
+    fn drift() {
+        let manifest = Manifest {
+            name: Some("demo".into()),
+            package_type: PackageType::PyPi,
+            external_dependencies: false,
+            dependencies: vec![
+                dependency("Requests", Some(">=2.28")),
+                dependency("flask", Some("~=2.0")),
+                dependency("numpy", None),
+                dependency("local", Some(">=1.0")),
+            ],
+        };
+        let packages = [
+            package(PackageType::PyPi, "requests", PackageVersion::FirstParty("2.31.0".into())),
+            package(
+                PackageType::PyPi,
+                "Flask",
+                PackageVersion::ThirdParty(ThirdPartyVersion {
+                    version: "3.0.0".into(),
+                    registry: "https://pypi.example.com".into(),
+                }),
+            ),
+            package(PackageType::PyPi, "local", PackageVersion::Path(None)),
+            package(PackageType::PyPi, "django", PackageVersion::FirstParty("4.2".into())),
+        ];
+        let direct = ["requests".into(), "flask".into(), "django".into()];
+
+        let drift = compare(&manifest, &packages, Some(&direct));
+
+        assert_eq!(drift.missing, [dependency("numpy", None)]);
+        assert_eq!(drift.out_of_range, [OutOfRange {
+            name: "flask".into(),
+            requirement: "~=2.0".into(),
+            locked: vec!["3.0.0".into()],
+        }]);
+        assert_eq!(drift.no_longer_required, Some(vec!["django".into()]));
+        assert!(!drift.is_empty());
+
+        let drift = compare(&manifest, &packages[..3], None);
+        assert_eq!(drift.no_longer_required, None);
+    }
+
+    #[test]
+    fn locked_direct() {
+        let manifest = Manifest {
+            name: Some("demo".into()),
+            package_type: PackageType::Cargo,
+            dependencies: vec![],
+            external_dependencies: false,
+        };
+
+        let package_lock = r#"{
+            "lockfileVersion": 3,
+            "packages": {
+                "": { "dependencies": { "a": "^1" }, "devDependencies": { "b": "^2" } },
+                "node_modules/a": { "version": "1.0.0" }
+            }
+        }"#;
+        let direct = locked_direct_dependencies(LockfileFormat::Npm, package_lock, &manifest);
+        assert_eq!(direct, Some(vec!["a".into(), "b".into()]));
+
+        let pnpm =
+            "lockfileVersion: '6.0'\nimporters:\n  .:\n    dependencies:\n      a:\n        \
+             specifier: ^1\n        version: 1.0.0\n";
+        let direct = locked_direct_dependencies(LockfileFormat::Pnpm, pnpm, &manifest);
+        assert_eq!(direct, Some(vec!["a".into()]));
+
+        let cargo_lock = r#"
+            [[package]]
+            name = "demo"
+            version = "0.1.0"
+            dependencies = ["serde", "syn 2.0.0 (registry+https://github.com/rust-lang/crates.io-index)"]
+
+            [[package]]
+            name = "serde"
+            version = "1.0.0"
+            source = "registry+https://github.com/rust-lang/crates.io-index"
+        "#;
+        let direct = locked_direct_dependencies(LockfileFormat::Cargo, cargo_lock, &manifest);
+        assert_eq!(direct, Some(vec!["serde".into(), "syn".into()]));
+
+        let gem_lock = "GEM\n  specs:\n    rails (7.0.0)\n\nDEPENDENCIES\n  engine!\n  rails (~> \
+                        7.0)\n\nBUNDLED WITH\n   2.4.0\n";
+        let direct = locked_direct_dependencies(LockfileFormat::Gem, gem_lock, &manifest);
+        assert_eq!(direct, Some(vec!["engine".into(), "rails".into()]));
+
+        assert_eq!(locked_direct_dependencies(LockfileFormat::Poetry, "", &manifest), None);
+    }
+}
@@ -0,0 +1,657 @@
+//! Verification of installed packages against lockfiles.
+//!
+//! Package managers install dependencies into well-known directory trees,
+//! which can diverge from the lockfile when packages are installed manually.
+
+use std::collections::{BTreeMap, HashSet};
+use std::ffi::OsStr;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use phylum_types::types::package::{PackageDescriptor, PackageType};
+use serde::Serialize;
+use serde_json::Value as JsonValue;
+use serde_yaml::Value as YamlValue;
+use walkdir::WalkDir;
+
+use crate::version::Version;
+use crate::{normalize_name, LockfileFormat};
+
+/// Maximum directory depth to recurse for finding Python `site-packages`.
+const MAX_SITE_PACKAGES_DEPTH: usize = 6;
+
+/// Packages installed into every Python environment.
+///
+/// These are usually not part of the lockfile and are ignored.
+const PYTHON_BOOTSTRAP_PACKAGES: [&str; 3] = ["pip", "setuptools", "wheel"];
+
+/// Package found in an installed dependency tree.
+#[derive(Serialize, Clone, PartialEq, Eq, Debug)]
+pub struct InstalledPackage {
+    pub name: String,
+    pub version: String,
+    pub package_type: PackageType,
+    /// Location of the package's metadata.
+    pub path: PathBuf,
+}
+
+/// Directory tree containing installed packages.
+#[derive(Serialize, Clone, Debug)]
+pub struct InstalledTree {
+    pub path: PathBuf,
+    pub package_type: PackageType,
+    pub packages: Vec<InstalledPackage>,
+}
+
+/// Find installed dependency trees at or below the specified root directory.
+///
+/// This looks for `node_modules` (including pnpm's `node_modules/.pnpm`
+/// store), Python `site-packages` and Go's `vendor/modules.txt`.
+pub fn find_installed_trees(root: impl AsRef<Path>) -> Vec<InstalledTree> {
+    let root = root.as_ref();
+    let mut trees = Vec::new();
+
+    let node_modules = root.join("node_modules");
+    if let Some(canonical) = node_modules.canonicalize().ok().filter(|path| path.is_dir()) {
+        let mut packages = Vec::new();
+        find_node_modules(&canonical, &node_modules, &mut HashSet::new(), &mut packages);
+        trees.push(InstalledTree { path: node_modules, package_type: PackageType::Npm, packages });
+    }
+
+    let site_packages = WalkDir::new(root)
+        .max_depth(MAX_SITE_PACKAGES_DEPTH)
+        .into_iter()
+        .filter_entry(|entry| {
+            entry.file_name() != OsStr::new("node_modules")
+                && entry.file_name() != OsStr::new(".git")
+        })
+        .flatten()
+        .filter(|entry| {
+            entry.file_type().is_dir()
+                && (entry.file_name() == OsStr::new("site-packages")
+                    || entry.file_name() == OsStr::new("dist-packages"))
+        });
+    for entry in site_packages {
+        let packages = find_site_packages(entry.path());
+        trees.push(InstalledTree {
+            path: entry.into_path(),
+            package_type: PackageType::PyPi,
+            packages,
+        });
+    }
+
+    let modules_txt = root.join("vendor").join("modules.txt");
+    if let Ok(data) = fs::read_to_string(&modules_txt) {
+        let packages = parse_modules_txt(&data, &modules_txt);
+        trees.push(InstalledTree {
+            path: modules_txt,
+            package_type: PackageType::Golang,
+            packages,
+        });
+    }
+
+    trees
+}
+
+/// Recursively collect packages from a `node_modules` directory.
+///
+/// Symlinks are only followed if they point into the canonical top-level
+/// `node_modules` directory `root`, like pnpm's links into its `.pnpm`
+/// store. This excludes linked workspace packages. Directories are only
+/// visited once, which protects against symlink cycles.
+fn find_node_modules(
+    root: &Path,
+    dir: &Path,
+    visited: &mut HashSet<PathBuf>,
+    packages: &mut Vec<InstalledPackage>,
+) {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(err) => {
+            log::debug!("Could not read {dir:?}: {err}");
+            return;
+        },
+    };
+
+    for entry in entries.flatten() {
+        let file_name = entry.file_name();
+        let file_name = file_name.to_string_lossy();
+        let path = entry.path();
+
+        // pnpm stores packages in `.pnpm/<name>@<version>/node_modules`.
+        if file_name == ".pnpm" {
+            let stores = fs::read_dir(&path).into_iter().flatten().flatten();
+            for store in stores.filter(|store| store.file_name() != "node_modules") {
+                find_node_modules(root, &store.path().join("node_modules"), visited, packages);
+            }
+            continue;
+        }
+
+        // Ignore metadata like `.bin` and `.package-lock.json`.
+        if file_name.starts_with('.') {
+            continue;
+        }
+
+        let canonical = match path.canonicalize() {
+            Ok(canonical) if canonical.is_dir() && canonical.starts_with(root) => canonical,
+            _ => continue,
+        };
+        if !visited.insert(canonical) {
+            continue;
+        }
+
+        // Scoped packages are nested one level deeper.
+        if file_name.starts_with('@') {
+            find_node_modules(root, &path, visited, packages);
+            continue;
+        }
+
+        let manifest_path = path.join("package.json");
+        let manifest = fs::read_to_string(&manifest_path)
+            .ok()
+            .and_then(|data| serde_json::from_str::<JsonValue>(&data).ok());
+        let field = |key| manifest.as_ref()?.get(key)?.as_str().map(String::from);
+        if let (Some(name), Some(version)) = (field("name"), field("version")) {
+            packages.push(InstalledPackage {
+                name,
+                version,
+                package_type: PackageType::Npm,
+                path: manifest_path,
+            });
+        }
+
+        find_node_modules(root, &path.join("node_modules"), visited, packages);
+    }
+}
+
+/// Collect packages from a Python `site-packages` directory.
+fn find_site_packages(dir: &Path) -> Vec<InstalledPackage> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(err) => {
+            log::debug!("Could not read {dir:?}: {err}");
+            return Vec::new();
+        },
+    };
+
+    entries
+        .flatten()
+        .filter(|entry| entry.path().extension() == Some(OsStr::new("dist-info")))
+        .filter_map(|entry| {
+            let metadata_path = entry.path().join("METADATA");
+            let metadata = fs::read_to_string(&metadata_path).ok()?;
+            let (name, version) = parse_metadata(&metadata)?;
+            Some(InstalledPackage {
+                name,
+                version,
+                package_type: PackageType::PyPi,
+                path: metadata_path,
+            })
+        })
+        .collect()
+}
+
+/// Get name and version from Python core metadata.
+fn parse_metadata(metadata: &str) -> Option<(String, String)> {
+    let mut name = None;
+    let mut version = None;
+
+    // Headers end at the first empty line.
+    for line in metadata.lines().take_while(|line| !line.is_empty()) {
+        if let Some(value) = line.strip_prefix("Name:") {
+            name = Some(value.trim().to_string());
+        } else if let Some(value) = line.strip_prefix("Version:") {
+            version = Some(value.trim().to_string());
+        }
+    }
+
+    Some((name?, version?))
+}
+
+/// Get vendored modules from Go's `vendor/modules.txt`.
+fn parse_modules_txt(data: &str, path: &Path) -> Vec<InstalledPackage> {
+    data.lines()
+        .filter_map(|line| line.strip_prefix("# "))
+        .filter_map(|module| {
+            // Replaced modules are vendored from their replacement.
+            let module = module.split_once("=>").map_or(module, |(_, replacement)| replacement);
+
+            // Local replacements have no version.
+            let mut parts = module.split_whitespace();
+            let (name, version) = (parts.next()?, parts.next()?);
+
+            Some(InstalledPackage {
+                name: name.into(),
+                version: version.into(),
+                package_type: PackageType::Golang,
+                path: path.into(),
+            })
+        })
+        .collect()
+}
+
+/// Package with different versions installed than locked.
+#[derive(Serialize, Clone, PartialEq, Eq, Debug)]
+pub struct VersionMismatch {
+    pub name: String,
+    pub package_type: PackageType,
+    pub locked: Vec<String>,
+    pub installed: Vec<String>,
+}
+
+/// Differences between locked and installed packages.
+#[derive(Serialize, Clone, Default, Debug)]
+pub struct InstalledDiff {
+    /// Installed packages which are not in the lockfile.
+    pub extra: Vec<InstalledPackage>,
+    /// Locked packages which are not installed.
+    pub missing: Vec<PackageDescriptor>,
+    /// Packages installed with a version which is not locked.
+    pub mismatched: Vec<VersionMismatch>,
+}
+
+impl InstalledDiff {
+    /// Check if the installed packages match the lockfile.
+    pub fn is_empty(&self) -> bool {
+        self.extra.is_empty() && self.missing.is_empty() && self.mismatched.is_empty()
+    }
+}
+
+/// Get locked packages which package managers skip when they can't be
+/// installed.
+///
+/// These are optional dependencies and packages restricted to specific
+/// operating systems or CPU architectures, which are only supported for npm
+/// and pnpm lockfiles.
+pub fn optional_packages(format: LockfileFormat, data: &str) -> Vec<PackageDescriptor> {
+    let packages = match format {
+        LockfileFormat::Npm => optional_npm_packages(data),
+        LockfileFormat::Pnpm => optional_pnpm_packages(data),
+        _ => return Vec::new(),
+    };
+
+    packages
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(name, version)| PackageDescriptor { name, version, package_type: PackageType::Npm })
+        .collect()
+}
+
+/// Get optional packages from a `package-lock.json`.
+fn optional_npm_packages(data: &str) -> Option<Vec<(String, String)>> {
+    let lockfile: JsonValue = serde_json::from_str(data).ok()?;
+
+    let is_optional = |package: &JsonValue| {
+        package.get("optional").and_then(JsonValue::as_bool).unwrap_or(false)
+            || package.get("os").is_some()
+            || package.get("cpu").is_some()
+    };
+    let version = |package: &JsonValue| Some(package.get("version")?.as_str()?.to_owned());
+
+    // Lockfiles before v2 only have nested `dependencies`.
+    if let Some(packages) = lockfile.get("packages").and_then(JsonValue::as_object) {
+        let optional = packages.iter().filter(|(_, package)| is_optional(package));
+        let optional = optional.filter_map(|(path, package)| {
+            let (_, name) = path.rsplit_once("node_modules/")?;
+            let name = package.get("name").and_then(JsonValue::as_str).unwrap_or(name);
+            Some((name.to_owned(), version(package)?))
+        });
+        return Some(optional.collect());
+    }
+
+    let mut optional = Vec::new();
+    let mut pending = vec![lockfile.get("dependencies")?];
+    while let Some(dependencies) = pending.pop() {
+        for (name, package) in dependencies.as_object().into_iter().flatten() {
+            if let Some(version) = version(package).filter(|_| is_optional(package)) {
+                optional.push((name.clone(), version));
+            }
+            pending.extend(package.get("dependencies"));
+        }
+    }
+    Some(optional)
+}
+
+/// Get optional packages from a `pnpm-lock.yaml`.
+fn optional_pnpm_packages(data: &str) -> Option<Vec<(String, String)>> {
+    let lockfile: YamlValue = serde_yaml::from_str(data).ok()?;
+
+    // Lockfiles before v6 separate names and versions with `/` instead of `@`.
+    let lockfile_version = match lockfile.get("lockfileVersion")? {
+        YamlValue::String(version) => version.clone(),
+        version => serde_yaml::to_string(version).ok()?,
+    };
+    let separator = if lockfile_version.trim().starts_with('5') { '/' } else { '@' };
+
+    let is_optional = |package: &YamlValue| {
+        package.get("optional").and_then(YamlValue::as_bool).unwrap_or(false)
+            || package.get("os").is_some()
+            || package.get("cpu").is_some()
+    };
+
+    // Since v9, optional flags are recorded in `snapshots`.
+    let packages = ["packages", "snapshots"]
+        .iter()
+        .filter_map(|key| lockfile.get(key)?.as_mapping())
+        .flatten()
+        .filter(|(_, package)| is_optional(package));
+
+    let mut optional = Vec::new();
+    for (key, package) in packages {
+        let key = key.as_str().unwrap_or_default();
+        let key = key.strip_prefix('/').unwrap_or(key);
+        let key = key.split_once('(').map_or(key, |(key, _)| key);
+
+        let (name, version) = match key.rsplit_once(separator) {
+            Some((name, version)) if !name.is_empty() => (name, version),
+            _ => continue,
+        };
+        // Peer dependencies are appended with `_` before v6.
+        let version = version.split_once('_').map_or(version, |(version, _)| version);
+        let name = package.get("name").and_then(YamlValue::as_str).unwrap_or(name);
+
+        optional.push((name.to_owned(), version.to_owned()));
+    }
+    Some(optional)
+}
+
+/// Compare locked packages against installed dependency trees.
+///
+/// Only ecosystems with an installed tree are compared. Go modules are never
+/// reported as missing, since `go.sum` also contains modules which are not
+/// required for building. Packages in `optional` are not reported as missing
+/// either, since they might not be supported on the current platform.
+pub fn verify_installed(
+    locked: &[PackageDescriptor],
+    optional: &[PackageDescriptor],
+    trees: &[InstalledTree],
+) -> InstalledDiff {
+    type Key = (PackageType, String);
+
+    let mut installed: BTreeMap<Key, Vec<&InstalledPackage>> = BTreeMap::new();
+    for package in trees.iter().flat_map(|tree| &tree.packages) {
+        let key = (package.package_type, normalize_name(package.package_type, &package.name));
+        installed.entry(key).or_default().push(package);
+    }
+
+    let mut locked_versions: BTreeMap<Key, Vec<&PackageDescriptor>> = BTreeMap::new();
+    for package in locked {
+        if trees.iter().all(|tree| tree.package_type != package.package_type) {
+            continue;
+        }
+
+        let key = (package.package_type, normalize_name(package.package_type, &package.name));
+        locked_versions.entry(key).or_default().push(package);
+    }
+
+    let mut diff = InstalledDiff::default();
+
+    for (key, packages) in &installed {
+        let locked = match locked_versions.get(key) {
+            Some(locked) => locked,
+            None => {
+                let is_bootstrap = key.0 == PackageType::PyPi
+                    && PYTHON_BOOTSTRAP_PACKAGES.contains(&key.1.as_str());
+                if !is_bootstrap {
+                    diff.extra.extend(packages.iter().map(|&package| package.clone()));
+                }
+                continue;
+            },
+        };
+
+        let unlocked = packages.iter().any(|package| {
+            !locked.iter().any(|locked| {
+                versions_equal(package.package_type, &package.version, &locked.version)
+            })
+        });
+        if unlocked {
+            diff.mismatched.push(VersionMismatch {
+                name: packages[0].name.clone(),
+                package_type: key.0,
+                locked: locked.iter().map(|package| package.version.clone()).collect(),
+                installed: packages.iter().map(|package| package.version.clone()).collect(),
+            });
+        }
+    }
+
+    for (key, packages) in locked_versions {
+        if key.0 != PackageType::Golang && !installed.contains_key(&key) {
+            let required = packages.into_iter().filter(|package| !optional.contains(package));
+            diff.missing.extend(required.cloned());
+        }
+    }
+
+    diff
+}
+
+/// Check if two versions are equal according to their ecosystem's rules.
+fn versions_equal(package_type: PackageType, lhs: &str, rhs: &str) -> bool {
+    match (Version::parse(&package_type, lhs), Version::parse(&package_type, rhs)) {
+        (Ok(lhs), Ok(rhs)) => lhs == rhs,
+        _ => lhs == rhs,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::TempDir;
+
+    use super::*;
+
+    fn write(path: impl AsRef<Path>, contents: &str) {
+        let path = path.as_ref();
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, contents).unwrap();
+    }
+
+    fn descriptor(package_type: PackageType, name: &str, version: &str) -> PackageDescriptor {
+        PackageDescriptor { name: name.into(), version: version.into(), package_type }
+    }
+
+    #[test]
+    fn find_trees() {
+        let tempdir = TempDir::new().unwrap();
+        let root = tempdir.path();
+
+        write(root.join("node_modules/a/package.json"), r#"{"name": "a", "version": "1.0.0"}"#);
+        write(
+            root.join("node_modules/@scope/b/package.json"),
+            r#"{"name": "@scope/b", "version": "2.0.0"}"#,
+        );
+        write(
+            root.join("node_modules/a/node_modules/c/package.json"),
+            r#"{"name": "c", "version": "3.0.0"}"#,
+        );
+        write(root.join("node_modules/.package-lock.json"), "{}");
+
+        write(
+            root.join(".venv/lib/python3.11/site-packages/Django-4.2.dist-info/METADATA"),
+            "Metadata-Version: 2.1\nName: Django\nVersion: 4.2\n\nName: Ignored\n",
+        );
+
+        write(
+            root.join("vendor/modules.txt"),
+            "# golang.org/x/net v0.17.0\n## explicit; go 1.17\ngolang.org/x/net/html\n# \
+             example.com/a v1.0.0 => example.com/b v1.1.0\n# example.com/c => ./c\n",
+        );
+
+        let trees = find_installed_trees(root);
+        let mut installed: Vec<_> = trees
+            .iter()
+            .flat_map(|tree| &tree.packages)
+            .map(|package| (package.name.as_str(), package.version.as_str()))
+            .collect();
+        installed.sort_unstable();
+
+        assert_eq!(installed, [
+            ("@scope/b", "2.0.0"),
+            ("Django", "4.2"),
+            ("a", "1.0.0"),
+            ("c", "3.0.0"),
+            ("example.com/b", "v1.1.0"),
+            ("golang.org/x/net", "v0.17.0"),
+        ]);
+    }
+
+    #[test]
+    fn diff() {
+        let installed = |package_type, name: &str, version: &str| InstalledPackage {
+            name: name.into(),
+            version: version.into(),
+            package_type,
+            path: PathBuf::new(),
+        };
+        let trees = [
+            InstalledTree {
+                path: PathBuf::from("node_modules"),
+                package_type: PackageType::Npm,
+                packages: vec![
+                    installed(PackageType::Npm, "a", "1.0.0"),
+                    installed(PackageType::Npm, "b", "2.1.0"),
+                    installed(PackageType::Npm, "adhoc", "0.1.0"),
+                ],
+            },
+            InstalledTree {
+                path: PathBuf::from("site-packages"),
+                package_type: PackageType::PyPi,
+                packages: vec![
+                    installed(PackageType::PyPi, "Django", "4.2.0"),
+                    installed(PackageType::PyPi, "pip", "23.0"),
+                ],
+            },
+        ];
+        let locked = [
+            descriptor(PackageType::Npm, "a", "1.0.0"),
+            descriptor(PackageType::Npm, "b", "2.0.0"),
+            descriptor(PackageType::Npm, "c", "3.0.0"),
+            descriptor(PackageType::PyPi, "django", "4.2"),
+            descriptor(PackageType::Cargo, "serde", "1.0.0"),
+        ];
+
+        let diff = verify_installed(&locked, &[], &trees);
+
+        assert_eq!(diff.extra, [installed(PackageType::Npm, "adhoc", "0.1.0")]);
+        assert_eq!(diff.missing, [descriptor(PackageType::Npm, "c", "3.0.0")]);
+        assert_eq!(diff.mismatched, [VersionMismatch {
+            name: "b".into(),
+            package_type: PackageType::Npm,
+            locked: vec!["2.0.0".into()],
+            installed: vec!["2.1.0".into()],
+        }]);
+        assert!(!diff.is_empty());
+
+        let optional = [descriptor(PackageType::Npm, "c", "3.0.0")];
+        let diff = verify_installed(&locked, &optional, &trees);
+        assert!(diff.missing.is_empty());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn find_pnpm_tree() {
+        use std::os::unix::fs::symlink;
+
+        let tempdir = TempDir::new().unwrap();
+        let root = tempdir.path();
+        let store = root.join("node_modules/.pnpm");
+
+        write(
+            store.join("a@1.0.0/node_modules/a/package.json"),
+            r#"{"name": "a", "version": "1.0.0"}"#,
+        );
+        write(
+            store.join("@scope+b@2.0.0/node_modules/@scope/b/package.json"),
+            r#"{"name": "@scope/b", "version": "2.0.0"}"#,
+        );
+        write(root.join("packages/local/package.json"), r#"{"name": "local", "version": "0.1.0"}"#);
+
+        // Dependencies are linked next to their dependents, with `a` and `@scope/b`
+        // depending on each other.
+        fs::create_dir_all(store.join("a@1.0.0/node_modules/@scope")).unwrap();
+        symlink(
+            "../../../@scope+b@2.0.0/node_modules/@scope/b",
+            store.join("a@1.0.0/node_modules/@scope/b"),
+        )
+        .unwrap();
+        symlink("../../../a@1.0.0/node_modules/a", store.join("@scope+b@2.0.0/node_modules/a"))
+            .unwrap();
+        fs::create_dir_all(store.join("node_modules")).unwrap();
+        symlink("../a@1.0.0/node_modules/a", store.join("node_modules/a")).unwrap();
+
+        // Direct dependencies and workspace packages are linked into the top level.
+        symlink(".pnpm/a@1.0.0/node_modules/a", root.join("node_modules/a")).unwrap();
+        symlink("../packages/local", root.join("node_modules/local")).unwrap();
+
+        // Nested `node_modules` linking back to its parent.
+        symlink("..", store.join("a@1.0.0/node_modules/a/node_modules")).unwrap();
+
+        let trees = find_installed_trees(root);
+        let mut installed: Vec<_> = trees
+            .iter()
+            .flat_map(|tree| &tree.packages)
+            .map(|package| (package.name.as_str(), package.version.as_str()))
+            .collect();
+        installed.sort_unstable();
+
+        assert_eq!(installed, [("@scope/b", "2.0.0"), ("a", "1.0.0")]);
+    }
+
+    #[test]
+    fn optional() {
+        let package_lock = r#"{
+            "lockfileVersion": 3,
+            "packages": {
+                "": { "optionalDependencies": { "fsevents": "^2.3.0" } },
+                "node_modules/fsevents": { "version": "2.3.3", "optional": true, "os": ["darwin"] },
+                "node_modules/a/node_modules/@esbuild/linux-arm64": { "version": "0.19.0", "cpu": ["arm64"] },
+                "node_modules/a": { "version": "1.0.0" }
+            }
+        }"#;
+        let mut optional = optional_packages(LockfileFormat::Npm, package_lock);
+        optional.sort_by(|a, b| a.name.cmp(&b.name));
+        assert_eq!(optional, [
+            descriptor(PackageType::Npm, "@esbuild/linux-arm64", "0.19.0"),
+            descriptor(PackageType::Npm, "fsevents", "2.3.3"),
+        ]);
+
+        let package_lock_v1 = r#"{
+            "lockfileVersion": 1,
+            "dependencies": {
+                "a": { "version": "1.0.0", "dependencies": { "b": { "version": "2.0.0", "optional": true } } }
+            }
+        }"#;
+        assert_eq!(optional_packages(LockfileFormat::Npm, package_lock_v1), [descriptor(
+            PackageType::Npm,
+            "b",
+            "2.0.0"
+        )]);
+
+        let pnpm_v6 =
+            "lockfileVersion: '6.0'\npackages:\n  /fsevents@2.3.3:\n    os: [darwin]\n    \
+             optional: true\n  /@scope/b@2.0.0(a@1.0.0):\n    cpu: [arm64]\n  /a@1.0.0:\n    dev: \
+             false\n";
+        optional = optional_packages(LockfileFormat::Pnpm, pnpm_v6);
+        optional.sort_by(|a, b| a.name.cmp(&b.name));
+        assert_eq!(optional, [
+            descriptor(PackageType::Npm, "@scope/b", "2.0.0"),
+            descriptor(PackageType::Npm, "fsevents", "2.3.3"),
+        ]);
+
+        let pnpm_v5 =
+            "lockfileVersion: 5.4\npackages:\n  /fsevents/2.3.3_a@1.0.0:\n    optional: true\n";
+        assert_eq!(optional_packages(LockfileFormat::Pnpm, pnpm_v5), [descriptor(
+            PackageType::Npm,
+            "fsevents",
+            "2.3.3"
+        )]);
+
+        let pnpm_v9 = "lockfileVersion: '9.0'\npackages:\n  fsevents@2.3.3:\n    resolution: \
+                       {}\nsnapshots:\n  fsevents@2.3.3:\n    optional: true\n";
+        assert_eq!(optional_packages(LockfileFormat::Pnpm, pnpm_v9), [descriptor(
+            PackageType::Npm,
+            "fsevents",
+            "2.3.3"
+        )]);
+
+        assert!(optional_packages(LockfileFormat::Cargo, "").is_empty());
+    }
+}
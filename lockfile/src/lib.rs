@@ -22,22 +22,28 @@ pub use crate::cyclonedx::CycloneDX;
 pub use crate::golang::{GoMod, GoSum};
 pub use crate::java::{GradleLock, Pom};
 pub use crate::javascript::{PackageLock, Pnpm, YarnLock};
-pub use crate::parse_depfile::{parse_depfile, ParseError, ParsedLockfile};
+pub use crate::parse_depfile::{parse_depfile, PackageSource, ParseError, ParsedLockfile};
 pub use crate::python::{PipFile, Poetry, PyRequirements};
 pub use crate::ruby::GemLock;
 pub use crate::spdx::Spdx;
+use crate::version::{Version, VersionError};
 
 mod cargo;
+pub mod confusion;
 mod csharp;
 mod cyclonedx;
+pub mod drift;
 mod golang;
+pub mod installed;
 mod java;
 mod javascript;
+pub mod lint;
 mod parse_depfile;
 mod parsers;
 mod python;
 mod ruby;
 mod spdx;
+pub mod version;
 
 /// Maximum directory depth to recurse for finding lockfiles.
 const MAX_LOCKFILE_DEPTH: usize = 5;
@@ -204,6 +210,20 @@ pub struct Package {
     pub package_type: PackageType,
 }
 
+impl Package {
+    /// Parse the package's registry version using its ecosystem's rules.
+    ///
+    /// Returns `None` if the package was not resolved from a registry.
+    pub fn registry_version(&self) -> Option<Result<Version, VersionError>> {
+        let version = match &self.version {
+            PackageVersion::FirstParty(version) => version,
+            PackageVersion::ThirdParty(ThirdPartyVersion { version, .. }) => version,
+            _ => return None,
+        };
+        Some(Version::parse(&self.package_type, version))
+    }
+}
+
 /// Version for a lockfile's package.
 #[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
 pub enum PackageVersion {
@@ -503,12 +523,41 @@ pub(crate) fn determine_package_version(
         .unwrap_or(PackageVersion::FirstParty(pkg_version.into()))
 }
 
+/// Normalize package names for comparison.
+///
+/// PyPI names are case-insensitive and treat runs of `-`, `_` and `.` as a
+/// single `-`, matching PEP 503's `re.sub(r"[-_.]+", "-", name).lower()`.
+pub(crate) fn normalize_name(package_type: PackageType, name: &str) -> String {
+    match package_type {
+        PackageType::PyPi => {
+            let mut normalized = String::with_capacity(name.len());
+            for c in name.chars() {
+                if !matches!(c, '-' | '_' | '.') {
+                    normalized.extend(c.to_lowercase());
+                } else if !normalized.ends_with('-') {
+                    normalized.push('-');
+                }
+            }
+            normalized
+        },
+        _ => name.into(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::fs::File;
 
     use super::*;
 
+    #[test]
+    fn normalize_pypi_names() {
+        for name in ["foo-bar", "foo__bar", "Foo.Bar", "foo-_.bar", "FOO_bar"] {
+            assert_eq!(normalize_name(PackageType::PyPi, name), "foo-bar", "{name}");
+        }
+        assert_eq!(normalize_name(PackageType::Npm, "Foo_Bar"), "Foo_Bar");
+    }
+
     #[test]
     fn get_path_parser_identifies_lockfile_parsers() {
         let test_cases: &[(&str, LockfileFormat)] = &[
@@ -0,0 +1,333 @@
+//! Detection of unpinned and mutable dependency sources.
+//!
+//! Registry versions are immutable, but git references, download URLs and
+//! local paths can change their contents without any change to the lockfile.
+
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+use serde::Serialize;
+
+use crate::{Package, PackageVersion};
+
+/// Hash algorithms recognized in URL fragments (`#sha256=...`).
+const HASH_ALGORITHMS: [&str; 6] = ["md5", "sha1", "sha224", "sha256", "sha384", "sha512"];
+
+/// Severity of a lint finding.
+#[derive(Serialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Info,
+    Warning,
+    Error,
+}
+
+/// Classification of a non-registry dependency source.
+#[derive(Serialize, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum SourceKind {
+    /// Git repository pinned to a commit.
+    GitCommit,
+    /// Git repository referenced by tag.
+    GitTag,
+    /// Git repository referenced by branch or without any reference.
+    GitBranch,
+    /// Download URL with an integrity hash.
+    UrlWithHash,
+    /// Download URL without an integrity hash.
+    UrlWithoutHash,
+    /// Local path inside the repository.
+    PathInsideRepo,
+    /// Local path outside the repository.
+    PathOutsideRepo,
+    /// Local path with unknown location.
+    PathUnknown,
+}
+
+impl SourceKind {
+    /// Default severity for this kind of source.
+    pub fn severity(&self) -> Severity {
+        match self {
+            Self::GitCommit | Self::UrlWithHash | Self::PathInsideRepo | Self::PathUnknown => {
+                Severity::Info
+            },
+            Self::GitTag | Self::PathOutsideRepo => Severity::Warning,
+            Self::GitBranch | Self::UrlWithoutHash => Severity::Error,
+        }
+    }
+
+    /// Human-readable description of the source.
+    pub fn description(&self) -> &'static str {
+        match self {
+            Self::GitCommit => "Git commit",
+            Self::GitTag => "Git tag",
+            Self::GitBranch => "Git branch",
+            Self::UrlWithHash => "URL with integrity hash",
+            Self::UrlWithoutHash => "URL without integrity hash",
+            Self::PathInsideRepo => "Path inside repository",
+            Self::PathOutsideRepo => "Path outside repository",
+            Self::PathUnknown => "Local path",
+        }
+    }
+}
+
+/// Non-registry source of a package.
+#[derive(Serialize, Clone, PartialEq, Eq, Debug)]
+pub struct SourceFinding {
+    pub name: String,
+    /// Git URL, download URL or path of the package.
+    pub source: Option<String>,
+    pub kind: SourceKind,
+    pub severity: Severity,
+}
+
+/// Classify the sources of all non-registry packages.
+///
+/// Relative paths are resolved against `lockfile_dir` and compared against
+/// `repo_root`. Both directories should be absolute, symlinks are resolved
+/// where the paths exist.
+pub fn lint_sources(
+    packages: &[Package],
+    lockfile_dir: &Path,
+    repo_root: &Path,
+) -> Vec<SourceFinding> {
+    packages
+        .iter()
+        .filter_map(|package| {
+            let kind = classify_source(&package.version, lockfile_dir, repo_root)?;
+            let source = match &package.version {
+                PackageVersion::Git(url) | PackageVersion::DownloadUrl(url) => Some(url.clone()),
+                PackageVersion::Path(path) => path.as_ref().map(|path| path.display().to_string()),
+                _ => None,
+            };
+            Some(SourceFinding {
+                name: package.name.clone(),
+                source,
+                kind,
+                severity: kind.severity(),
+            })
+        })
+        .collect()
+}
+
+/// Classify a package's source.
+///
+/// Returns `None` for registry versions.
+pub fn classify_source(
+    version: &PackageVersion,
+    lockfile_dir: &Path,
+    repo_root: &Path,
+) -> Option<SourceKind> {
+    match version {
+        PackageVersion::Git(url) => Some(classify_git(url)),
+        PackageVersion::DownloadUrl(url) => Some(classify_url(url)),
+        PackageVersion::Path(Some(path)) => {
+            let path = resolve_path(&lockfile_dir.join(path));
+            if path.starts_with(resolve_path(repo_root)) {
+                Some(SourceKind::PathInsideRepo)
+            } else {
+                Some(SourceKind::PathOutsideRepo)
+            }
+        },
+        PackageVersion::Path(None) => Some(SourceKind::PathUnknown),
+        PackageVersion::FirstParty(_) | PackageVersion::ThirdParty(_) | PackageVersion::Unknown => {
+            None
+        },
+    }
+}
+
+/// Classify the reference of a git URL.
+fn classify_git(url: &str) -> SourceKind {
+    let (url, fragment) = match url.split_once('#') {
+        Some((url, fragment)) => (url, Some(fragment)),
+        None => (url, None),
+    };
+    let (url, query) = match url.split_once('?') {
+        Some((url, query)) => (url, Some(query)),
+        None => (url, None),
+    };
+
+    // Lockfiles usually record the resolved commit in the fragment, even when
+    // the manifest referenced a branch or tag (like Cargo's `?branch=main`).
+    if let Some(reference) = fragment.map(|fragment| fragment.trim_start_matches("commit=")) {
+        return classify_git_reference(reference);
+    }
+
+    // Cargo's `?rev=`, `?tag=` and `?branch=` parameters.
+    for param in query.into_iter().flat_map(|query| query.split('&')) {
+        match param.split_once('=') {
+            Some(("rev", rev)) => return classify_git_reference(rev),
+            Some(("tag", _)) => return SourceKind::GitTag,
+            Some(("branch", _)) => return SourceKind::GitBranch,
+            _ => (),
+        }
+    }
+
+    // pip's `git+https://host/repo.git@ref` syntax.
+    let path = match url.split_once("://") {
+        Some((_, rest)) => rest.split_once('/').map_or("", |(_, path)| path),
+        None => url.split_once(':').map_or("", |(_, path)| path),
+    };
+    match path.rsplit_once('@') {
+        Some((_, reference)) => classify_git_reference(reference),
+        None => SourceKind::GitBranch,
+    }
+}
+
+/// Classify a git reference as commit, tag or branch.
+fn classify_git_reference(reference: &str) -> SourceKind {
+    if reference.starts_with("refs/tags/") || reference.starts_with("tag=") {
+        return SourceKind::GitTag;
+    }
+
+    let is_commit =
+        (7..=64).contains(&reference.len()) && reference.chars().all(|c| c.is_ascii_hexdigit());
+    let is_version = reference.trim_start_matches('v').starts_with(|c: char| c.is_ascii_digit());
+
+    match (is_commit, is_version) {
+        (true, _) => SourceKind::GitCommit,
+        (false, true) => SourceKind::GitTag,
+        (false, false) => SourceKind::GitBranch,
+    }
+}
+
+/// Check if a download URL contains an integrity hash.
+fn classify_url(url: &str) -> SourceKind {
+    let fragment = url.split_once('#').map_or("", |(_, fragment)| fragment);
+
+    let has_hash = fragment.split('&').any(|param| match param.split_once('=') {
+        Some((algorithm, hash)) => HASH_ALGORITHMS.contains(&algorithm) && !hash.is_empty(),
+        // Yarn appends the bare SHA-1 checksum.
+        None => param.len() >= 40 && param.chars().all(|c| c.is_ascii_hexdigit()),
+    });
+
+    if has_hash {
+        SourceKind::UrlWithHash
+    } else {
+        SourceKind::UrlWithoutHash
+    }
+}
+
+/// Canonicalize a path, falling back to lexical normalization for paths which
+/// do not exist.
+fn resolve_path(path: &Path) -> PathBuf {
+    fs::canonicalize(path).unwrap_or_else(|_| normalize_path(path))
+}
+
+/// Lexically resolve `.` and `..` path components.
+fn normalize_path(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => (),
+            Component::ParentDir => {
+                normalized.pop();
+            },
+            component => normalized.push(component),
+        }
+    }
+    normalized
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn git_sources() {
+        let cases = [
+            (
+                "git+ssh://git@github.com/Microsoft/TypeScript.git#\
+                 9189e42b1c8b1a91906a245a24697da5e0c11a08",
+                SourceKind::GitCommit,
+            ),
+            (
+                "git+https://github.com/phylum-dev/rust-landlock?branch=main#\
+                 b553736cefc2a740eda746e5730cf250b069a4c1",
+                SourceKind::GitCommit,
+            ),
+            ("https://github.com/x/y.git#commit=2a00da2067b7017f769c9100205a2a5f267a884b", SourceKind::GitCommit),
+            ("git+https://github.com/x/y.git@v1.0.0", SourceKind::GitTag),
+            ("https://github.com/django/django.git#1.11.4", SourceKind::GitTag),
+            ("git+https://github.com/x/y?tag=release", SourceKind::GitTag),
+            ("git+https://github.com/x/y?branch=main", SourceKind::GitBranch),
+            ("git+https://git@github.com/x/y.git@main", SourceKind::GitBranch),
+            ("git@github.com:ruby/yaml.git#feature/next", SourceKind::GitBranch),
+            ("git+https://github.com/x/y.git", SourceKind::GitBranch),
+        ];
+
+        for (url, expected) in cases {
+            assert_eq!(classify_git(url), expected, "{url}");
+        }
+    }
+
+    #[test]
+    fn url_sources() {
+        let cases = [
+            ("https://example.com/pkg-1.0.tar.gz", SourceKind::UrlWithoutHash),
+            ("https://example.com/pkg-1.0.tar.gz#egg=pkg", SourceKind::UrlWithoutHash),
+            ("https://example.com/pkg-1.0.tar.gz#sha256=0123abcd", SourceKind::UrlWithHash),
+            (
+                "https://registry.yarnpkg.com/pkg/-/pkg-1.0.0.tgz#\
+                 0e7fbe7b1a5f0d2a1a5f7b7c6f8b2f1e0d3c4b5a",
+                SourceKind::UrlWithHash,
+            ),
+        ];
+
+        for (url, expected) in cases {
+            assert_eq!(classify_url(url), expected, "{url}");
+        }
+    }
+
+    #[test]
+    fn path_sources() {
+        let repo = Path::new("/repo");
+        let lockfile_dir = Path::new("/repo/app");
+
+        let inside = PackageVersion::Path(Some("../lib".into()));
+        let outside = PackageVersion::Path(Some("../../shared".into()));
+        let registry = PackageVersion::FirstParty("1.0.0".into());
+
+        assert_eq!(classify_source(&inside, lockfile_dir, repo), Some(SourceKind::PathInsideRepo));
+        assert_eq!(
+            classify_source(&outside, lockfile_dir, repo),
+            Some(SourceKind::PathOutsideRepo)
+        );
+        assert_eq!(
+            classify_source(&PackageVersion::Path(None), lockfile_dir, repo),
+            Some(SourceKind::PathUnknown)
+        );
+        assert_eq!(classify_source(&registry, lockfile_dir, repo), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn symlinked_repo_root() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let repo = tempdir.path().join("repo");
+        fs::create_dir_all(repo.join("app")).unwrap();
+        fs::create_dir_all(repo.join("lib")).unwrap();
+
+        let link = tempdir.path().join("link");
+        std::os::unix::fs::symlink(&repo, &link).unwrap();
+
+        let lockfile_dir = fs::canonicalize(repo.join("app")).unwrap();
+        let inside = PackageVersion::Path(Some("../lib".into()));
+
+        assert_eq!(
+            classify_source(&inside, &lockfile_dir, &link),
+            Some(SourceKind::PathInsideRepo)
+        );
+        assert_eq!(
+            classify_source(&inside, &link.join("app"), &repo),
+            Some(SourceKind::PathInsideRepo)
+        );
+    }
+
+    #[test]
+    fn severities() {
+        assert!(SourceKind::GitBranch.severity() > SourceKind::GitTag.severity());
+        assert!(SourceKind::GitTag.severity() > SourceKind::GitCommit.severity());
+        assert_eq!(SourceKind::UrlWithoutHash.severity(), Severity::Error);
+    }
+}
@@ -4,7 +4,7 @@ use std::path::{Path, PathBuf};
 #[cfg(feature = "generator")]
 use anyhow::anyhow;
 use anyhow::Context;
-use phylum_types::types::package::PackageDescriptor;
+use phylum_types::types::package::{PackageDescriptor, PackageType};
 use serde::{Deserialize, Serialize};
 
 use crate::{LockfileFormat, Package, PackageVersion, Parse, ThirdPartyVersion};
@@ -28,6 +28,12 @@ pub struct ParsedLockfile {
     pub path: String,
     pub packages: Vec<PackageDescriptor>,
     pub format: LockfileFormat,
+    /// Registries of all packages resolved through a package registry.
+    ///
+    /// These are not serialized, to keep the output of dependency file
+    /// parsing unchanged.
+    #[serde(skip)]
+    pub sources: Vec<PackageSource>,
 }
 
 impl ParsedLockfile {
@@ -35,11 +41,23 @@ impl ParsedLockfile {
         path: impl Into<String>,
         format: LockfileFormat,
         packages: Vec<PackageDescriptor>,
+        sources: Vec<PackageSource>,
     ) -> Self {
-        Self { path: path.into(), packages, format }
+        Self { path: path.into(), packages, format, sources }
     }
 }
 
+/// Registry a package was resolved from.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct PackageSource {
+    pub name: String,
+    pub version: String,
+    #[serde(rename = "type")]
+    pub package_type: PackageType,
+    /// Third-party registry, `None` for the ecosystem's public registry.
+    pub registry: Option<String>,
+}
+
 /// Parse a dependency file.
 ///
 /// The `path` argument is used for format identification and display purposes.
@@ -82,7 +100,9 @@ pub fn parse_depfile(
         let packages = parse_lockfile_content(contents, parser);
 
         match packages {
-            Ok(packages) => return Ok(ParsedLockfile::new(path, format, packages)),
+            Ok((packages, sources)) => {
+                return Ok(ParsedLockfile::new(path, format, packages, sources))
+            },
             // Store error on failure.
             Err(err) => lockfile_error = Some(err),
         }
@@ -121,9 +141,9 @@ fn try_get_packages(path: impl Into<String>, contents: &str) -> Result<ParsedLoc
         if let Some(packages) = parser.parse(contents).ok().filter(|pkgs| !pkgs.is_empty()) {
             log::info!("Identified lockfile type: {}", format);
 
-            let packages = filter_packages(packages);
+            let (packages, sources) = filter_packages(packages);
 
-            return Ok(ParsedLockfile::new(path, format, packages));
+            return Ok(ParsedLockfile::new(path, format, packages, sources));
         }
     }
 
@@ -154,35 +174,43 @@ fn generate_lockfile(
     )?;
 
     // Parse the generated lockfile.
-    let packages = parse_lockfile_content(&generated_lockfile, parser)?;
+    let (packages, sources) = parse_lockfile_content(&generated_lockfile, parser)?;
 
-    Ok(ParsedLockfile::new(display_path, format, packages))
+    Ok(ParsedLockfile::new(display_path, format, packages, sources))
 }
 
 /// Attempt to parse a lockfile.
 fn parse_lockfile_content(
     content: &str,
     parser: &dyn Parse,
-) -> Result<Vec<PackageDescriptor>, ParseError> {
+) -> Result<(Vec<PackageDescriptor>, Vec<PackageSource>), ParseError> {
     let packages = parser.parse(content).context("Failed to parse lockfile")?;
     Ok(filter_packages(packages))
 }
 
 /// Filter packages for submission.
-fn filter_packages(mut packages: Vec<Package>) -> Vec<PackageDescriptor> {
-    packages
+///
+/// Returns the packages which should be submitted, together with the
+/// registries of all registry packages.
+fn filter_packages(mut packages: Vec<Package>) -> (Vec<PackageDescriptor>, Vec<PackageSource>) {
+    let mut sources = Vec::new();
+    let packages = packages
         .drain(..)
         .filter_map(|package| {
             // Check if package should be submitted based on version format.
-            let version = match package.version {
-                PackageVersion::FirstParty(version) => version,
+            let (version, registry) = match package.version {
+                PackageVersion::FirstParty(version) => (version, None),
                 PackageVersion::ThirdParty(ThirdPartyVersion { registry, version }) => {
                     log::debug!("Using registry {registry:?} for {} ({version})", package.name);
-                    version
+                    (version, Some(registry))
                 },
                 PackageVersion::Git(url) => {
                     log::debug!("Git dependency {} will not be analyzed ({url:?})", package.name);
-                    url
+                    return Some(PackageDescriptor {
+                        package_type: package.package_type,
+                        version: url,
+                        name: package.name,
+                    });
                 },
                 PackageVersion::Path(path) => {
                     log::debug!("Ignoring filesystem dependency {} ({path:?})", package.name);
@@ -198,13 +226,22 @@ fn filter_packages(mut packages: Vec<Package>) -> Vec<PackageDescriptor> {
                 },
             };
 
+            sources.push(PackageSource {
+                name: package.name.clone(),
+                version: version.clone(),
+                package_type: package.package_type,
+                registry,
+            });
+
             Some(PackageDescriptor {
                 package_type: package.package_type,
                 version,
                 name: package.name,
             })
         })
-        .collect()
+        .collect();
+
+    (packages, sources)
 }
 
 #[cfg(test)]
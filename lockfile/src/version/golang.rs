@@ -0,0 +1,217 @@
+//! Go module versions, including pseudo-versions.
+
+use std::cmp::Ordering;
+use std::fmt::{self, Display, Formatter};
+
+use crate::version::semver::{Comparator, Identifier, Op, SemVer};
+use crate::version::VersionError;
+
+/// Name of the version scheme used in errors.
+const SCHEME: &str = "Go";
+
+/// Go module version.
+///
+/// Pseudo-versions (`v0.0.0-20191109021931-daa7c04131f5`) are valid semantic
+/// versions whose pre-release encodes the commit timestamp, so they are
+/// ordered chronologically relative to each other.
+#[derive(Clone, Debug)]
+pub struct GoVersion {
+    pub semver: SemVer,
+    raw: String,
+}
+
+impl GoVersion {
+    /// Parse a Go module version.
+    ///
+    /// The leading `v` is optional, since some tools omit it.
+    pub fn parse(version: &str) -> Result<Self, VersionError> {
+        let raw = version.trim();
+        let semver = SemVer::parse(raw).map_err(|_| VersionError::invalid_version(SCHEME, raw))?;
+        Ok(Self { semver, raw: raw.into() })
+    }
+
+    /// Check if this is a pre-release version.
+    ///
+    /// This includes all pseudo-versions.
+    pub fn is_prerelease(&self) -> bool {
+        self.semver.is_prerelease()
+    }
+
+    /// Check if this version is marked as `+incompatible`.
+    pub fn is_incompatible(&self) -> bool {
+        self.semver.build == ["incompatible"]
+    }
+
+    /// Check if this is a pseudo-version referencing an untagged commit.
+    pub fn is_pseudo(&self) -> bool {
+        self.pseudo_parts().is_some()
+    }
+
+    /// Commit timestamp (`yyyymmddhhmmss`) of a pseudo-version.
+    pub fn pseudo_timestamp(&self) -> Option<&str> {
+        self.pseudo_parts().map(|(timestamp, _)| timestamp)
+    }
+
+    /// Abbreviated commit hash of a pseudo-version.
+    pub fn pseudo_revision(&self) -> Option<&str> {
+        self.pseudo_parts().map(|(_, revision)| revision)
+    }
+
+    /// Split a pseudo-version's last pre-release identifier into timestamp and
+    /// revision.
+    fn pseudo_parts(&self) -> Option<(&str, &str)> {
+        let pre = &self.semver.pre;
+        let last = match pre.last()? {
+            Identifier::AlphaNumeric(last) => last,
+            Identifier::Numeric(_) => return None,
+        };
+
+        let (timestamp, revision) = last.split_once('-')?;
+        let valid = timestamp.len() == 14
+            && timestamp.chars().all(|c| c.is_ascii_digit())
+            && !revision.is_empty()
+            && revision.chars().all(|c| c.is_ascii_alphanumeric());
+
+        // Pseudo-versions are either `vX.0.0-TIMESTAMP-REV` or
+        // `vX.Y.Z-PRE.0.TIMESTAMP-REV`.
+        let base_valid = match pre.len() {
+            1 => self.semver.minor == 0 && self.semver.patch == 0,
+            len => pre[len - 2] == Identifier::Numeric(0),
+        };
+
+        (valid && base_valid).then_some((timestamp, revision))
+    }
+}
+
+impl PartialEq for GoVersion {
+    fn eq(&self, other: &Self) -> bool {
+        self.semver == other.semver
+    }
+}
+
+impl Eq for GoVersion {}
+
+impl PartialOrd for GoVersion {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for GoVersion {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.semver.cmp(&other.semver)
+    }
+}
+
+impl Display for GoVersion {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.raw)
+    }
+}
+
+/// Go module requirement.
+///
+/// Requirements in `go.mod` are minimum versions under minimal version
+/// selection, so a bare version matches itself and all newer versions.
+/// Explicit comparison operators are also accepted.
+#[derive(Clone, Debug)]
+pub struct GoReq {
+    comparators: Vec<Comparator>,
+    raw: String,
+}
+
+impl GoReq {
+    /// Parse a requirement.
+    pub fn parse(requirement: &str) -> Result<Self, VersionError> {
+        let invalid = || VersionError::invalid_requirement(SCHEME, requirement);
+
+        let mut comparators = Vec::new();
+        for comparator in requirement.split(',') {
+            let comparator = comparator.trim();
+            let (op, version) = match comparator {
+                _ if comparator.starts_with(">=") => (Op::GreaterEq, &comparator[2..]),
+                _ if comparator.starts_with("<=") => (Op::LessEq, &comparator[2..]),
+                _ if comparator.starts_with('>') => (Op::Greater, &comparator[1..]),
+                _ if comparator.starts_with('<') => (Op::Less, &comparator[1..]),
+                _ if comparator.starts_with('=') => (Op::Exact, &comparator[1..]),
+                _ => (Op::GreaterEq, comparator),
+            };
+
+            // Go does not support wildcards or partial versions.
+            let version = SemVer::parse(version).map_err(|_| invalid())?;
+
+            comparators.push(Comparator::new(op, version));
+        }
+
+        Ok(Self { comparators, raw: requirement.into() })
+    }
+
+    /// Check if a version satisfies this requirement.
+    ///
+    /// Contrary to other ecosystems, pre-releases are not excluded, since
+    /// pseudo-versions are commonly required directly.
+    pub fn matches(&self, version: &GoVersion) -> bool {
+        self.comparators.iter().all(|comparator| comparator.matches(&version.semver))
+    }
+}
+
+impl Display for GoReq {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.raw)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn version(version: &str) -> GoVersion {
+        GoVersion::parse(version).unwrap()
+    }
+
+    #[test]
+    fn pseudo_versions() {
+        let pseudo = version("v0.0.0-20191109021931-daa7c04131f5");
+        assert!(pseudo.is_pseudo());
+        assert_eq!(pseudo.pseudo_timestamp(), Some("20191109021931"));
+        assert_eq!(pseudo.pseudo_revision(), Some("daa7c04131f5"));
+
+        assert!(version("v1.2.4-0.20191109021931-daa7c04131f5").is_pseudo());
+        assert!(version("v1.2.3-pre.0.20191109021931-daa7c04131f5").is_pseudo());
+        assert!(!version("v1.2.3-pre").is_pseudo());
+        assert!(!version("v1.2.0-20191109021931-daa7c04131f5").is_pseudo());
+    }
+
+    #[test]
+    fn version_ordering() {
+        let ordered = [
+            "v0.0.0-20191109021931-daa7c04131f5",
+            "v0.0.0-20200101000000-000000000000",
+            "v0.1.0",
+            "v1.2.3",
+            "v1.2.4-0.20191109021931-daa7c04131f5",
+            "v1.2.4",
+            "v2.0.0+incompatible",
+        ];
+
+        for pair in ordered.windows(2) {
+            assert!(version(pair[0]) < version(pair[1]), "{} < {}", pair[0], pair[1]);
+        }
+
+        assert!(version("v2.0.0+incompatible").is_incompatible());
+    }
+
+    #[test]
+    fn requirements() {
+        let minimum = GoReq::parse("v1.2.3").unwrap();
+        assert!(minimum.matches(&version("v1.2.3")));
+        assert!(minimum.matches(&version("v1.9.0")));
+        assert!(!minimum.matches(&version("v1.2.2")));
+
+        let range = GoReq::parse(">=v1.2.3, <v1.3.0").unwrap();
+        assert!(range.matches(&version("v1.2.4-0.20191109021931-daa7c04131f5")));
+        assert!(!range.matches(&version("v1.3.0")));
+
+        assert!(GoReq::parse("v1.2").is_err());
+    }
+}
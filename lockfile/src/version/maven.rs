@@ -0,0 +1,446 @@
+//! Maven versions, ordered like Maven's `ComparableVersion`.
+
+use std::cmp::Ordering;
+use std::fmt::{self, Display, Formatter};
+
+use crate::version::VersionError;
+
+/// Name of the version scheme used in errors.
+const SCHEME: &str = "Maven";
+
+/// Well-known qualifiers in ascending order.
+///
+/// The empty string represents release versions. Unknown qualifiers are
+/// sorted after all known ones.
+const QUALIFIERS: [&str; 7] = ["alpha", "beta", "milestone", "rc", "snapshot", "", "sp"];
+
+/// Maven version.
+///
+/// Maven accepts arbitrary strings as versions, so parsing only fails for
+/// empty versions.
+#[derive(Clone, Debug)]
+pub struct MavenVersion {
+    items: Vec<Item>,
+    raw: String,
+}
+
+impl MavenVersion {
+    /// Parse a Maven version.
+    pub fn parse(version: &str) -> Result<Self, VersionError> {
+        let raw = version.trim();
+        if raw.is_empty() || raw.contains(char::is_whitespace) {
+            return Err(VersionError::invalid_version(SCHEME, version));
+        }
+
+        Ok(Self { items: parse_items(&raw.to_lowercase()), raw: raw.into() })
+    }
+
+    /// Check if this version has a qualifier below release, like `-SNAPSHOT`
+    /// or `-rc1`.
+    pub fn is_prerelease(&self) -> bool {
+        fn is_prerelease(items: &[Item]) -> bool {
+            items.iter().any(|item| match item {
+                Item::Int(_) => false,
+                Item::Str(qualifier) => qualifier_key(qualifier) < qualifier_key(""),
+                Item::List(items) => is_prerelease(items),
+            })
+        }
+        is_prerelease(&self.items)
+    }
+}
+
+impl PartialEq for MavenVersion {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for MavenVersion {}
+
+impl PartialOrd for MavenVersion {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for MavenVersion {
+    fn cmp(&self, other: &Self) -> Ordering {
+        compare_lists(&self.items, &other.items)
+    }
+}
+
+impl Display for MavenVersion {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.raw)
+    }
+}
+
+/// Version component.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Item {
+    Int(u64),
+    Str(String),
+    List(Vec<Item>),
+}
+
+impl Item {
+    /// Parse a single token.
+    ///
+    /// Single-letter qualifiers directly followed by a digit are expanded
+    /// (`a1` is `alpha-1`), and release aliases are normalized.
+    fn new(token: &str, followed_by_digit: bool) -> Self {
+        if !token.is_empty() && token.chars().all(|c| c.is_ascii_digit()) {
+            // Numbers exceeding `u64` are exceedingly rare, so just saturate.
+            return Self::Int(token.parse().unwrap_or(u64::MAX));
+        }
+
+        let qualifier = match token {
+            "a" if followed_by_digit => "alpha",
+            "b" if followed_by_digit => "beta",
+            "m" if followed_by_digit => "milestone",
+            "ga" | "final" | "release" => "",
+            "cr" => "rc",
+            token => token,
+        };
+        Self::Str(qualifier.into())
+    }
+
+    /// Check if the item is equivalent to a missing item.
+    fn is_null(&self) -> bool {
+        match self {
+            Self::Int(number) => *number == 0,
+            Self::Str(qualifier) => qualifier.is_empty(),
+            Self::List(items) => items.is_empty(),
+        }
+    }
+
+    /// Compare against another item, with `None` representing padding.
+    fn compare(&self, other: Option<&Item>) -> Ordering {
+        match (self, other) {
+            (Self::Int(number), None) => number.cmp(&0),
+            (Self::Int(lhs), Some(Self::Int(rhs))) => lhs.cmp(rhs),
+            (Self::Int(_), Some(_)) => Ordering::Greater,
+
+            (Self::Str(qualifier), None) => qualifier_key(qualifier).cmp(&qualifier_key("")),
+            (Self::Str(lhs), Some(Self::Str(rhs))) => qualifier_key(lhs).cmp(&qualifier_key(rhs)),
+            (Self::Str(_), Some(_)) => Ordering::Less,
+
+            (Self::List(items), None) => {
+                items.first().map_or(Ordering::Equal, |first| first.compare(None))
+            },
+            (Self::List(lhs), Some(Self::List(rhs))) => compare_lists(lhs, rhs),
+            (Self::List(_), Some(Self::Int(_))) => Ordering::Less,
+            (Self::List(_), Some(Self::Str(_))) => Ordering::Greater,
+        }
+    }
+}
+
+/// Sort key for string qualifiers.
+fn qualifier_key(qualifier: &str) -> (usize, &str) {
+    match QUALIFIERS.iter().position(|known| *known == qualifier) {
+        Some(index) => (index, ""),
+        None => (QUALIFIERS.len(), qualifier),
+    }
+}
+
+/// Compare two item lists, padding the shorter one with null items.
+fn compare_lists(lhs: &[Item], rhs: &[Item]) -> Ordering {
+    for i in 0..lhs.len().max(rhs.len()) {
+        let ordering = match (lhs.get(i), rhs.get(i)) {
+            (Some(lhs), rhs) => lhs.compare(rhs),
+            (None, Some(rhs)) => rhs.compare(None).reverse(),
+            (None, None) => unreachable!(),
+        };
+
+        if ordering.is_ne() {
+            return ordering;
+        }
+    }
+
+    Ordering::Equal
+}
+
+/// Split a lowercase version into its items.
+///
+/// `.` separates items, while `-` and transitions between digits and letters
+/// start a new sub-list.
+fn parse_items(version: &str) -> Vec<Item> {
+    // Stack of nested lists, the last list is the one currently being appended to.
+    let mut stack: Vec<Vec<Item>> = vec![Vec::new()];
+
+    let chars: Vec<char> = version.chars().collect();
+    let mut start = 0;
+    let mut is_digit = false;
+
+    for (i, &c) in chars.iter().enumerate() {
+        let token: String = chars[start..i].iter().collect();
+        let current = stack.last_mut().unwrap();
+
+        match c {
+            '.' => {
+                current.push(if token.is_empty() {
+                    Item::Int(0)
+                } else {
+                    Item::new(&token, false)
+                });
+                start = i + 1;
+            },
+            '-' => {
+                current.push(if token.is_empty() {
+                    Item::Int(0)
+                } else {
+                    Item::new(&token, false)
+                });
+                start = i + 1;
+                stack.push(Vec::new());
+            },
+            c if c.is_ascii_digit() => {
+                if !is_digit && i > start {
+                    current.push(Item::new(&token, true));
+                    start = i;
+                    stack.push(Vec::new());
+                }
+                is_digit = true;
+                continue;
+            },
+            _ => {
+                if is_digit && i > start {
+                    current.push(Item::new(&token, false));
+                    start = i;
+                    stack.push(Vec::new());
+                }
+            },
+        }
+
+        is_digit = false;
+    }
+
+    if chars.len() > start {
+        let token: String = chars[start..].iter().collect();
+        stack.last_mut().unwrap().push(Item::new(&token, false));
+    }
+
+    // Collapse the stack, normalizing each list by trimming trailing null items.
+    let mut items = stack.pop().unwrap();
+    loop {
+        normalize(&mut items);
+        match stack.pop() {
+            Some(mut parent) => {
+                parent.push(Item::List(items));
+                items = parent;
+            },
+            None => return items,
+        }
+    }
+}
+
+/// Remove trailing null items from a list.
+///
+/// Trimming stops at the first non-null item that is not a list.
+fn normalize(items: &mut Vec<Item>) {
+    for i in (0..items.len()).rev() {
+        if items[i].is_null() {
+            items.remove(i);
+        } else if !matches!(items[i], Item::List(_)) {
+            break;
+        }
+    }
+}
+
+/// Maven version range specification.
+///
+/// Supports range syntax like `[1.0,2.0)` and unions of ranges. Bare
+/// versions are "soft" requirements in Maven; since the resolved version
+/// always matches those in practice, they are treated as exact pins here.
+#[derive(Clone, Debug)]
+pub struct MavenReq {
+    ranges: Vec<Range>,
+    raw: String,
+}
+
+impl MavenReq {
+    /// Parse a version range specification.
+    pub fn parse(requirement: &str) -> Result<Self, VersionError> {
+        let invalid = || VersionError::invalid_requirement(SCHEME, requirement);
+
+        let trimmed = requirement.trim();
+
+        // Property references cannot be resolved without the full POM.
+        if trimmed.contains("${") {
+            return Err(invalid());
+        }
+
+        let ranges = if trimmed.starts_with(['[', '(']) {
+            parse_ranges(trimmed).ok_or_else(invalid)?
+        } else {
+            let version = MavenVersion::parse(trimmed).map_err(|_| invalid())?;
+            vec![Range::exact(version)]
+        };
+
+        Ok(Self { ranges, raw: requirement.into() })
+    }
+
+    /// Check if a version is within any of the ranges.
+    pub fn matches(&self, version: &MavenVersion) -> bool {
+        self.ranges.iter().any(|range| range.contains(version))
+    }
+}
+
+impl Display for MavenReq {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.raw)
+    }
+}
+
+/// Version range with optional bounds.
+#[derive(Clone, Debug)]
+struct Range {
+    lower: Option<(MavenVersion, bool)>,
+    upper: Option<(MavenVersion, bool)>,
+}
+
+impl Range {
+    fn exact(version: MavenVersion) -> Self {
+        Self { lower: Some((version.clone(), true)), upper: Some((version, true)) }
+    }
+
+    fn contains(&self, version: &MavenVersion) -> bool {
+        let above_lower = match &self.lower {
+            Some((lower, true)) => version >= lower,
+            Some((lower, false)) => version > lower,
+            None => true,
+        };
+        let below_upper = match &self.upper {
+            Some((upper, true)) => version <= upper,
+            Some((upper, false)) => version < upper,
+            None => true,
+        };
+        above_lower && below_upper
+    }
+}
+
+/// Parse a comma-separated list of bracketed ranges.
+fn parse_ranges(mut input: &str) -> Option<Vec<Range>> {
+    let mut ranges = Vec::new();
+
+    loop {
+        input = input.trim_start().strip_prefix(',').unwrap_or(input).trim_start();
+        if input.is_empty() {
+            return (!ranges.is_empty()).then_some(ranges);
+        }
+
+        let lower_inclusive = match input.chars().next()? {
+            '[' => true,
+            '(' => false,
+            _ => return None,
+        };
+
+        let end = input.find([']', ')'])?;
+        let upper_inclusive = input[end..].starts_with(']');
+        let inner = &input[1..end];
+        input = &input[end + 1..];
+
+        let bound = |version: &str| -> Option<Option<MavenVersion>> {
+            let version = version.trim();
+            match version.is_empty() {
+                true => Some(None),
+                false => MavenVersion::parse(version).ok().map(Some),
+            }
+        };
+
+        let range = match inner.split_once(',') {
+            // Single version ranges must be inclusive (`[1.0]`).
+            None if lower_inclusive && upper_inclusive => Range::exact(bound(inner)??),
+            None => return None,
+            Some((lower, upper)) => Range {
+                lower: bound(lower)?.map(|lower| (lower, lower_inclusive)),
+                upper: bound(upper)?.map(|upper| (upper, upper_inclusive)),
+            },
+        };
+        ranges.push(range);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn version(version: &str) -> MavenVersion {
+        MavenVersion::parse(version).unwrap()
+    }
+
+    #[test]
+    fn version_ordering() {
+        let ordered = [
+            "1-alpha-snapshot",
+            "1-alpha",
+            "1-alpha2",
+            "1-beta",
+            "1-milestone-1",
+            "1-rc1",
+            "1-snapshot",
+            "1",
+            "1-sp",
+            "1-abc",
+            "1-1",
+            "1.0.1",
+            "1.1",
+            "1.2-snapshot",
+            "1.2",
+            "1.10",
+            "2.0",
+        ];
+
+        for pair in ordered.windows(2) {
+            assert!(version(pair[0]) < version(pair[1]), "{} < {}", pair[0], pair[1]);
+        }
+    }
+
+    #[test]
+    fn version_equality() {
+        let equal = [
+            ("1", "1.0.0"),
+            ("1-ga", "1"),
+            ("1.0.final", "1"),
+            ("1-RELEASE", "1"),
+            ("1cr1", "1rc1"),
+            ("1a1", "1-alpha-1"),
+            ("1.0-SNAPSHOT", "1-snapshot"),
+        ];
+
+        for (lhs, rhs) in equal {
+            assert_eq!(version(lhs), version(rhs), "{lhs} == {rhs}");
+        }
+    }
+
+    #[test]
+    fn ranges() {
+        let cases = [
+            ("[1.0,2.0)", "1.5", true),
+            ("[1.0,2.0)", "2.0", false),
+            ("(1.0,2.0]", "1.0", false),
+            ("(1.0,2.0]", "2.0", true),
+            ("[1.0,)", "9.9", true),
+            ("(,1.0]", "1.0", true),
+            ("[1.5]", "1.5", true),
+            ("[1.5]", "1.5.1", false),
+            ("(,1.0],[1.2,)", "1.1", false),
+            ("(,1.0],[1.2,)", "1.3", true),
+            ("1.0", "1.0.0", true),
+            ("1.0", "1.1", false),
+        ];
+
+        for (requirement, candidate, expected) in cases {
+            let parsed = MavenReq::parse(requirement).unwrap();
+            assert_eq!(
+                parsed.matches(&version(candidate)),
+                expected,
+                "{requirement:?} matches {candidate:?}"
+            );
+        }
+
+        for invalid in ["[1.0", "(1.0)", "${project.version}", "[1.0,2.0) x"] {
+            assert!(MavenReq::parse(invalid).is_err(), "{invalid:?} should be invalid");
+        }
+    }
+}
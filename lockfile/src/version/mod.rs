@@ -0,0 +1,221 @@
+//! Ecosystem-specific version ordering and requirement matching.
+//!
+//! Lockfile parsers only provide versions as opaque strings. This module
+//! implements each ecosystem's rules for comparing versions and for matching
+//! them against the version requirements found in manifests.
+
+use std::cmp::Ordering;
+use std::fmt::{self, Display, Formatter};
+
+use phylum_types::types::package::PackageType;
+
+use crate::version::golang::{GoReq, GoVersion};
+use crate::version::maven::{MavenReq, MavenVersion};
+use crate::version::nuget::{NuGetReq, NuGetVersion};
+use crate::version::pep440::{Pep440, Pep440Req};
+use crate::version::rubygems::{GemReq, GemVersion};
+use crate::version::semver::{Dialect, SemVer, SemVerReq};
+
+pub mod golang;
+pub mod maven;
+pub mod nuget;
+pub mod pep440;
+pub mod rubygems;
+pub mod semver;
+
+/// Version parsing error.
+#[derive(thiserror::Error, Clone, Debug, PartialEq, Eq)]
+pub enum VersionError {
+    #[error("Invalid {scheme} version: {version:?}")]
+    InvalidVersion { scheme: &'static str, version: String },
+    #[error("Invalid {scheme} version requirement: {requirement:?}")]
+    InvalidRequirement { scheme: &'static str, requirement: String },
+}
+
+impl VersionError {
+    pub(crate) fn invalid_version(scheme: &'static str, version: &str) -> Self {
+        Self::InvalidVersion { scheme, version: version.into() }
+    }
+
+    pub(crate) fn invalid_requirement(scheme: &'static str, requirement: &str) -> Self {
+        Self::InvalidRequirement { scheme, requirement: requirement.into() }
+    }
+}
+
+/// Package version parsed according to its ecosystem's rules.
+///
+/// Versions of different ecosystems are not comparable with each other.
+#[derive(Clone, Debug)]
+pub enum Version {
+    Npm(SemVer),
+    Cargo(SemVer),
+    PyPi(Pep440),
+    Maven(MavenVersion),
+    RubyGems(GemVersion),
+    Nuget(NuGetVersion),
+    Golang(GoVersion),
+}
+
+impl Version {
+    /// Parse a version using the rules of the package's ecosystem.
+    pub fn parse(package_type: &PackageType, version: &str) -> Result<Self, VersionError> {
+        Ok(match package_type {
+            PackageType::Npm => Self::Npm(SemVer::parse(version)?),
+            PackageType::Cargo => Self::Cargo(SemVer::parse(version)?),
+            PackageType::PyPi => Self::PyPi(Pep440::parse(version)?),
+            PackageType::Maven => Self::Maven(MavenVersion::parse(version)?),
+            PackageType::RubyGems => Self::RubyGems(GemVersion::parse(version)?),
+            PackageType::Nuget => Self::Nuget(NuGetVersion::parse(version)?),
+            PackageType::Golang => Self::Golang(GoVersion::parse(version)?),
+        })
+    }
+
+    /// Check if this is a pre-release version.
+    pub fn is_prerelease(&self) -> bool {
+        match self {
+            Self::Npm(version) | Self::Cargo(version) => version.is_prerelease(),
+            Self::PyPi(version) => version.is_prerelease(),
+            Self::Maven(version) => version.is_prerelease(),
+            Self::RubyGems(version) => version.is_prerelease(),
+            Self::Nuget(version) => version.is_prerelease(),
+            Self::Golang(version) => version.is_prerelease(),
+        }
+    }
+}
+
+impl PartialEq for Version {
+    fn eq(&self, other: &Self) -> bool {
+        self.partial_cmp(other) == Some(Ordering::Equal)
+    }
+}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        match (self, other) {
+            (Self::Npm(lhs), Self::Npm(rhs)) => Some(lhs.cmp(rhs)),
+            (Self::Cargo(lhs), Self::Cargo(rhs)) => Some(lhs.cmp(rhs)),
+            (Self::PyPi(lhs), Self::PyPi(rhs)) => Some(lhs.cmp(rhs)),
+            (Self::Maven(lhs), Self::Maven(rhs)) => Some(lhs.cmp(rhs)),
+            (Self::RubyGems(lhs), Self::RubyGems(rhs)) => Some(lhs.cmp(rhs)),
+            (Self::Nuget(lhs), Self::Nuget(rhs)) => Some(lhs.cmp(rhs)),
+            (Self::Golang(lhs), Self::Golang(rhs)) => Some(lhs.cmp(rhs)),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Version {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Npm(version) | Self::Cargo(version) => version.fmt(f),
+            Self::PyPi(version) => version.fmt(f),
+            Self::Maven(version) => version.fmt(f),
+            Self::RubyGems(version) => version.fmt(f),
+            Self::Nuget(version) => version.fmt(f),
+            Self::Golang(version) => version.fmt(f),
+        }
+    }
+}
+
+/// Version requirement in the syntax of an ecosystem's manifest.
+///
+/// | Ecosystem | Syntax                                              |
+/// |-----------|-----------------------------------------------------|
+/// | npm       | `package.json` ranges (`^1.2.3`, `1.x \|\| >=3`)    |
+/// | Cargo     | `Cargo.toml` requirements (`1.2`, `>=1, <1.5`)      |
+/// | PyPI      | PEP 440 specifiers and Poetry constraints           |
+/// | Maven     | Version ranges (`[1.0,2.0)`)                        |
+/// | RubyGems  | `Gemfile` requirements (`~> 7.0, >= 7.0.1`)         |
+/// | NuGet     | Version ranges and floating versions (`1.*`)        |
+/// | Go        | Minimum versions from `go.mod`                      |
+#[derive(Clone, Debug)]
+pub enum VersionReq {
+    Npm(SemVerReq),
+    Cargo(SemVerReq),
+    PyPi(Pep440Req),
+    Maven(MavenReq),
+    RubyGems(GemReq),
+    Nuget(NuGetReq),
+    Golang(GoReq),
+}
+
+impl VersionReq {
+    /// Parse a requirement using the manifest syntax of the package's
+    /// ecosystem.
+    pub fn parse(package_type: &PackageType, requirement: &str) -> Result<Self, VersionError> {
+        Ok(match package_type {
+            PackageType::Npm => Self::Npm(SemVerReq::parse(requirement, Dialect::Npm)?),
+            PackageType::Cargo => Self::Cargo(SemVerReq::parse(requirement, Dialect::Cargo)?),
+            PackageType::PyPi => Self::PyPi(Pep440Req::parse(requirement)?),
+            PackageType::Maven => Self::Maven(MavenReq::parse(requirement)?),
+            PackageType::RubyGems => Self::RubyGems(GemReq::parse(requirement)?),
+            PackageType::Nuget => Self::Nuget(NuGetReq::parse(requirement)?),
+            PackageType::Golang => Self::Golang(GoReq::parse(requirement)?),
+        })
+    }
+
+    /// Check if a version satisfies this requirement.
+    ///
+    /// Versions from a different ecosystem never match.
+    pub fn matches(&self, version: &Version) -> bool {
+        match (self, version) {
+            (Self::Npm(req), Version::Npm(version)) => req.matches(version),
+            (Self::Cargo(req), Version::Cargo(version)) => req.matches(version),
+            (Self::PyPi(req), Version::PyPi(version)) => req.matches(version),
+            (Self::Maven(req), Version::Maven(version)) => req.matches(version),
+            (Self::RubyGems(req), Version::RubyGems(version)) => req.matches(version),
+            (Self::Nuget(req), Version::Nuget(version)) => req.matches(version),
+            (Self::Golang(req), Version::Golang(version)) => req.matches(version),
+            _ => false,
+        }
+    }
+}
+
+impl Display for VersionReq {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Npm(req) | Self::Cargo(req) => req.fmt(f),
+            Self::PyPi(req) => req.fmt(f),
+            Self::Maven(req) => req.fmt(f),
+            Self::RubyGems(req) => req.fmt(f),
+            Self::Nuget(req) => req.fmt(f),
+            Self::Golang(req) => req.fmt(f),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compare_across_ecosystems() {
+        let npm = Version::parse(&PackageType::Npm, "1.0.0").unwrap();
+        let cargo = Version::parse(&PackageType::Cargo, "1.0.0").unwrap();
+        assert_eq!(npm.partial_cmp(&cargo), None);
+        assert_ne!(npm, cargo);
+
+        let req = VersionReq::parse(&PackageType::Cargo, "1").unwrap();
+        assert!(req.matches(&cargo));
+        assert!(!req.matches(&npm));
+    }
+
+    #[test]
+    fn newer_than() {
+        let cases = [
+            (PackageType::Npm, "1.10.0", "1.9.0"),
+            (PackageType::Cargo, "0.10.0", "0.10.0-rc.1"),
+            (PackageType::PyPi, "2.0.post1", "2.0"),
+            (PackageType::Maven, "1.0", "1.0-SNAPSHOT"),
+            (PackageType::RubyGems, "1.0", "1.0.rc1"),
+            (PackageType::Nuget, "1.0.0.1", "1.0.0"),
+            (PackageType::Golang, "v1.0.0", "v0.0.0-20191109021931-daa7c04131f5"),
+        ];
+
+        for (package_type, newer, older) in cases {
+            let newer = Version::parse(&package_type, newer).unwrap();
+            let older = Version::parse(&package_type, older).unwrap();
+            assert!(newer > older, "{package_type:?}: {newer} > {older}");
+        }
+    }
+}
@@ -0,0 +1,273 @@
+//! NuGet versions and version ranges.
+//!
+//! NuGet follows SemVer 2.0, but allows an optional fourth "revision" number
+//! and compares pre-release labels case-insensitively.
+
+use std::cmp::Ordering;
+use std::fmt::{self, Display, Formatter};
+
+use crate::version::semver::{self, Identifier};
+use crate::version::VersionError;
+
+/// Name of the version scheme used in errors.
+const SCHEME: &str = "NuGet";
+
+/// NuGet version.
+#[derive(Clone, Debug)]
+pub struct NuGetVersion {
+    /// Major, minor, patch and revision numbers.
+    pub numbers: [u64; 4],
+    /// Lowercase pre-release identifiers.
+    pre: Vec<Identifier>,
+    raw: String,
+}
+
+impl NuGetVersion {
+    /// Parse a NuGet version.
+    ///
+    /// Missing minor, patch and revision numbers default to zero.
+    pub fn parse(version: &str) -> Result<Self, VersionError> {
+        Self::parse_inner(version).ok_or_else(|| VersionError::invalid_version(SCHEME, version))
+    }
+
+    fn parse_inner(version: &str) -> Option<Self> {
+        let raw = version.trim();
+
+        // Build metadata is ignored for comparison.
+        let without_metadata = raw.split_once('+').map_or(raw, |(version, _)| version);
+
+        let (core, pre) = match without_metadata.split_once('-') {
+            Some((core, pre)) => (core, semver::parse_pre(&pre.to_lowercase())?),
+            None => (without_metadata, Vec::new()),
+        };
+
+        let parts: Vec<_> = core.split('.').collect();
+        if parts.len() > 4 {
+            return None;
+        }
+
+        let mut numbers = [0; 4];
+        for (number, part) in numbers.iter_mut().zip(parts) {
+            *number = semver::parse_number(part)?;
+        }
+
+        Some(Self { numbers, pre, raw: raw.into() })
+    }
+
+    /// Check if this is a pre-release version.
+    pub fn is_prerelease(&self) -> bool {
+        !self.pre.is_empty()
+    }
+}
+
+impl PartialEq for NuGetVersion {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for NuGetVersion {}
+
+impl PartialOrd for NuGetVersion {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for NuGetVersion {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.numbers.cmp(&other.numbers).then_with(|| {
+            match (self.pre.is_empty(), other.pre.is_empty()) {
+                (true, true) => Ordering::Equal,
+                (true, false) => Ordering::Greater,
+                (false, true) => Ordering::Less,
+                (false, false) => self.pre.cmp(&other.pre),
+            }
+        })
+    }
+}
+
+impl Display for NuGetVersion {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.raw)
+    }
+}
+
+/// NuGet version range.
+///
+/// Supports interval notation (`[1.0,2.0)`), bare minimum versions (`1.0`
+/// means `>= 1.0`) and floating versions (`1.*`, `1.0.0-*`), which are
+/// treated as their lowest matching version.
+#[derive(Clone, Debug)]
+pub struct NuGetReq {
+    lower: Option<(NuGetVersion, bool)>,
+    upper: Option<(NuGetVersion, bool)>,
+    raw: String,
+}
+
+impl NuGetReq {
+    /// Parse a version range.
+    pub fn parse(requirement: &str) -> Result<Self, VersionError> {
+        Self::parse_inner(requirement)
+            .ok_or_else(|| VersionError::invalid_requirement(SCHEME, requirement))
+    }
+
+    fn parse_inner(requirement: &str) -> Option<Self> {
+        let trimmed = requirement.trim();
+        let raw = requirement.into();
+
+        let lower_inclusive = match trimmed.chars().next()? {
+            '[' => true,
+            '(' => false,
+            _ => {
+                let lower = Some((parse_floating(trimmed)?, true));
+                return Some(Self { lower, upper: None, raw });
+            },
+        };
+
+        let upper_inclusive = match trimmed.chars().last()? {
+            ']' => true,
+            ')' => false,
+            _ => return None,
+        };
+
+        let inner = &trimmed[1..trimmed.len() - 1];
+        let bound = |version: &str| -> Option<Option<NuGetVersion>> {
+            let version = version.trim();
+            match version.is_empty() {
+                true => Some(None),
+                false => NuGetVersion::parse(version).ok().map(Some),
+            }
+        };
+
+        match inner.split_once(',') {
+            // Exact versions must be inclusive (`[1.0]`).
+            None if lower_inclusive && upper_inclusive => {
+                let version = bound(inner)??;
+                let lower = Some((version.clone(), true));
+                Some(Self { lower, upper: Some((version, true)), raw })
+            },
+            None => None,
+            Some((lower, upper)) => {
+                let lower = bound(lower)?.map(|lower| (lower, lower_inclusive));
+                let upper = bound(upper)?.map(|upper| (upper, upper_inclusive));
+                Some(Self { lower, upper, raw })
+            },
+        }
+    }
+
+    /// Check if a version is within the range.
+    pub fn matches(&self, version: &NuGetVersion) -> bool {
+        let above_lower = match &self.lower {
+            Some((lower, true)) => version >= lower,
+            Some((lower, false)) => version > lower,
+            None => true,
+        };
+        let below_upper = match &self.upper {
+            Some((upper, true)) => version <= upper,
+            Some((upper, false)) => version < upper,
+            None => true,
+        };
+        above_lower && below_upper
+    }
+}
+
+impl Display for NuGetReq {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.raw)
+    }
+}
+
+/// Parse a potentially floating version into its lowest matching version.
+fn parse_floating(version: &str) -> Option<NuGetVersion> {
+    if !version.contains('*') {
+        return NuGetVersion::parse(version).ok();
+    }
+
+    let (core, pre) = match version.split_once('-') {
+        Some((core, pre)) => (core, Some(pre)),
+        None => (version, None),
+    };
+
+    // Replace floating numbers with zero (`1.*` becomes `1.0`).
+    let core = match core.strip_suffix('*') {
+        Some(prefix) if prefix.is_empty() || prefix.ends_with('.') => format!("{prefix}0"),
+        Some(_) => return None,
+        None => core.into(),
+    };
+
+    // Floating pre-release labels start at their lowest possible value.
+    let pre = match pre.map(|pre| pre.strip_suffix('*')) {
+        Some(Some("")) => "-0".into(),
+        Some(Some(label)) => format!("-{}", label.trim_end_matches('.')),
+        Some(None) => return None,
+        None => String::new(),
+    };
+
+    NuGetVersion::parse(&format!("{core}{pre}")).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn version(version: &str) -> NuGetVersion {
+        NuGetVersion::parse(version).unwrap()
+    }
+
+    #[test]
+    fn version_ordering() {
+        let ordered = [
+            "1.0.0-alpha",
+            "1.0.0-Beta",
+            "1.0.0-beta.2",
+            "1.0.0",
+            "1.0.0.1",
+            "1.0.1",
+            "1.1",
+            "2.0.0.0",
+        ];
+
+        for pair in ordered.windows(2) {
+            assert!(version(pair[0]) < version(pair[1]), "{} < {}", pair[0], pair[1]);
+        }
+
+        assert_eq!(version("1.0"), version("1.0.0.0"));
+        assert_eq!(version("1.0.0-RC"), version("1.0.0-rc+build"));
+
+        for invalid in ["", "1.0.0.0.0", "a", "1.0.0-"] {
+            assert!(NuGetVersion::parse(invalid).is_err(), "{invalid:?} should be invalid");
+        }
+    }
+
+    #[test]
+    fn ranges() {
+        let cases = [
+            ("1.0", "0.9", false),
+            ("1.0", "5.0", true),
+            ("[1.0]", "1.0.0.0", true),
+            ("[1.0]", "1.0.1", false),
+            ("[1.0,2.0)", "2.0", false),
+            ("(1.0,2.0]", "2.0", true),
+            ("(,1.0]", "0.5", true),
+            ("(1.0,)", "1.0", false),
+            ("1.*", "1.0", true),
+            ("1.2.*", "1.1.9", false),
+            ("1.0.0-*", "1.0.0-alpha", true),
+            ("1.0.0-beta*", "1.0.0-alpha", false),
+        ];
+
+        for (requirement, candidate, expected) in cases {
+            let parsed = NuGetReq::parse(requirement).unwrap();
+            assert_eq!(
+                parsed.matches(&version(candidate)),
+                expected,
+                "{requirement:?} matches {candidate:?}"
+            );
+        }
+
+        for invalid in ["[1.0", "(1.0)", "1.*.0", "$(Version)"] {
+            assert!(NuGetReq::parse(invalid).is_err(), "{invalid:?} should be invalid");
+        }
+    }
+}
@@ -0,0 +1,720 @@
+//! PEP 440 versions and specifiers, as used by PyPI.
+//!
+//! Requirements additionally support Poetry's caret (`^`), tilde (`~`) and
+//! `||` syntax, since it is used in `pyproject.toml` manifests.
+
+use std::cmp::Ordering;
+use std::fmt::{self, Display, Formatter};
+
+use crate::version::VersionError;
+
+/// Name of the version scheme used in errors.
+const SCHEME: &str = "PEP 440";
+
+/// PEP 440 version.
+#[derive(Clone, Debug)]
+pub struct Pep440 {
+    pub epoch: u64,
+    pub release: Vec<u64>,
+    pub pre: Option<(PreKind, u64)>,
+    pub post: Option<u64>,
+    pub dev: Option<u64>,
+    pub local: Vec<LocalSegment>,
+}
+
+impl Pep440 {
+    /// Parse a PEP 440 version.
+    ///
+    /// All alternative spellings permitted by PEP 440's normalization rules
+    /// are accepted.
+    pub fn parse(version: &str) -> Result<Self, VersionError> {
+        Self::parse_prefix(version)
+            .filter(|(_, rest)| rest.is_empty())
+            .map(|(version, _)| version)
+            .ok_or_else(|| VersionError::invalid_version(SCHEME, version))
+    }
+
+    /// Parse a version, returning the unparsed remainder.
+    fn parse_prefix(version: &str) -> Option<(Self, String)> {
+        let normalized = version.trim().to_ascii_lowercase();
+        let mut cursor = Cursor::new(&normalized);
+
+        cursor.eat("v");
+
+        // Parse epoch and release segments.
+        let first = cursor.number()?;
+        let (epoch, first) = match cursor.eat("!") {
+            true => (first, cursor.number()?),
+            false => (0, first),
+        };
+        let mut release = vec![first];
+        loop {
+            let checkpoint = cursor.pos;
+            match cursor.eat(".").then(|| cursor.number()).flatten() {
+                Some(number) => release.push(number),
+                None => {
+                    cursor.pos = checkpoint;
+                    break;
+                },
+            }
+        }
+
+        // Parse pre-release segment.
+        let checkpoint = cursor.pos;
+        cursor.separator();
+        let pre_kinds = [
+            ("alpha", PreKind::Alpha),
+            ("beta", PreKind::Beta),
+            ("preview", PreKind::Rc),
+            ("pre", PreKind::Rc),
+            ("rc", PreKind::Rc),
+            ("a", PreKind::Alpha),
+            ("b", PreKind::Beta),
+            ("c", PreKind::Rc),
+        ];
+        let pre = match cursor.keyword(&pre_kinds) {
+            Some(kind) => Some((kind, cursor.implicit_number())),
+            None => {
+                cursor.pos = checkpoint;
+                None
+            },
+        };
+
+        // Parse post-release segment, including the implicit `1.0-1` syntax.
+        let checkpoint = cursor.pos;
+        let post = if let Some(number) = cursor.eat("-").then(|| cursor.number()).flatten() {
+            Some(number)
+        } else {
+            cursor.pos = checkpoint;
+            cursor.separator();
+            let post_kinds = [("post", ()), ("rev", ()), ("r", ())];
+            match cursor.keyword(&post_kinds) {
+                Some(()) => Some(cursor.implicit_number()),
+                None => {
+                    cursor.pos = checkpoint;
+                    None
+                },
+            }
+        };
+
+        // Parse development release segment.
+        let checkpoint = cursor.pos;
+        cursor.separator();
+        let dev = match cursor.eat("dev") {
+            true => Some(cursor.implicit_number()),
+            false => {
+                cursor.pos = checkpoint;
+                None
+            },
+        };
+
+        // Parse local version label.
+        let mut local = Vec::new();
+        if cursor.eat("+") {
+            loop {
+                let segment = cursor.take_while(|c| c.is_ascii_alphanumeric());
+                if segment.is_empty() {
+                    return None;
+                }
+                local.push(LocalSegment::new(segment));
+
+                if !cursor.separator() {
+                    break;
+                }
+            }
+        }
+
+        let version = Self { epoch, release, pre, post, dev, local };
+        Some((version, cursor.rest().into()))
+    }
+
+    /// Check if this is a pre-release or development release.
+    pub fn is_prerelease(&self) -> bool {
+        self.pre.is_some() || self.dev.is_some()
+    }
+
+    /// Check if this is a post-release.
+    pub fn is_postrelease(&self) -> bool {
+        self.post.is_some()
+    }
+
+    /// Version without its local version label.
+    pub fn public(&self) -> Self {
+        Self { local: Vec::new(), ..self.clone() }
+    }
+
+    /// Version with only its epoch and release segments.
+    fn base(&self) -> Self {
+        Self {
+            epoch: self.epoch,
+            release: self.release.clone(),
+            pre: None,
+            post: None,
+            dev: None,
+            local: Vec::new(),
+        }
+    }
+
+    /// Check if the release segments start with the specified prefix.
+    ///
+    /// Missing release segments are treated as zero.
+    fn release_starts_with(&self, prefix: &[u64]) -> bool {
+        prefix.iter().enumerate().all(|(i, segment)| self.release.get(i).unwrap_or(&0) == segment)
+    }
+
+    /// Sort key for the pre-release segment.
+    fn pre_key(&self) -> PreKey {
+        match (self.pre, self.post, self.dev) {
+            // Development releases without pre/post come before pre-releases.
+            (None, None, Some(_)) => PreKey::Dev,
+            (None, ..) => PreKey::Final,
+            (Some((kind, number)), ..) => PreKey::Pre(kind, number),
+        }
+    }
+}
+
+impl PartialEq for Pep440 {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Pep440 {}
+
+impl PartialOrd for Pep440 {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Pep440 {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Trailing zeros are ignored for release comparisons.
+        let len = self.release.len().max(other.release.len());
+        let release = |version: &Self, i| *version.release.get(i).unwrap_or(&0);
+        let release_ordering = (0..len)
+            .map(|i| release(self, i).cmp(&release(other, i)))
+            .find(|ordering| ordering.is_ne())
+            .unwrap_or(Ordering::Equal);
+
+        // Missing development releases are sorted after all development releases.
+        let dev_key = |version: &Self| (version.dev.is_none(), version.dev);
+
+        self.epoch
+            .cmp(&other.epoch)
+            .then(release_ordering)
+            .then_with(|| self.pre_key().cmp(&other.pre_key()))
+            .then_with(|| self.post.cmp(&other.post))
+            .then_with(|| dev_key(self).cmp(&dev_key(other)))
+            .then_with(|| self.local.cmp(&other.local))
+    }
+}
+
+impl Display for Pep440 {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if self.epoch != 0 {
+            write!(f, "{}!", self.epoch)?;
+        }
+
+        let release: Vec<_> = self.release.iter().map(u64::to_string).collect();
+        write!(f, "{}", release.join("."))?;
+
+        if let Some((kind, number)) = self.pre {
+            write!(f, "{kind}{number}")?;
+        }
+
+        if let Some(post) = self.post {
+            write!(f, ".post{post}")?;
+        }
+
+        if let Some(dev) = self.dev {
+            write!(f, ".dev{dev}")?;
+        }
+
+        if !self.local.is_empty() {
+            let local: Vec<_> = self.local.iter().map(LocalSegment::to_string).collect();
+            write!(f, "+{}", local.join("."))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Pre-release phase.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum PreKind {
+    Alpha,
+    Beta,
+    Rc,
+}
+
+impl Display for PreKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Alpha => write!(f, "a"),
+            Self::Beta => write!(f, "b"),
+            Self::Rc => write!(f, "rc"),
+        }
+    }
+}
+
+/// Local version label segment.
+///
+/// Alphanumeric segments sort before numeric ones.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum LocalSegment {
+    AlphaNumeric(String),
+    Numeric(u64),
+}
+
+impl LocalSegment {
+    fn new(segment: &str) -> Self {
+        match segment.parse() {
+            Ok(number) if segment.chars().all(|c| c.is_ascii_digit()) => Self::Numeric(number),
+            _ => Self::AlphaNumeric(segment.into()),
+        }
+    }
+}
+
+impl Display for LocalSegment {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::AlphaNumeric(segment) => write!(f, "{segment}"),
+            Self::Numeric(number) => write!(f, "{number}"),
+        }
+    }
+}
+
+/// Sort key of the pre-release segment.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum PreKey {
+    Dev,
+    Pre(PreKind, u64),
+    Final,
+}
+
+/// PEP 440 version specifier set.
+#[derive(Clone, Debug)]
+pub struct Pep440Req {
+    /// Alternative specifier sets, of which all specifiers must match.
+    sets: Vec<Vec<Specifier>>,
+    raw: String,
+}
+
+impl Pep440Req {
+    /// Parse a version specifier.
+    pub fn parse(requirement: &str) -> Result<Self, VersionError> {
+        let invalid = || VersionError::invalid_requirement(SCHEME, requirement);
+
+        let sets = requirement
+            .split("||")
+            .flat_map(|set| set.split('|'))
+            .map(|set| Self::clauses(set).iter().map(|clause| Specifier::parse(clause)).collect())
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(invalid)?;
+
+        Ok(Self { sets, raw: requirement.into() })
+    }
+
+    /// Split a set of clauses separated by commas or whitespace.
+    ///
+    /// Poetry allows `>=1.0 <2.0`, so whitespace separates clauses unless it
+    /// directly follows an operator like in `>= 1.0`.
+    fn clauses(set: &str) -> Vec<String> {
+        let mut clauses: Vec<String> = Vec::new();
+        for token in set.split(',').flat_map(str::split_whitespace) {
+            match clauses.last_mut() {
+                Some(clause) if Specifier::is_operator(clause) => clause.push_str(token),
+                _ => clauses.push(token.to_owned()),
+            }
+        }
+
+        // Keep empty sets, which match all versions.
+        if clauses.is_empty() {
+            clauses.push(String::new());
+        }
+
+        clauses
+    }
+
+    /// Check if a version satisfies this requirement.
+    ///
+    /// Pre-releases are only matched if a specifier explicitly includes a
+    /// pre-release version.
+    pub fn matches(&self, version: &Pep440) -> bool {
+        self.sets.iter().any(|set| {
+            let allow_prerelease = set.iter().any(Specifier::allows_prerelease);
+            (allow_prerelease || !version.is_prerelease())
+                && set.iter().all(|specifier| specifier.matches(version))
+        })
+    }
+}
+
+impl Display for Pep440Req {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.raw)
+    }
+}
+
+/// Single version clause.
+#[derive(Clone, Debug)]
+enum Specifier {
+    /// Matches all versions.
+    Any,
+    /// `~=`.
+    Compatible(Pep440),
+    /// `==`, with optional `.*` prefix matching.
+    Equal(Pep440, bool),
+    /// `!=`, with optional `.*` prefix matching.
+    NotEqual(Pep440, bool),
+    Less(Pep440),
+    LessEq(Pep440),
+    Greater(Pep440),
+    GreaterEq(Pep440),
+    /// Inclusive lower and exclusive upper bound, used for Poetry's `^` and
+    /// `~`.
+    Range(Box<(Pep440, Pep440)>),
+    /// `===`, which compares versions as strings.
+    Arbitrary(String),
+}
+
+impl Specifier {
+    /// Operators, ordered so that longer prefixes match first.
+    const OPERATORS: [&'static str; 10] = ["===", "~=", "==", "!=", "<=", ">=", "<", ">", "^", "~"];
+
+    /// Check if a clause consists only of an operator.
+    fn is_operator(clause: &str) -> bool {
+        Self::OPERATORS.contains(&clause)
+    }
+
+    fn parse(specifier: &str) -> Option<Self> {
+        let specifier = specifier.trim();
+
+        if specifier.is_empty() || specifier == "*" {
+            return Some(Self::Any);
+        }
+
+        if let Some(version) = specifier.strip_prefix("===") {
+            return Some(Self::Arbitrary(version.trim().to_ascii_lowercase()));
+        }
+
+        for op in &Self::OPERATORS[1..] {
+            let version = match specifier.strip_prefix(op) {
+                Some(version) => version.trim(),
+                None => continue,
+            };
+
+            return match *op {
+                "==" => Self::parse_prefix_match(version).map(|(v, prefix)| Self::Equal(v, prefix)),
+                "!=" => {
+                    Self::parse_prefix_match(version).map(|(v, prefix)| Self::NotEqual(v, prefix))
+                },
+                // Compatible releases require at least two release segments.
+                "~=" => Pep440::parse(version)
+                    .ok()
+                    .filter(|version| version.release.len() >= 2)
+                    .map(Self::Compatible),
+                "<=" => Some(Self::LessEq(Pep440::parse(version).ok()?)),
+                ">=" => Some(Self::GreaterEq(Pep440::parse(version).ok()?)),
+                "<" => Some(Self::Less(Pep440::parse(version).ok()?)),
+                ">" => Some(Self::Greater(Pep440::parse(version).ok()?)),
+                "^" => Self::poetry_caret(version),
+                "~" => Self::poetry_tilde(version),
+                _ => unreachable!(),
+            };
+        }
+
+        // Poetry treats bare versions as exact matches.
+        Self::parse_prefix_match(specifier).map(|(version, prefix)| Self::Equal(version, prefix))
+    }
+
+    /// Parse a version with optional trailing `.*`.
+    fn parse_prefix_match(version: &str) -> Option<(Pep440, bool)> {
+        match version.strip_suffix(".*") {
+            Some(prefix) => Some((Pep440::parse(prefix).ok()?, true)),
+            None => Some((Pep440::parse(version).ok()?, false)),
+        }
+    }
+
+    /// Poetry's `^1.2.3`, allowing updates which do not change the left-most
+    /// non-zero release segment.
+    fn poetry_caret(version: &str) -> Option<Self> {
+        let version = Pep440::parse(version).ok()?;
+
+        let last = version.release.len() - 1;
+        let index = version.release.iter().position(|segment| *segment != 0).unwrap_or(last);
+        Some(Self::range(version, index))
+    }
+
+    /// Poetry's `~1.2.3`, allowing patch-level updates.
+    fn poetry_tilde(version: &str) -> Option<Self> {
+        let version = Pep440::parse(version).ok()?;
+
+        let index = if version.release.len() == 1 { 0 } else { 1 };
+
+        Some(Self::range(version, index))
+    }
+
+    /// Increment the release segment at `index`, dropping all following
+    /// segments.
+    ///
+    /// Returns `None` if the segment would overflow.
+    fn bump(version: &Pep440, index: usize) -> Option<Pep440> {
+        let mut release = version.release[..=index].to_vec();
+        release[index] = release[index].checked_add(1)?;
+        Some(Pep440 { epoch: version.epoch, release, ..Pep440::default() })
+    }
+
+    /// Desugared range `>=lower,<upper`, with `upper` bumping the release
+    /// segment at `index`.
+    ///
+    /// Overflowing upper bounds are treated as unbounded.
+    fn range(lower: Pep440, index: usize) -> Self {
+        match Self::bump(&lower, index) {
+            Some(upper) => Self::Range(Box::new((lower, upper))),
+            None => Self::GreaterEq(lower),
+        }
+    }
+
+    /// Whether this specifier allows matching pre-releases.
+    fn allows_prerelease(&self) -> bool {
+        match self {
+            Self::Compatible(version)
+            | Self::Equal(version, _)
+            | Self::LessEq(version)
+            | Self::GreaterEq(version) => version.is_prerelease(),
+            Self::Range(range) => range.0.is_prerelease(),
+            Self::Arbitrary(_) => true,
+            _ => false,
+        }
+    }
+
+    fn matches(&self, candidate: &Pep440) -> bool {
+        match self {
+            Self::Any => true,
+            Self::Compatible(version) => {
+                let prefix = &version.release[..version.release.len() - 1];
+                candidate >= version
+                    && candidate.epoch == version.epoch
+                    && candidate.release_starts_with(prefix)
+            },
+            Self::Equal(version, prefix) => Self::equal(candidate, version, *prefix),
+            Self::NotEqual(version, prefix) => !Self::equal(candidate, version, *prefix),
+            Self::LessEq(version) => &candidate.public() <= version,
+            Self::GreaterEq(version) => &candidate.public() >= version,
+            Self::Less(version) => {
+                // Exclude pre-releases of the bound, unless it is a pre-release itself.
+                candidate < version
+                    && (version.is_prerelease()
+                        || !candidate.is_prerelease()
+                        || candidate.base() != version.base())
+            },
+            Self::Greater(version) => {
+                // Exclude post-releases and local versions of the bound.
+                candidate > version
+                    && (version.is_postrelease()
+                        || !candidate.is_postrelease()
+                        || candidate.base() != version.base())
+                    && (candidate.local.is_empty() || candidate.base() != version.base())
+            },
+            Self::Range(range) => {
+                let (lower, upper) = &**range;
+                candidate >= lower && Self::Less(upper.clone()).matches(candidate)
+            },
+            Self::Arbitrary(version) => candidate.to_string() == *version,
+        }
+    }
+
+    /// Check for version equality, optionally only comparing release prefixes.
+    fn equal(candidate: &Pep440, version: &Pep440, prefix: bool) -> bool {
+        if prefix {
+            candidate.epoch == version.epoch && candidate.release_starts_with(&version.release)
+        } else if version.local.is_empty() {
+            &candidate.public() == version
+        } else {
+            candidate == version
+        }
+    }
+}
+
+impl Default for Pep440 {
+    fn default() -> Self {
+        Self { epoch: 0, release: vec![0], pre: None, post: None, dev: None, local: Vec::new() }
+    }
+}
+
+/// Simple parser state for PEP 440 versions.
+struct Cursor<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn new(input: &'a str) -> Self {
+        Self { input, pos: 0 }
+    }
+
+    /// Remaining unparsed input.
+    fn rest(&self) -> &'a str {
+        &self.input[self.pos..]
+    }
+
+    /// Consume `token` if the input starts with it.
+    fn eat(&mut self, token: &str) -> bool {
+        let found = self.rest().starts_with(token);
+        if found {
+            self.pos += token.len();
+        }
+        found
+    }
+
+    /// Consume an optional `-`, `_` or `.` separator.
+    fn separator(&mut self) -> bool {
+        self.eat("-") || self.eat("_") || self.eat(".")
+    }
+
+    /// Consume the longest prefix matching `predicate`.
+    fn take_while(&mut self, predicate: impl Fn(char) -> bool) -> &'a str {
+        let rest = self.rest();
+        let len = rest.find(|c| !predicate(c)).unwrap_or(rest.len());
+        self.pos += len;
+        &rest[..len]
+    }
+
+    /// Consume a decimal number.
+    fn number(&mut self) -> Option<u64> {
+        let digits = self.take_while(|c| c.is_ascii_digit());
+        digits.parse().ok()
+    }
+
+    /// Consume a number with optional separator, defaulting to zero.
+    fn implicit_number(&mut self) -> u64 {
+        let checkpoint = self.pos;
+        self.separator();
+        self.number().unwrap_or_else(|| {
+            self.pos = checkpoint;
+            0
+        })
+    }
+
+    /// Consume the first matching keyword.
+    fn keyword<T: Copy>(&mut self, keywords: &[(&str, T)]) -> Option<T> {
+        keywords.iter().find(|(keyword, _)| self.eat(keyword)).map(|(_, value)| *value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn version(version: &str) -> Pep440 {
+        Pep440::parse(version).unwrap()
+    }
+
+    #[test]
+    fn normalize_versions() {
+        let cases = [
+            ("1.0", "1.0"),
+            ("v1.0", "1.0"),
+            ("1!2.0", "1!2.0"),
+            ("1.0-alpha1", "1.0a1"),
+            ("1.0.BETA", "1.0b0"),
+            ("1.0c2", "1.0rc2"),
+            ("1.0.preview-3", "1.0rc3"),
+            ("1.0-1", "1.0.post1"),
+            ("1.0-rev2", "1.0.post2"),
+            ("1.0.r", "1.0.post0"),
+            ("1.0-dev", "1.0.dev0"),
+            ("1.0rc1.post2.dev3", "1.0rc1.post2.dev3"),
+            ("1.0+ubuntu-1", "1.0+ubuntu.1"),
+        ];
+
+        for (input, expected) in cases {
+            assert_eq!(version(input).to_string(), expected, "{input:?}");
+        }
+
+        for invalid in ["", "latest", "1.0+", "1.0.x", "1.0 beta"] {
+            assert!(Pep440::parse(invalid).is_err(), "{invalid:?} should be invalid");
+        }
+    }
+
+    #[test]
+    fn version_ordering() {
+        let ordered = [
+            "1.0.dev456",
+            "1.0a1",
+            "1.0a2.dev456",
+            "1.0a12.dev456",
+            "1.0a12",
+            "1.0b1.dev456",
+            "1.0b2",
+            "1.0b2.post345.dev456",
+            "1.0b2.post345",
+            "1.0rc1.dev456",
+            "1.0rc1",
+            "1.0",
+            "1.0+abc.5",
+            "1.0+abc.7",
+            "1.0+5",
+            "1.0.post456.dev34",
+            "1.0.post456",
+            "1.1.dev1",
+            "1!0.1",
+        ];
+
+        for pair in ordered.windows(2) {
+            assert!(version(pair[0]) < version(pair[1]), "{} < {}", pair[0], pair[1]);
+        }
+
+        assert_eq!(version("1.0"), version("1.0.0"));
+    }
+
+    #[test]
+    fn specifiers() {
+        let cases = [
+            ("~=2.2", "2.9", true),
+            ("~=2.2", "3.0", false),
+            ("~=1.4.5", "1.4.9", true),
+            ("~=1.4.5", "1.5.0", false),
+            ("==1.1.*", "1.1.3", true),
+            ("==1.1.*", "1.2.0", false),
+            ("==1.1", "1.1.0+local", true),
+            ("!=1.1", "1.1.0", false),
+            (">=1.0, <2.0", "1.5", true),
+            (">=1.0, <2.0", "2.0", false),
+            ("<2.0", "2.0rc1", false),
+            (">1.7", "1.7.post2", false),
+            (">1.7.post2", "1.7.post3", true),
+            (">=1.0", "2.0b1", false),
+            (">=1.0b1", "2.0b1", true),
+            ("===1.0+local", "1.0+local", true),
+            ("^1.2.3", "1.9", true),
+            ("^1.2.3", "2.0", false),
+            ("^0.2", "0.3", false),
+            ("~1.2.3", "1.2.9", true),
+            ("~1.2.3", "1.3", false),
+            ("1.2.*", "1.2.5", true),
+            ("1.2.3", "1.2.4", false),
+            ("^1.0 || ^3.0", "3.1", true),
+            ("*", "4.0", true),
+            (">=1.0 <2.0", "1.5", true),
+            (">=1.0 <2.0", "2.0", false),
+            (">= 1.0, < 2.0", "0.9", false),
+            (">= 1.0 < 2.0 || == 3.0", "3.0", true),
+            ("^18446744073709551615", "18446744073709551615.1", true),
+            ("~1.18446744073709551615", "1.18446744073709551615.1", true),
+        ];
+
+        for (requirement, candidate, expected) in cases {
+            let parsed = Pep440Req::parse(requirement).unwrap();
+            assert_eq!(
+                parsed.matches(&version(candidate)),
+                expected,
+                "{requirement:?} matches {candidate:?}"
+            );
+        }
+
+        assert!(Pep440Req::parse("~=1").is_err());
+        assert!(Pep440Req::parse(">=1.0 <").is_err());
+    }
+}
@@ -0,0 +1,331 @@
+//! RubyGems versions and requirements.
+
+use std::cmp::Ordering;
+use std::fmt::{self, Display, Formatter};
+
+use crate::version::VersionError;
+
+/// Name of the version scheme used in errors.
+const SCHEME: &str = "RubyGems";
+
+/// RubyGems version.
+#[derive(Clone, Debug)]
+pub struct GemVersion {
+    segments: Vec<Segment>,
+    raw: String,
+}
+
+impl GemVersion {
+    /// Parse a RubyGems version.
+    pub fn parse(version: &str) -> Result<Self, VersionError> {
+        let raw = version.trim();
+        if !is_valid(raw) {
+            return Err(VersionError::invalid_version(SCHEME, version));
+        }
+
+        // Dashes are shorthand for pre-release segments (`1.0-1` is `1.0.pre.1`).
+        let expanded = raw.replace('-', ".pre.");
+
+        let mut segments = Vec::new();
+        let mut chars = expanded.chars().peekable();
+        while let Some(&c) = chars.peek() {
+            if c == '.' {
+                chars.next();
+            } else if c.is_ascii_digit() {
+                let mut number = String::new();
+                while let Some(c) = chars.next_if(char::is_ascii_digit) {
+                    number.push(c);
+                }
+                segments.push(Segment::Numeric(number.parse().unwrap_or(u64::MAX)));
+            } else {
+                let mut text = String::new();
+                while let Some(c) = chars.next_if(char::is_ascii_alphabetic) {
+                    text.push(c);
+                }
+                segments.push(Segment::Text(text));
+            }
+        }
+
+        Ok(Self { segments, raw: raw.into() })
+    }
+
+    /// Check if this is a pre-release version.
+    ///
+    /// Any version containing letters is a pre-release.
+    pub fn is_prerelease(&self) -> bool {
+        self.segments.iter().any(|segment| matches!(segment, Segment::Text(_)))
+    }
+
+    /// Release version, without any pre-release segments.
+    pub fn release(&self) -> Self {
+        let segments: Vec<_> = self
+            .segments
+            .iter()
+            .take_while(|segment| matches!(segment, Segment::Numeric(_)))
+            .cloned()
+            .collect();
+        let raw = segments.iter().map(Segment::to_string).collect::<Vec<_>>().join(".");
+        Self { segments, raw }
+    }
+
+    /// Upper bound of the pessimistic operator (`~>`).
+    ///
+    /// Drops pre-release segments and the last release segment, then
+    /// increments the new last segment (`2.2.1` becomes `2.3`).
+    ///
+    /// Returns `None` if the incremented segment would overflow.
+    fn bump(&self) -> Option<Self> {
+        let mut segments = self.release().segments;
+        if segments.len() > 1 {
+            segments.pop();
+        }
+        if let Some(Segment::Numeric(last)) = segments.last_mut() {
+            *last = last.checked_add(1)?;
+        }
+        let raw = segments.iter().map(Segment::to_string).collect::<Vec<_>>().join(".");
+        Some(Self { segments, raw })
+    }
+
+    /// Segments with trailing zeros removed from release and pre-release parts.
+    fn canonical_segments(&self) -> Vec<&Segment> {
+        let split = self
+            .segments
+            .iter()
+            .position(|segment| matches!(segment, Segment::Text(_)))
+            .unwrap_or(self.segments.len());
+        let (release, pre) = self.segments.split_at(split);
+
+        let trimmed_len = |segments: &[Segment]| {
+            segments.iter().rposition(|s| s != &Segment::Numeric(0)).map_or(0, |i| i + 1)
+        };
+
+        release[..trimmed_len(release)].iter().chain(&pre[..trimmed_len(pre)]).collect()
+    }
+}
+
+impl PartialEq for GemVersion {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for GemVersion {}
+
+impl PartialOrd for GemVersion {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for GemVersion {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let lhs = self.canonical_segments();
+        let rhs = other.canonical_segments();
+
+        let zero = Segment::Numeric(0);
+        for i in 0..lhs.len().max(rhs.len()) {
+            let lhs = lhs.get(i).copied().unwrap_or(&zero);
+            let rhs = rhs.get(i).copied().unwrap_or(&zero);
+
+            let ordering = match (lhs, rhs) {
+                (Segment::Numeric(lhs), Segment::Numeric(rhs)) => lhs.cmp(rhs),
+                (Segment::Text(lhs), Segment::Text(rhs)) => lhs.cmp(rhs),
+                (Segment::Text(_), Segment::Numeric(_)) => Ordering::Less,
+                (Segment::Numeric(_), Segment::Text(_)) => Ordering::Greater,
+            };
+
+            if ordering.is_ne() {
+                return ordering;
+            }
+        }
+
+        Ordering::Equal
+    }
+}
+
+impl Display for GemVersion {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.raw)
+    }
+}
+
+/// Version segment.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Segment {
+    Numeric(u64),
+    Text(String),
+}
+
+impl Display for Segment {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Numeric(number) => write!(f, "{number}"),
+            Self::Text(text) => write!(f, "{text}"),
+        }
+    }
+}
+
+/// Check a version against `Gem::Version`'s pattern.
+fn is_valid(version: &str) -> bool {
+    let (release, pre) = match version.split_once('-') {
+        Some((release, pre)) => (release, Some(pre)),
+        None => (version, None),
+    };
+
+    let mut release_parts = release.split('.');
+    let first_valid = release_parts
+        .next()
+        .is_some_and(|first| !first.is_empty() && first.chars().all(|c| c.is_ascii_digit()));
+    let release_valid = first_valid
+        && release_parts
+            .all(|part| !part.is_empty() && part.chars().all(|c| c.is_ascii_alphanumeric()));
+
+    let pre_valid = pre.into_iter().flat_map(|pre| pre.split('.')).all(|part| {
+        !part.is_empty() && part.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+    });
+
+    release_valid && pre_valid
+}
+
+/// RubyGems requirement, like the ones used in a `Gemfile`.
+///
+/// Multiple comma-separated constraints must all be satisfied.
+#[derive(Clone, Debug)]
+pub struct GemReq {
+    constraints: Vec<(Op, GemVersion)>,
+    raw: String,
+}
+
+impl GemReq {
+    /// Parse a requirement.
+    pub fn parse(requirement: &str) -> Result<Self, VersionError> {
+        let invalid = || VersionError::invalid_requirement(SCHEME, requirement);
+
+        let mut constraints = Vec::new();
+        for constraint in requirement.split(',') {
+            // Remove quotes from constraints copied from a `Gemfile`.
+            let constraint = constraint.trim().trim_matches(|c| c == '"' || c == '\'').trim();
+
+            let (op, version) = Op::split(constraint);
+            let version = GemVersion::parse(version).map_err(|_| invalid())?;
+            constraints.push((op, version));
+        }
+
+        Ok(Self { constraints, raw: requirement.into() })
+    }
+
+    /// Check if a version satisfies all constraints.
+    pub fn matches(&self, version: &GemVersion) -> bool {
+        self.constraints.iter().all(|(op, requirement)| match op {
+            Op::Exact => version == requirement,
+            Op::NotEqual => version != requirement,
+            Op::Greater => version > requirement,
+            Op::GreaterEq => version >= requirement,
+            Op::Less => version < requirement,
+            Op::LessEq => version <= requirement,
+            // Overflowing upper bounds are treated as unbounded.
+            Op::Pessimistic => {
+                version >= requirement
+                    && requirement.bump().map_or(true, |upper| version.release() < upper)
+            },
+        })
+    }
+}
+
+impl Display for GemReq {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.raw)
+    }
+}
+
+/// Requirement operator.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Op {
+    Exact,
+    NotEqual,
+    Greater,
+    GreaterEq,
+    Less,
+    LessEq,
+    Pessimistic,
+}
+
+impl Op {
+    /// Split the operator from a constraint, defaulting to `=`.
+    fn split(constraint: &str) -> (Self, &str) {
+        let ops = [
+            ("~>", Self::Pessimistic),
+            (">=", Self::GreaterEq),
+            ("<=", Self::LessEq),
+            ("!=", Self::NotEqual),
+            (">", Self::Greater),
+            ("<", Self::Less),
+            ("=", Self::Exact),
+        ];
+
+        for (token, op) in ops {
+            if let Some(version) = constraint.strip_prefix(token) {
+                return (op, version.trim_start());
+            }
+        }
+
+        (Self::Exact, constraint)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn version(version: &str) -> GemVersion {
+        GemVersion::parse(version).unwrap()
+    }
+
+    #[test]
+    fn version_ordering() {
+        let ordered = [
+            "1.0.a", "1.0.a.1", "1.0.b1", "1.0-1", "1.0.rc1", "1.0", "1.0.1", "1.1.a", "1.1",
+            "1.10", "2",
+        ];
+
+        for pair in ordered.windows(2) {
+            assert!(version(pair[0]) < version(pair[1]), "{} < {}", pair[0], pair[1]);
+        }
+
+        assert_eq!(version("1.0"), version("1"));
+        assert_eq!(version("1.0.a"), version("1.0.a.0"));
+        assert!(version("1.0.rc1").is_prerelease());
+        assert!(!version("1.0.1").is_prerelease());
+
+        for invalid in ["", "a.1", "1..2", "1.0 beta", "latest"] {
+            assert!(GemVersion::parse(invalid).is_err(), "{invalid:?} should be invalid");
+        }
+    }
+
+    #[test]
+    fn requirements() {
+        let cases = [
+            ("~> 2.2", "2.9", true),
+            ("~> 2.2", "3.0", false),
+            ("~> 2.2.0", "2.2.9", true),
+            ("~> 2.2.0", "2.3.0", false),
+            ("~> 7.0, >= 7.0.1", "7.0.0", false),
+            ("~> 7.0, >= 7.0.1", "7.0.4", true),
+            ("'>= 1.0', '< 2'", "1.9", true),
+            ("1.2.3", "1.2.3", true),
+            ("!= 1.2.3", "1.2.3", false),
+            ("> 1.0", "1.0.1", true),
+            ("<= 1.0", "1.0.1", false),
+            ("~> 18446744073709551615.0", "18446744073709551615.9", true),
+        ];
+
+        for (requirement, candidate, expected) in cases {
+            let parsed = GemReq::parse(requirement).unwrap();
+            assert_eq!(
+                parsed.matches(&version(candidate)),
+                expected,
+                "{requirement:?} matches {candidate:?}"
+            );
+        }
+    }
+}
@@ -0,0 +1,783 @@
+//! Semantic versioning, as used by npm and Cargo.
+
+use std::cmp::Ordering;
+use std::fmt::{self, Display, Formatter};
+
+use crate::version::VersionError;
+
+/// Name of the version scheme used in errors.
+const SCHEME: &str = "semver";
+
+/// Semantic version.
+///
+/// Build metadata is preserved for display purposes, but ignored for
+/// comparisons.
+#[derive(Clone, Debug)]
+pub struct SemVer {
+    pub major: u64,
+    pub minor: u64,
+    pub patch: u64,
+    pub pre: Vec<Identifier>,
+    pub build: Vec<String>,
+}
+
+impl SemVer {
+    /// Create a new release version.
+    pub fn new(major: u64, minor: u64, patch: u64) -> Self {
+        Self { major, minor, patch, pre: Vec::new(), build: Vec::new() }
+    }
+
+    /// Parse a semantic version.
+    ///
+    /// A leading `v` or `=` is accepted, since it is commonly found in
+    /// lockfiles and tags.
+    pub fn parse(version: &str) -> Result<Self, VersionError> {
+        let invalid = || VersionError::invalid_version(SCHEME, version);
+
+        let trimmed = version.trim().trim_start_matches('=').trim_start();
+        let trimmed = trimmed.strip_prefix(['v', 'V']).unwrap_or(trimmed);
+
+        let (trimmed, build) = match trimmed.split_once('+') {
+            Some((trimmed, build)) => (trimmed, parse_build(build).ok_or_else(invalid)?),
+            None => (trimmed, Vec::new()),
+        };
+
+        let (core, pre) = match trimmed.split_once('-') {
+            Some((core, pre)) => (core, parse_pre(pre).ok_or_else(invalid)?),
+            None => (trimmed, Vec::new()),
+        };
+
+        let mut parts = core.split('.').map(parse_number);
+        let (Some(Some(major)), Some(Some(minor)), Some(Some(patch)), None) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            return Err(invalid());
+        };
+
+        Ok(Self { major, minor, patch, pre, build })
+    }
+
+    /// Check if this is a pre-release version.
+    pub fn is_prerelease(&self) -> bool {
+        !self.pre.is_empty()
+    }
+
+    /// Version's `major.minor.patch` triple.
+    fn release(&self) -> (u64, u64, u64) {
+        (self.major, self.minor, self.patch)
+    }
+
+    /// Lowest possible pre-release of a version.
+    ///
+    /// This is used for exclusive upper bounds, so pre-releases of the bound
+    /// are excluded too.
+    fn lowest(major: u64, minor: u64, patch: u64) -> Self {
+        let mut version = Self::new(major, minor, patch);
+        version.pre.push(Identifier::Numeric(0));
+        version
+    }
+}
+
+impl PartialEq for SemVer {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for SemVer {}
+
+impl PartialOrd for SemVer {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for SemVer {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.release().cmp(&other.release()).then_with(|| {
+            // Pre-releases have lower precedence than the associated release.
+            match (self.pre.is_empty(), other.pre.is_empty()) {
+                (true, true) => Ordering::Equal,
+                (true, false) => Ordering::Greater,
+                (false, true) => Ordering::Less,
+                (false, false) => self.pre.cmp(&other.pre),
+            }
+        })
+    }
+}
+
+impl Display for SemVer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)?;
+
+        if !self.pre.is_empty() {
+            let pre: Vec<_> = self.pre.iter().map(Identifier::to_string).collect();
+            write!(f, "-{}", pre.join("."))?;
+        }
+
+        if !self.build.is_empty() {
+            write!(f, "+{}", self.build.join("."))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Dot-separated pre-release identifier.
+///
+/// Numeric identifiers always have lower precedence than alphanumeric ones.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Identifier {
+    Numeric(u64),
+    AlphaNumeric(String),
+}
+
+impl Identifier {
+    /// Parse a single pre-release identifier.
+    pub(crate) fn parse(identifier: &str) -> Option<Self> {
+        if identifier.is_empty()
+            || !identifier.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+        {
+            return None;
+        }
+
+        match parse_number(identifier) {
+            Some(number) => Some(Self::Numeric(number)),
+            None => Some(Self::AlphaNumeric(identifier.into())),
+        }
+    }
+}
+
+impl Display for Identifier {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Numeric(number) => write!(f, "{number}"),
+            Self::AlphaNumeric(identifier) => write!(f, "{identifier}"),
+        }
+    }
+}
+
+/// Requirement dialect of a [`SemVerReq`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Dialect {
+    /// npm's `package.json` ranges.
+    ///
+    /// Supports `||` alternatives, hyphen ranges, and treats bare versions as
+    /// exact matches.
+    Npm,
+    /// Cargo's `Cargo.toml` requirements.
+    ///
+    /// Comparators are comma-separated and bare versions are caret
+    /// requirements.
+    Cargo,
+}
+
+/// Semantic version requirement.
+#[derive(Clone, Debug)]
+pub struct SemVerReq {
+    sets: Vec<ComparatorSet>,
+    raw: String,
+}
+
+impl SemVerReq {
+    /// Parse a version requirement in the specified dialect.
+    pub fn parse(requirement: &str, dialect: Dialect) -> Result<Self, VersionError> {
+        let invalid = || VersionError::invalid_requirement(SCHEME, requirement);
+
+        let sets = match dialect {
+            Dialect::Npm => {
+                let range = npm_range(requirement).ok_or_else(invalid)?;
+                range.split("||").map(parse_npm_set).collect::<Option<_>>()
+            },
+            Dialect::Cargo => parse_cargo_set(requirement).map(|set| vec![set]),
+        };
+
+        Ok(Self { sets: sets.ok_or_else(invalid)?, raw: requirement.into() })
+    }
+
+    /// Check if a version satisfies this requirement.
+    ///
+    /// Pre-release versions only match if one of the comparators explicitly
+    /// mentions a pre-release of the same `major.minor.patch` triple.
+    pub fn matches(&self, version: &SemVer) -> bool {
+        self.sets.iter().any(|set| set.matches(version))
+    }
+}
+
+impl Display for SemVerReq {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.raw)
+    }
+}
+
+/// Comparison operator.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(crate) enum Op {
+    Exact,
+    Greater,
+    GreaterEq,
+    Less,
+    LessEq,
+}
+
+impl Op {
+    /// Split a leading comparison operator from a comparator.
+    fn split(comparator: &str) -> (Option<&str>, &str) {
+        for op in [">=", "<=", "~>", ">", "<", "=", "~", "^"] {
+            if let Some(rest) = comparator.strip_prefix(op) {
+                return (Some(op), rest.trim_start());
+            }
+        }
+        (None, comparator)
+    }
+}
+
+/// Single version comparison.
+#[derive(Clone, Debug)]
+pub(crate) struct Comparator {
+    pub op: Op,
+    pub version: SemVer,
+}
+
+impl Comparator {
+    pub fn new(op: Op, version: SemVer) -> Self {
+        Self { op, version }
+    }
+
+    /// Check if a version satisfies this comparator.
+    pub fn matches(&self, version: &SemVer) -> bool {
+        match self.op {
+            Op::Exact => version == &self.version,
+            Op::Greater => version > &self.version,
+            Op::GreaterEq => version >= &self.version,
+            Op::Less => version < &self.version,
+            Op::LessEq => version <= &self.version,
+        }
+    }
+
+    /// Comparator which does not match any version.
+    fn none() -> Self {
+        Self::new(Op::Less, SemVer::lowest(0, 0, 0))
+    }
+}
+
+/// Comparators which must all match.
+#[derive(Clone, Debug, Default)]
+struct ComparatorSet {
+    comparators: Vec<Comparator>,
+    /// Release triples for which pre-releases are allowed to match.
+    prerelease_triples: Vec<(u64, u64, u64)>,
+}
+
+impl ComparatorSet {
+    fn matches(&self, version: &SemVer) -> bool {
+        if version.is_prerelease() && !self.prerelease_triples.contains(&version.release()) {
+            return false;
+        }
+
+        self.comparators.iter().all(|comparator| comparator.matches(version))
+    }
+
+    /// Add a comparator with an optional operator to this set.
+    ///
+    /// The `bare` function is used to desugar comparators without operator.
+    fn push(
+        &mut self,
+        op: Option<&str>,
+        version: &str,
+        bare: fn(&Partial) -> Vec<Comparator>,
+    ) -> Option<()> {
+        let partial = Partial::parse(version)?;
+
+        if let Some(triple) = partial.prerelease_triple() {
+            self.prerelease_triples.push(triple);
+        }
+
+        let comparators = match op {
+            None => bare(&partial),
+            Some("=") => partial.exact(),
+            Some("~" | "~>") => partial.tilde(),
+            Some("^") => partial.caret(),
+            Some(">") => partial.greater(),
+            Some(">=") => partial.greater_eq(),
+            Some("<") => partial.less(),
+            Some("<=") => partial.less_eq(),
+            Some(_) => return None,
+        };
+        self.comparators.extend(comparators);
+
+        Some(())
+    }
+}
+
+/// Version with optional components.
+///
+/// Missing and wildcard (`x`, `X`, `*`) components are both represented as
+/// `None`.
+#[derive(Clone, Debug)]
+pub(crate) struct Partial {
+    major: Option<u64>,
+    minor: Option<u64>,
+    patch: Option<u64>,
+    pre: Vec<Identifier>,
+    /// Whether the version contained an explicit wildcard.
+    wildcard: bool,
+}
+
+impl Partial {
+    pub fn parse(version: &str) -> Option<Self> {
+        let version = version.trim();
+        let version = version.strip_prefix(['v', 'V']).unwrap_or(version);
+        let version = version.split_once('+').map_or(version, |(version, _)| version);
+
+        if version.is_empty() {
+            return Some(Self::any());
+        }
+
+        let (core, pre) = match version.split_once('-') {
+            Some((core, pre)) => (core, parse_pre(pre)?),
+            None => (version, Vec::new()),
+        };
+
+        let parts: Vec<_> = core.split('.').collect();
+        if parts.len() > 3 {
+            return None;
+        }
+
+        let mut components = [None; 3];
+        let mut wildcard = false;
+        for (component, part) in components.iter_mut().zip(parts) {
+            if matches!(part, "x" | "X" | "*") {
+                wildcard = true;
+            } else if wildcard {
+                // Components after a wildcard must be wildcards too.
+                return None;
+            } else {
+                *component = Some(parse_number(part)?);
+            }
+        }
+
+        // Pre-release identifiers are only valid on complete versions.
+        let [major, minor, patch] = components;
+        if patch.is_none() && !pre.is_empty() {
+            return None;
+        }
+
+        Some(Self { major, minor, patch, pre, wildcard })
+    }
+
+    /// Partial version matching everything.
+    fn any() -> Self {
+        Self { major: None, minor: None, patch: None, pre: Vec::new(), wildcard: true }
+    }
+
+    /// Triple for which this version allows pre-releases.
+    fn prerelease_triple(&self) -> Option<(u64, u64, u64)> {
+        match (self.major, self.minor, self.patch) {
+            (Some(major), Some(minor), Some(patch)) if !self.pre.is_empty() => {
+                Some((major, minor, patch))
+            },
+            _ => None,
+        }
+    }
+
+    /// Lowest version described by this partial version.
+    fn lower(&self) -> SemVer {
+        let mut version =
+            SemVer::new(self.major.unwrap_or(0), self.minor.unwrap_or(0), self.patch.unwrap_or(0));
+        if self.patch.is_some() {
+            version.pre = self.pre.clone();
+        }
+        version
+    }
+
+    /// Exclusive bound above all versions described by this partial version.
+    ///
+    /// Returns `None` if there is no upper bound, either because the version
+    /// is a wildcard or because the bound would overflow.
+    fn upper(&self) -> Option<SemVer> {
+        match (self.major, self.minor, self.patch) {
+            (None, ..) => None,
+            (Some(major), None, _) => Some(SemVer::lowest(bump(major)?, 0, 0)),
+            (Some(major), Some(minor), None) => Some(SemVer::lowest(major, bump(minor)?, 0)),
+            (Some(major), Some(minor), Some(patch)) => {
+                Some(SemVer::lowest(major, minor, bump(patch)?))
+            },
+        }
+    }
+
+    /// Inclusive range starting at `lower` with an optional exclusive `upper`.
+    fn range(&self, upper: Option<SemVer>) -> Vec<Comparator> {
+        let mut comparators = vec![Comparator::new(Op::GreaterEq, self.lower())];
+        comparators.extend(upper.map(|upper| Comparator::new(Op::Less, upper)));
+        comparators
+    }
+
+    /// `=1.2.3`, `1.2.x`.
+    pub fn exact(&self) -> Vec<Comparator> {
+        match (self.major, self.patch) {
+            (None, _) => Vec::new(),
+            (Some(_), Some(_)) => vec![Comparator::new(Op::Exact, self.lower())],
+            (Some(_), None) => self.range(self.upper()),
+        }
+    }
+
+    /// `~1.2.3`: Allow patch-level changes.
+    pub fn tilde(&self) -> Vec<Comparator> {
+        let upper = match (self.major, self.minor) {
+            (None, _) => return Vec::new(),
+            (Some(major), None) => bump(major).map(|major| SemVer::lowest(major, 0, 0)),
+            (Some(major), Some(minor)) => bump(minor).map(|minor| SemVer::lowest(major, minor, 0)),
+        };
+        self.range(upper)
+    }
+
+    /// `^1.2.3`: Allow changes that do not modify the left-most non-zero
+    /// component.
+    pub fn caret(&self) -> Vec<Comparator> {
+        let upper = match (self.major, self.minor, self.patch) {
+            (None, ..) => return Vec::new(),
+            (Some(major), None, _) => bump(major).map(|major| SemVer::lowest(major, 0, 0)),
+            (Some(0), Some(minor), None) => bump(minor).map(|minor| SemVer::lowest(0, minor, 0)),
+            (Some(0), Some(0), Some(patch)) => bump(patch).map(|patch| SemVer::lowest(0, 0, patch)),
+            (Some(0), Some(minor), Some(_)) => bump(minor).map(|minor| SemVer::lowest(0, minor, 0)),
+            (Some(major), ..) => bump(major).map(|major| SemVer::lowest(major, 0, 0)),
+        };
+        self.range(upper)
+    }
+
+    /// `>1.2.3`, `>1.2`.
+    pub fn greater(&self) -> Vec<Comparator> {
+        match (self.major, self.patch) {
+            (None, _) => vec![Comparator::none()],
+            (Some(_), Some(_)) => vec![Comparator::new(Op::Greater, self.lower())],
+            (Some(_), None) => match self.upper() {
+                Some(mut upper) => {
+                    upper.pre.clear();
+                    vec![Comparator::new(Op::GreaterEq, upper)]
+                },
+                // Nothing is above the highest representable version.
+                None => vec![Comparator::none()],
+            },
+        }
+    }
+
+    /// `>=1.2.3`, `>=1.2`.
+    pub fn greater_eq(&self) -> Vec<Comparator> {
+        match self.major {
+            None => Vec::new(),
+            Some(_) => vec![Comparator::new(Op::GreaterEq, self.lower())],
+        }
+    }
+
+    /// `<1.2.3`, `<1.2`.
+    pub fn less(&self) -> Vec<Comparator> {
+        match (self.major, self.patch) {
+            (None, _) => vec![Comparator::none()],
+            (Some(_), Some(_)) => vec![Comparator::new(Op::Less, self.lower())],
+            (Some(major), None) => {
+                let bound = SemVer::lowest(major, self.minor.unwrap_or(0), 0);
+                vec![Comparator::new(Op::Less, bound)]
+            },
+        }
+    }
+
+    /// `<=1.2.3`, `<=1.2`.
+    pub fn less_eq(&self) -> Vec<Comparator> {
+        match (self.major, self.patch) {
+            (None, _) => Vec::new(),
+            (Some(_), Some(_)) => vec![Comparator::new(Op::LessEq, self.lower())],
+            (Some(_), None) => {
+                self.upper().map(|upper| Comparator::new(Op::Less, upper)).into_iter().collect()
+            },
+        }
+    }
+}
+
+/// Increment a version component, returning `None` on overflow.
+///
+/// Overflowing bounds are treated as unbounded.
+fn bump(component: u64) -> Option<u64> {
+    component.checked_add(1)
+}
+
+/// Extract the range from an npm dependency specifier.
+///
+/// Returns `None` for specifiers which do not resolve through the registry,
+/// like tags, git URLs or local paths.
+fn npm_range(specifier: &str) -> Option<&str> {
+    let specifier = specifier.trim();
+
+    // Aliases (`npm:name@range`) use the range of the aliased package.
+    if let Some(alias) = specifier.strip_prefix("npm:") {
+        let (_, range) = alias.rsplit_once('@').filter(|(name, _)| !name.is_empty())?;
+        return Some(range);
+    }
+
+    // Workspace protocol used by pnpm and Yarn.
+    if let Some(range) = specifier.strip_prefix("workspace:") {
+        return Some(if matches!(range, "^" | "~") { "*" } else { range });
+    }
+
+    // Reject anything that looks like a URL, path or distribution tag.
+    let is_range = specifier.chars().all(|c| {
+        c.is_ascii_alphanumeric()
+            || matches!(c, ' ' | '.' | '-' | '+' | '*' | '<' | '>' | '=' | '~' | '^' | '|')
+    });
+    let starts_like_version = specifier.is_empty()
+        || specifier.starts_with(|c: char| {
+            c.is_ascii_digit()
+                || matches!(c, 'v' | 'V' | 'x' | 'X' | '*' | '<' | '>' | '=' | '~' | '^')
+        });
+    (is_range && starts_like_version).then_some(specifier)
+}
+
+/// Parse a single npm comparator set.
+fn parse_npm_set(set: &str) -> Option<ComparatorSet> {
+    let tokens = tokenize(set);
+    let mut comparators = ComparatorSet::default();
+
+    let mut i = 0;
+    while i < tokens.len() {
+        // Handle hyphen ranges (`1.2.3 - 2.3.4`).
+        if tokens.get(i + 1).map(String::as_str) == Some("-") {
+            let lower = Partial::parse(&tokens[i])?;
+            let upper = Partial::parse(tokens.get(i + 2)?)?;
+
+            comparators.prerelease_triples.extend(lower.prerelease_triple());
+            comparators.prerelease_triples.extend(upper.prerelease_triple());
+
+            comparators.comparators.extend(lower.greater_eq());
+            comparators.comparators.extend(upper.less_eq());
+
+            i += 3;
+            continue;
+        }
+
+        let (op, version) = Op::split(&tokens[i]);
+        comparators.push(op, version, Partial::exact)?;
+
+        i += 1;
+    }
+
+    Some(comparators)
+}
+
+/// Parse a comma-separated Cargo requirement.
+fn parse_cargo_set(requirement: &str) -> Option<ComparatorSet> {
+    let mut comparators = ComparatorSet::default();
+
+    for comparator in requirement.split(',') {
+        let (op, version) = Op::split(comparator.trim());
+        if version.is_empty() || version.contains(char::is_whitespace) || op == Some("~>") {
+            return None;
+        }
+
+        // Bare versions are caret requirements, unless they contain wildcards.
+        comparators.push(op, version, |partial| match partial.wildcard {
+            true => partial.exact(),
+            false => partial.caret(),
+        })?;
+    }
+
+    Some(comparators)
+}
+
+/// Split a comparator set into its whitespace-separated tokens.
+///
+/// Operators separated from their version by whitespace (`>= 1.2.3`) are
+/// joined back together.
+fn tokenize(set: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut words = set.split_whitespace().peekable();
+
+    while let Some(word) = words.next() {
+        let is_op = matches!(word, ">=" | "<=" | ">" | "<" | "=" | "~" | "~>" | "^");
+        match words.next_if(|_| is_op) {
+            Some(next) => tokens.push(format!("{word}{next}")),
+            None => tokens.push(word.into()),
+        }
+    }
+
+    tokens
+}
+
+/// Parse a dot-separated list of pre-release identifiers.
+pub(crate) fn parse_pre(pre: &str) -> Option<Vec<Identifier>> {
+    pre.split('.').map(Identifier::parse).collect()
+}
+
+/// Parse dot-separated build metadata.
+fn parse_build(build: &str) -> Option<Vec<String>> {
+    build
+        .split('.')
+        .map(|identifier| {
+            let valid = !identifier.is_empty()
+                && identifier.chars().all(|c| c.is_ascii_alphanumeric() || c == '-');
+            valid.then(|| identifier.into())
+        })
+        .collect()
+}
+
+/// Parse a purely numeric version component.
+pub(crate) fn parse_number(number: &str) -> Option<u64> {
+    if number.is_empty() || !number.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    number.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn version(version: &str) -> SemVer {
+        SemVer::parse(version).unwrap()
+    }
+
+    fn npm(requirement: &str) -> SemVerReq {
+        SemVerReq::parse(requirement, Dialect::Npm).unwrap()
+    }
+
+    fn cargo(requirement: &str) -> SemVerReq {
+        SemVerReq::parse(requirement, Dialect::Cargo).unwrap()
+    }
+
+    #[test]
+    fn parse_versions() {
+        let parsed = version("v1.2.3-beta.1+build.5");
+        assert_eq!((parsed.major, parsed.minor, parsed.patch), (1, 2, 3));
+        assert_eq!(parsed.pre, [Identifier::AlphaNumeric("beta".into()), Identifier::Numeric(1)]);
+        assert_eq!(parsed.build, ["build", "5"]);
+        assert_eq!(parsed.to_string(), "1.2.3-beta.1+build.5");
+
+        for invalid in ["1.2", "1.2.3.4", "1.2.x", "latest", "1.2.3-", "1.2.3-a..b", ""] {
+            assert!(SemVer::parse(invalid).is_err(), "{invalid:?} should be invalid");
+        }
+    }
+
+    #[test]
+    fn version_ordering() {
+        let ordered = [
+            "1.0.0-alpha",
+            "1.0.0-alpha.1",
+            "1.0.0-alpha.beta",
+            "1.0.0-beta",
+            "1.0.0-beta.2",
+            "1.0.0-beta.11",
+            "1.0.0-rc.1",
+            "1.0.0",
+            "1.0.1",
+            "1.2.0",
+            "1.10.0",
+            "2.0.0",
+        ];
+
+        for pair in ordered.windows(2) {
+            assert!(version(pair[0]) < version(pair[1]), "{} < {}", pair[0], pair[1]);
+        }
+
+        assert_eq!(version("1.0.0+a"), version("1.0.0+b"));
+    }
+
+    #[test]
+    fn npm_ranges() {
+        let cases = [
+            ("^1.2.3", "1.9.9", true),
+            ("^1.2.3", "2.0.0", false),
+            ("^1.2.3", "1.2.2", false),
+            ("^0.2.3", "0.2.9", true),
+            ("^0.2.3", "0.3.0", false),
+            ("^0.0.3", "0.0.4", false),
+            ("~1.2.3", "1.2.9", true),
+            ("~1.2.3", "1.3.0", false),
+            ("~1", "1.9.0", true),
+            ("1.2.x", "1.2.7", true),
+            ("1.2.x", "1.3.0", false),
+            ("1.2.3", "1.2.3", true),
+            ("1.2.3", "1.2.4", false),
+            ("*", "3.0.0", true),
+            ("", "3.0.0", true),
+            (">=1.2.3 <2", "1.5.0", true),
+            (">= 1.2.3 < 2", "2.0.0", false),
+            (">1.2", "1.2.9", false),
+            (">1.2", "1.3.0", true),
+            ("<=1.2", "1.2.9", true),
+            ("<1.2", "1.2.0", false),
+            ("1.2.3 - 2.3", "2.3.9", true),
+            ("1.2.3 - 2.3", "2.4.0", false),
+            ("1 || 3", "3.1.0", true),
+            ("1 || 3", "2.1.0", false),
+            ("^1.2.3", "1.5.0-beta", false),
+            ("^1.2.3-beta", "1.2.3-rc.1", true),
+            ("^1.2.3-beta", "1.2.4-rc.1", false),
+            ("npm:other@^2.0.0", "2.1.0", true),
+            ("workspace:^", "0.1.0", true),
+        ];
+
+        for (requirement, candidate, expected) in cases {
+            assert_eq!(
+                npm(requirement).matches(&version(candidate)),
+                expected,
+                "{requirement:?} matches {candidate:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn overflowing_bounds() {
+        const MAX: u64 = u64::MAX;
+
+        let cases = [
+            (format!("^{MAX}"), format!("{MAX}.1.0"), true),
+            (format!("~1.{MAX}"), format!("1.{MAX}.3"), true),
+            (format!("{MAX}.x"), format!("{MAX}.{MAX}.{MAX}"), true),
+            (format!("<={MAX}"), format!("{MAX}.2.0"), true),
+            (format!(">{MAX}.x"), format!("{MAX}.{MAX}.{MAX}"), false),
+            (format!("0.0.{MAX} - 1"), "1.5.0".into(), true),
+        ];
+
+        for (requirement, candidate, expected) in cases {
+            assert_eq!(
+                npm(&requirement).matches(&version(&candidate)),
+                expected,
+                "{requirement:?} matches {candidate:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn npm_non_registry_specifiers() {
+        for specifier in ["latest", "file:../local", "git+https://github.com/a/b.git", "user/repo"]
+        {
+            assert!(SemVerReq::parse(specifier, Dialect::Npm).is_err(), "{specifier:?}");
+        }
+    }
+
+    #[test]
+    fn cargo_requirements() {
+        let cases = [
+            ("1.2.3", "1.9.0", true),
+            ("1.2.3", "2.0.0", false),
+            ("0.2", "0.2.9", true),
+            ("0.2", "0.3.0", false),
+            ("=1.2.3", "1.2.4", false),
+            ("~1.2", "1.2.9", true),
+            ("1.*", "1.9.9", true),
+            ("1.*", "2.0.0", false),
+            (">=1.2, <1.5", "1.4.9", true),
+            (">=1.2, <1.5", "1.5.0", false),
+            ("*", "0.0.1", true),
+        ];
+
+        for (requirement, candidate, expected) in cases {
+            assert_eq!(
+                cargo(requirement).matches(&version(candidate)),
+                expected,
+                "{requirement:?} matches {candidate:?}"
+            );
+        }
+
+        assert!(SemVerReq::parse("1 || 2", Dialect::Cargo).is_err());
+    }
+}
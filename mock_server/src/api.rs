@@ -0,0 +1,680 @@
+//! Phylum API endpoints.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::routing::{delete, get, post};
+use axum::{Json, Router};
+use chrono::Utc;
+use phylum_api::types::{
+    AddOrgUserRequest, AnalysisPackageDescriptor, ApiOrgGroup, GetProjectResponse, GroupRole,
+    HistoryJob, ListUserGroupsResponse, Org, OrgGroupsResponse, OrgMember, OrgMembersResponse,
+    OrgsResponse, PackageSpecifier, Paginated, PingResponse, PolicyEvaluationRequest,
+    PolicyEvaluationResponse, PolicyEvaluationResponseRaw, ProjectListEntry, SubmitPackageRequest,
+    UpdateProjectRequest, UserGroup, UserToken,
+};
+use phylum_types::types::common::{JobId, ProjectId};
+use phylum_types::types::package::PackageDescriptor;
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::policy::{self, Dependency};
+use crate::state::{ApiError, AppState, Data, Group, Job, Project};
+
+type Result<T> = std::result::Result<Json<T>, ApiError>;
+
+/// Default page size of the project list.
+const DEFAULT_PAGE_LIMIT: usize = 100;
+
+/// Routes which do not require authentication.
+pub(crate) fn public_routes() -> Router<Arc<AppState>> {
+    Router::new().route("/api/v0/health", get(health))
+}
+
+/// Routes which require authentication.
+pub(crate) fn protected_routes() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/api/v0/projects", get(list_projects))
+        .route("/api/v0/data/projects", post(create_project))
+        .route(
+            "/api/v0/data/projects/:project_id",
+            get(get_project).put(update_project).delete(delete_project),
+        )
+        .route("/api/v0/data/projects/:project_id/history", get(project_history))
+        .route("/api/v0/groups/:group/projects/:project_id/history", get(group_project_history))
+        .route("/api/v0/groups", get(list_groups).post(create_group))
+        .route("/api/v0/groups/:group", delete(delete_group))
+        .route("/api/v0/groups/:group/members", get(group_members))
+        .route("/api/v0/groups/:group/members/:email", post(group_add).delete(group_remove))
+        .route("/api/v0/organizations", get(list_orgs))
+        .route("/api/v0/organizations/:org/members", get(org_members).post(org_member_add))
+        .route("/api/v0/organizations/:org/members/:email", delete(org_member_remove))
+        .route("/api/v0/organizations/:org/groups", get(org_groups).post(org_group_create))
+        .route("/api/v0/organizations/:org/groups/:group", delete(org_group_delete))
+        .route("/api/v0/data/jobs", post(submit_job))
+        .route("/api/v0/data/jobs/", get(list_jobs))
+        .route("/api/v0/data/jobs/:job_id/policy/evaluate", post(evaluate_job))
+        .route("/api/v0/data/jobs/:job_id/policy/evaluate/raw", post(evaluate_job_raw))
+        .route("/api/v0/data/packages/check", post(check_packages))
+        .route("/api/v0/data/packages/check/raw", post(check_packages_raw))
+        .route("/api/v0/data/packages/submit", post(submit_package))
+        .route("/locksmith/v1/tokens", get(list_tokens))
+        .route("/locksmith/v1/revoke", post(revoke_token))
+        .route("/reachability/vulnerabilities", post(vulnerabilities))
+}
+
+async fn health() -> Json<PingResponse> {
+    Json(PingResponse { response: "alive".into() })
+}
+
+/// Parse a project ID path parameter.
+fn parse_project_id(project_id: &str) -> std::result::Result<ProjectId, ApiError> {
+    project_id
+        .parse()
+        .map_err(|_| ApiError::not_found(format!("Project {project_id} does not exist")))
+}
+
+/// Check that the owner of a project exists.
+fn check_project_owner(
+    data: &mut Data,
+    org: Option<&str>,
+    group: Option<&str>,
+) -> std::result::Result<(), ApiError> {
+    match (org, group) {
+        (Some(org), group) => {
+            let org = data.org_mut(org)?;
+            if let Some(group) = group.filter(|group| !org.groups.iter().any(|g| g == group)) {
+                return Err(ApiError::not_found(format!("Group {group:?} does not exist")));
+            }
+        },
+        (None, Some(group)) => {
+            data.group_mut(group)?;
+        },
+        (None, None) => (),
+    }
+
+    Ok(())
+}
+
+/// Check that no other project with the same name and owner exists.
+fn check_project_conflict(
+    data: &Data,
+    id: Option<ProjectId>,
+    request: &UpdateProjectRequest,
+) -> std::result::Result<(), ApiError> {
+    let conflict = data.projects.iter().any(|project| {
+        Some(project.id) != id
+            && project.name == request.name
+            && project.org == request.organization_name
+            && project.group == request.group_name
+    });
+
+    if conflict {
+        Err(ApiError::conflict(format!("Project {:?} already exists", request.name)))
+    } else {
+        Ok(())
+    }
+}
+
+async fn list_projects(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<HashMap<String, String>>,
+) -> Result<Paginated<ProjectListEntry>> {
+    let org = query.get("filter.organization");
+    let group = query.get("filter.group");
+    let name = query.get("filter.name");
+    let limit = query
+        .get("paginate.limit")
+        .and_then(|limit| limit.parse().ok())
+        .unwrap_or(DEFAULT_PAGE_LIMIT);
+
+    let data = state.data();
+    let mut projects: Vec<_> = data
+        .projects
+        .iter()
+        .filter(|project| org.map_or(true, |org| project.org.as_ref() == Some(org)))
+        .filter(|project| group.map_or(true, |group| project.group.as_ref() == Some(group)))
+        .filter(|project| name.map_or(true, |name| project.name.contains(name.as_str())))
+        .collect();
+
+    // Skip everything up to and including the cursor.
+    if let Some(cursor) = query.get("paginate.cursor") {
+        let position = projects.iter().position(|project| &project.id.to_string() == cursor);
+        projects.drain(..position.map_or(0, |position| position + 1));
+    }
+
+    let has_more = projects.len() > limit;
+    let values = projects
+        .into_iter()
+        .take(limit)
+        .map(|project| ProjectListEntry {
+            id: project.id,
+            created_at: project.created_at,
+            updated_at: project.updated_at,
+            name: project.name.clone(),
+            ecosystems: project.ecosystems.clone(),
+            organization_name: project.org.clone(),
+            group_name: project.group.clone(),
+        })
+        .collect();
+
+    Ok(Json(Paginated { values, has_more }))
+}
+
+async fn create_project(
+    State(state): State<Arc<AppState>>,
+    Json(request): Json<UpdateProjectRequest>,
+) -> Result<Value> {
+    let mut data = state.data();
+    check_project_owner(
+        &mut data,
+        request.organization_name.as_deref(),
+        request.group_name.as_deref(),
+    )?;
+    check_project_conflict(&data, None, &request)?;
+
+    let now = Utc::now();
+    let id = ProjectId::new_v4();
+    data.projects.push(Project {
+        id,
+        name: request.name,
+        org: request.organization_name,
+        group: request.group_name,
+        repository_url: request.repository_url,
+        default_label: request.default_label,
+        ecosystems: Vec::new(),
+        created_at: now,
+        updated_at: now,
+    });
+
+    Ok(Json(json!({ "id": id })))
+}
+
+async fn update_project(
+    State(state): State<Arc<AppState>>,
+    Path(project_id): Path<String>,
+    Json(request): Json<UpdateProjectRequest>,
+) -> Result<Value> {
+    let id = parse_project_id(&project_id)?;
+
+    let mut data = state.data();
+    check_project_owner(
+        &mut data,
+        request.organization_name.as_deref(),
+        request.group_name.as_deref(),
+    )?;
+    check_project_conflict(&data, Some(id), &request)?;
+
+    let project = data.project_mut(id)?;
+    project.name = request.name;
+    project.org = request.organization_name;
+    project.group = request.group_name;
+    project.repository_url = request.repository_url;
+    project.default_label = request.default_label;
+    project.updated_at = Utc::now();
+
+    Ok(Json(json!({ "id": id })))
+}
+
+async fn get_project(
+    State(state): State<Arc<AppState>>,
+    Path(project_id): Path<String>,
+) -> Result<GetProjectResponse> {
+    let id = parse_project_id(&project_id)?;
+
+    let mut data = state.data();
+    let latest_job = data.jobs.iter().rev().find(|job| job.project == id);
+    let latest_job_id = latest_job.map(|job| job.id);
+    let latest_job_created_at = latest_job.map(|job| job.created_at);
+    let label = latest_job.map(|job| job.label.clone());
+
+    let project = data.project_mut(id)?;
+    Ok(Json(GetProjectResponse {
+        id,
+        name: project.name.clone(),
+        registries: project.ecosystems.iter().map(ToString::to_string).collect(),
+        created_at: project.created_at,
+        updated_at: Some(project.updated_at),
+        latest_job_created_at,
+        latest_job_id,
+        label,
+        default_label: project.default_label.clone(),
+        repository_url: project.repository_url.clone(),
+    }))
+}
+
+async fn delete_project(
+    State(state): State<Arc<AppState>>,
+    Path(project_id): Path<String>,
+) -> Result<Value> {
+    let id = parse_project_id(&project_id)?;
+
+    let mut data = state.data();
+    data.project_mut(id)?;
+    data.projects.retain(|project| project.id != id);
+    data.jobs.retain(|job| job.project != id);
+
+    Ok(Json(json!({})))
+}
+
+async fn project_history(
+    State(state): State<Arc<AppState>>,
+    Path(project_id): Path<String>,
+) -> Result<Vec<HistoryJob>> {
+    let id = parse_project_id(&project_id)?;
+
+    let mut data = state.data();
+    data.project_mut(id)?;
+
+    let history = data
+        .jobs
+        .iter()
+        .rev()
+        .filter(|job| job.project == id)
+        .map(|job| HistoryJob {
+            id: job.id.to_string(),
+            created: job.created_at,
+            label: Some(job.label.clone()),
+        })
+        .collect();
+
+    Ok(Json(history))
+}
+
+async fn group_project_history(
+    state: State<Arc<AppState>>,
+    Path((_group, project_id)): Path<(String, String)>,
+) -> Result<Vec<HistoryJob>> {
+    project_history(state, Path(project_id)).await
+}
+
+/// Request body for `POST /groups`.
+#[derive(Deserialize)]
+struct CreateGroupRequest {
+    group_name: String,
+}
+
+async fn list_groups(State(state): State<Arc<AppState>>) -> Json<ListUserGroupsResponse> {
+    let email = &state.user.email;
+    let groups = state
+        .data()
+        .groups
+        .iter()
+        .filter(|group| group.members.contains(email))
+        .map(|group| UserGroup {
+            created_at: group.created_at,
+            last_modified: group.created_at,
+            group_id: None,
+            group_name: group.name.clone(),
+            role: if &group.owner == email { GroupRole::Admin } else { GroupRole::Member },
+        })
+        .collect();
+
+    Json(ListUserGroupsResponse { groups })
+}
+
+async fn create_group(
+    State(state): State<Arc<AppState>>,
+    Json(request): Json<CreateGroupRequest>,
+) -> Result<Value> {
+    let mut data = state.data();
+    if data.groups.iter().any(|group| group.name == request.group_name) {
+        return Err(ApiError::conflict(format!("Group {:?} already exists", request.group_name)));
+    }
+
+    let email = state.user.email.clone();
+    let created_at = Utc::now();
+    data.groups.push(Group {
+        name: request.group_name.clone(),
+        owner: email.clone(),
+        members: vec![email.clone()],
+        created_at,
+    });
+
+    Ok(Json(json!({
+        "group_name": request.group_name,
+        "owner_email": email,
+        "created_at": created_at,
+        "last_modified": created_at,
+    })))
+}
+
+async fn delete_group(
+    State(state): State<Arc<AppState>>,
+    Path(group): Path<String>,
+) -> Result<Value> {
+    let mut data = state.data();
+    data.group_mut(&group)?;
+    data.groups.retain(|g| g.name != group);
+    data.projects.retain(|project| project.org.is_some() || project.group.as_ref() != Some(&group));
+
+    Ok(Json(json!({})))
+}
+
+async fn group_members(
+    State(state): State<Arc<AppState>>,
+    Path(group): Path<String>,
+) -> Result<Value> {
+    let mut data = state.data();
+    let group = data.group_mut(&group)?;
+
+    let members: Vec<_> = group
+        .members
+        .iter()
+        .map(|email| {
+            // Only the authenticated user's name is known.
+            let name = if email == &state.user.email { state.user.name.as_str() } else { "" };
+            let (first_name, last_name) = name.split_once(' ').unwrap_or((name, ""));
+            let role = if email == &group.owner { GroupRole::Admin } else { GroupRole::Member };
+
+            json!({
+                "user_email": email,
+                "first_name": first_name,
+                "last_name": last_name,
+                "role": role,
+            })
+        })
+        .collect();
+
+    Ok(Json(json!({ "members": members })))
+}
+
+async fn group_add(
+    State(state): State<Arc<AppState>>,
+    Path((group, email)): Path<(String, String)>,
+) -> Result<Value> {
+    let mut data = state.data();
+    let group = data.group_mut(&group)?;
+    if !group.members.contains(&email) {
+        group.members.push(email);
+    }
+
+    Ok(Json(json!({})))
+}
+
+async fn group_remove(
+    State(state): State<Arc<AppState>>,
+    Path((group, email)): Path<(String, String)>,
+) -> Result<Value> {
+    let mut data = state.data();
+    let group = data.group_mut(&group)?;
+    if !group.members.contains(&email) {
+        return Err(ApiError::not_found(format!("User {email:?} is not a group member")));
+    }
+    group.members.retain(|member| member != &email);
+
+    Ok(Json(json!({})))
+}
+
+async fn list_orgs(State(state): State<Arc<AppState>>) -> Json<OrgsResponse> {
+    let email = &state.user.email;
+    let organizations = state
+        .data()
+        .orgs
+        .iter()
+        .filter(|org| org.members.iter().any(|member| &member.email == email))
+        .map(|org| Org { name: org.name.clone() })
+        .collect();
+
+    Json(OrgsResponse { organizations })
+}
+
+async fn org_members(
+    State(state): State<Arc<AppState>>,
+    Path(org): Path<String>,
+) -> Result<OrgMembersResponse> {
+    let mut data = state.data();
+    let members = data.org_mut(&org)?.members.clone();
+    Ok(Json(OrgMembersResponse { members }))
+}
+
+async fn org_member_add(
+    State(state): State<Arc<AppState>>,
+    Path(org): Path<String>,
+    Json(request): Json<AddOrgUserRequest>,
+) -> Result<Value> {
+    let mut data = state.data();
+    let org = data.org_mut(&org)?;
+    if !org.members.iter().any(|member| member.email == request.email) {
+        org.members.push(OrgMember { email: request.email, role: GroupRole::Member });
+    }
+
+    Ok(Json(json!({})))
+}
+
+async fn org_member_remove(
+    State(state): State<Arc<AppState>>,
+    Path((org, email)): Path<(String, String)>,
+) -> Result<Value> {
+    let mut data = state.data();
+    let org = data.org_mut(&org)?;
+    if !org.members.iter().any(|member| member.email == email) {
+        return Err(ApiError::not_found(format!("User {email:?} is not an organization member")));
+    }
+    org.members.retain(|member| member.email != email);
+
+    Ok(Json(json!({})))
+}
+
+async fn org_groups(
+    State(state): State<Arc<AppState>>,
+    Path(org): Path<String>,
+) -> Result<OrgGroupsResponse> {
+    let mut data = state.data();
+    let groups = data.org_mut(&org)?.groups.iter().map(|name| ApiOrgGroup { name: name.clone() });
+    Ok(Json(OrgGroupsResponse { groups: groups.collect() }))
+}
+
+async fn org_group_create(
+    State(state): State<Arc<AppState>>,
+    Path(org): Path<String>,
+    Json(request): Json<ApiOrgGroup>,
+) -> Result<Value> {
+    let mut data = state.data();
+    let org = data.org_mut(&org)?;
+    if org.groups.contains(&request.name) {
+        return Err(ApiError::conflict(format!("Group {:?} already exists", request.name)));
+    }
+    org.groups.push(request.name);
+
+    Ok(Json(json!({})))
+}
+
+async fn org_group_delete(
+    State(state): State<Arc<AppState>>,
+    Path((org, group)): Path<(String, String)>,
+) -> Result<Value> {
+    let mut data = state.data();
+    let org_groups = &mut data.org_mut(&org)?.groups;
+    if !org_groups.contains(&group) {
+        return Err(ApiError::not_found(format!("Group {group:?} does not exist")));
+    }
+    org_groups.retain(|g| g != &group);
+    data.projects.retain(|project| {
+        project.org.as_ref() != Some(&org) || project.group.as_ref() != Some(&group)
+    });
+
+    Ok(Json(json!({})))
+}
+
+async fn submit_job(
+    State(state): State<Arc<AppState>>,
+    Json(request): Json<SubmitPackageRequest>,
+) -> Result<Value> {
+    let mut data = state.data();
+    let project = data.project_mut(request.project)?;
+
+    // Track the project's ecosystems.
+    for package in &request.packages {
+        if let AnalysisPackageDescriptor::PackageDescriptor(package) = package {
+            let package_type = package.package_descriptor.package_type;
+            if !project.ecosystems.contains(&package_type) {
+                project.ecosystems.push(package_type);
+            }
+        }
+    }
+    project.updated_at = Utc::now();
+
+    let id = JobId::new_v4();
+    data.jobs.push(Job {
+        id,
+        project: request.project,
+        label: request.label,
+        packages: request.packages,
+        created_at: Utc::now(),
+    });
+
+    Ok(Json(json!({ "job_id": id })))
+}
+
+/// Query parameters for `GET /data/jobs/`.
+#[derive(Deserialize)]
+struct ListJobsQuery {
+    limit: Option<usize>,
+}
+
+async fn list_jobs(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<ListJobsQuery>,
+) -> Json<Value> {
+    let data = state.data();
+    let limit = query.limit.unwrap_or(usize::MAX);
+
+    let jobs: Vec<_> = data
+        .jobs
+        .iter()
+        .rev()
+        .take(limit)
+        .map(|job| {
+            let project = data.projects.iter().find(|project| project.id == job.project);
+            let dependencies: Vec<_> = job.packages.iter().map(Dependency::from).collect();
+            let evaluation = state.policy.evaluate(&dependencies, &[], None);
+
+            let packages: Vec<_> = job
+                .packages
+                .iter()
+                .filter_map(|package| match package {
+                    AnalysisPackageDescriptor::PackageDescriptor(package) => {
+                        Some(&package.package_descriptor)
+                    },
+                    AnalysisPackageDescriptor::Purl(_) => None,
+                })
+                .collect();
+            let ecosystems: Vec<_> = project
+                .map(|project| project.ecosystems.iter().map(ToString::to_string).collect())
+                .unwrap_or_default();
+
+            json!({
+                "job_id": job.id,
+                "project": project.map(|project| project.name.as_str()).unwrap_or_default(),
+                "label": job.label,
+                "date": job.created_at.to_rfc3339(),
+                "num_dependencies": job.packages.len(),
+                "num_incomplete": evaluation.incomplete_packages_count,
+                "score": 1.0,
+                "pass": !evaluation.is_failure,
+                "msg": policy::summarize(&evaluation).output,
+                "ecosystem": ecosystems.first().cloned().unwrap_or_default(),
+                "ecosystems": ecosystems,
+                "packages": packages,
+            })
+        })
+        .collect();
+
+    Json(json!({
+        "count": jobs.len(),
+        "total_jobs": data.jobs.len(),
+        "jobs": jobs,
+    }))
+}
+
+/// Evaluate a job's dependencies against the policy.
+fn evaluate(
+    state: &AppState,
+    job_id: &str,
+    ignored: &[PackageDescriptor],
+) -> std::result::Result<PolicyEvaluationResponseRaw, ApiError> {
+    let job_id: JobId =
+        job_id.parse().map_err(|_| ApiError::not_found(format!("Job {job_id} does not exist")))?;
+
+    let data = state.data();
+    let job = data.job(job_id)?;
+
+    let dependencies: Vec<_> = job.packages.iter().map(Dependency::from).collect();
+    let job_link = format!("{}/projects/{}?label={}", state.base_uri, job.project, job.label);
+    Ok(state.policy.evaluate(&dependencies, ignored, Some(job_link)))
+}
+
+async fn evaluate_job(
+    State(state): State<Arc<AppState>>,
+    Path(job_id): Path<String>,
+    Json(request): Json<PolicyEvaluationRequest>,
+) -> Result<PolicyEvaluationResponse> {
+    let raw = evaluate(&state, &job_id, &request.ignored_packages)?;
+    Ok(Json(policy::summarize(&raw)))
+}
+
+async fn evaluate_job_raw(
+    State(state): State<Arc<AppState>>,
+    Path(job_id): Path<String>,
+    Json(request): Json<PolicyEvaluationRequest>,
+) -> Result<PolicyEvaluationResponseRaw> {
+    Ok(Json(evaluate(&state, &job_id, &request.ignored_packages)?))
+}
+
+async fn check_packages(
+    State(state): State<Arc<AppState>>,
+    Json(packages): Json<Vec<PackageDescriptor>>,
+) -> Json<PolicyEvaluationResponse> {
+    let dependencies: Vec<_> = packages.iter().map(Dependency::from).collect();
+    let raw = state.policy.evaluate(&dependencies, &[], None);
+    Json(policy::summarize(&raw))
+}
+
+async fn check_packages_raw(
+    State(state): State<Arc<AppState>>,
+    Json(packages): Json<Vec<PackageDescriptor>>,
+) -> Json<PolicyEvaluationResponseRaw> {
+    let dependencies: Vec<_> = packages.iter().map(Dependency::from).collect();
+    Json(state.policy.evaluate(&dependencies, &[], None))
+}
+
+async fn submit_package(
+    State(state): State<Arc<AppState>>,
+    Json(package): Json<PackageSpecifier>,
+) -> Json<Value> {
+    match state.fixtures.get(&package.registry, &package.name, &package.version) {
+        Some(package) => Json(json!({ "status": "AlreadyProcessed", "data": package })),
+        None => Json(json!({ "status": "New" })),
+    }
+}
+
+/// Request body for `/locksmith/v1/revoke`.
+#[derive(Deserialize)]
+struct RevokeTokenRequest {
+    name: String,
+}
+
+async fn list_tokens(State(state): State<Arc<AppState>>) -> Json<Vec<UserToken>> {
+    Json(state.data().tokens.clone())
+}
+
+async fn revoke_token(
+    State(state): State<Arc<AppState>>,
+    Json(request): Json<RevokeTokenRequest>,
+) -> std::result::Result<StatusCode, ApiError> {
+    let mut data = state.data();
+    if !data.tokens.iter().any(|token| token.name == request.name) {
+        return Err(ApiError::not_found(format!("Token {:?} does not exist", request.name)));
+    }
+    data.tokens.retain(|token| token.name != request.name);
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// No vulnerabilities are ever reachable.
+async fn vulnerabilities() -> Json<Vec<Value>> {
+    Json(Vec::new())
+}
@@ -0,0 +1,160 @@
+//! OIDC and locksmith authentication endpoints.
+//!
+//! Every login is approved immediately. Locksmith tokens and OIDC refresh
+//! tokens issued by the server are always [`API_KEY`].
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use axum::extract::{Query, Request, State};
+use axum::http::{header, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Redirect, Response};
+use axum::routing::{get, post};
+use axum::{Form, Json, Router};
+use chrono::Utc;
+use phylum_api::types::UserToken;
+use phylum_types::types::auth::{AccessToken, IdToken, RefreshToken, TokenResponse};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use url::Url;
+use uuid::Uuid;
+
+use crate::state::{ApiError, AppState};
+use crate::API_KEY;
+
+/// Access token issued by the OIDC token endpoint.
+const ACCESS_TOKEN: &str = "MOCK_ACCESS_TOKEN";
+
+/// Lifetime of issued access tokens.
+const ACCESS_TOKEN_LIFETIME_SECS: u32 = 3600;
+
+/// Grant types accepted by the OIDC token endpoint.
+const GRANT_TYPES: [&str; 3] = [
+    "refresh_token",
+    "urn:ietf:params:oauth:grant-type:device_code",
+    "urn:ietf:params:oauth:grant-type:token-exchange",
+];
+
+/// Routes which do not require authentication.
+pub(crate) fn public_routes() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/api/v0/.well-known/openid-configuration", get(oidc_configuration))
+        .route("/locksmith/v1/.well-known/locksmith-configuration", get(locksmith_configuration))
+        .route("/locksmith/v1/auth", get(authorize))
+        .route("/locksmith/v1/registrations", get(authorize))
+        .route("/locksmith/v1/token", post(locksmith_token))
+        .route("/oidc/token", post(oidc_token))
+        .route("/oidc/device", post(device_authorization))
+}
+
+/// Routes which require authentication.
+pub(crate) fn protected_routes() -> Router<Arc<AppState>> {
+    Router::new().route("/locksmith/v1/userinfo", get(user_info))
+}
+
+/// Reject requests without a bearer token.
+///
+/// The token itself is not validated, to allow using any credentials.
+pub(crate) async fn require_auth(request: Request, next: Next) -> Response {
+    let authorization = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|authorization| authorization.to_str().ok());
+
+    match authorization.and_then(|authorization| authorization.strip_prefix("Bearer ")) {
+        Some(token) if !token.trim().is_empty() => next.run(request).await,
+        _ => ApiError::new(StatusCode::UNAUTHORIZED, "Missing bearer token").into_response(),
+    }
+}
+
+async fn oidc_configuration(State(state): State<Arc<AppState>>) -> Json<Value> {
+    Json(json!({
+        "issuer": state.base_uri,
+        "token_endpoint": format!("{}/oidc/token", state.base_uri),
+        "device_authorization_endpoint": format!("{}/oidc/device", state.base_uri),
+    }))
+}
+
+async fn locksmith_configuration(State(state): State<Arc<AppState>>) -> Json<Value> {
+    Json(json!({
+        "authorization_endpoint": format!("{}/locksmith/v1/auth", state.base_uri),
+        "token_endpoint": format!("{}/locksmith/v1/token", state.base_uri),
+        "userinfo_endpoint": format!("{}/locksmith/v1/userinfo", state.base_uri),
+    }))
+}
+
+/// Authorization request query parameters.
+#[derive(Deserialize)]
+struct AuthorizeQuery {
+    redirect_uri: String,
+    state: Option<String>,
+}
+
+/// Approve the authorization request and redirect back to the client.
+async fn authorize(Query(query): Query<AuthorizeQuery>) -> Result<Redirect, ApiError> {
+    let mut redirect_uri = Url::parse(&query.redirect_uri).map_err(|err| {
+        ApiError::new(StatusCode::BAD_REQUEST, format!("Invalid redirect: {err}"))
+    })?;
+
+    redirect_uri.query_pairs_mut().append_pair("code", &Uuid::new_v4().to_string());
+    if let Some(state) = &query.state {
+        redirect_uri.query_pairs_mut().append_pair("state", state);
+    }
+
+    Ok(Redirect::to(redirect_uri.as_str()))
+}
+
+/// Exchange an authorization code for a locksmith token.
+async fn locksmith_token(
+    State(state): State<Arc<AppState>>,
+    Form(form): Form<HashMap<String, String>>,
+) -> Json<Value> {
+    if let Some(name) = form.get("name") {
+        let expiry = form.get("expiry").and_then(|expiry| expiry.parse().ok());
+        let token =
+            UserToken { name: name.clone(), creation_time: Utc::now(), access_time: None, expiry };
+
+        let mut data = state.data();
+        data.tokens.retain(|token| &token.name != name);
+        data.tokens.push(token);
+    }
+
+    Json(json!({ "token": API_KEY }))
+}
+
+/// Issue OIDC tokens.
+async fn oidc_token(Form(form): Form<HashMap<String, String>>) -> Response {
+    let grant_type = form.get("grant_type").map(String::as_str).unwrap_or_default();
+    if !GRANT_TYPES.contains(&grant_type) {
+        let error = json!({ "error": "unsupported_grant_type" });
+        return (StatusCode::BAD_REQUEST, Json(error)).into_response();
+    }
+
+    Json(TokenResponse {
+        access_token: AccessToken::new(ACCESS_TOKEN),
+        refresh_token: RefreshToken::new(API_KEY),
+        id_token: IdToken::new(ACCESS_TOKEN),
+        expires_in_seconds: ACCESS_TOKEN_LIFETIME_SECS,
+    })
+    .into_response()
+}
+
+/// Start a device authorization, which is approved immediately.
+async fn device_authorization(State(state): State<Arc<AppState>>) -> Json<Value> {
+    Json(json!({
+        "device_code": Uuid::new_v4().to_string(),
+        "user_code": "MOCK-CODE",
+        "verification_uri": format!("{}/oidc/device", state.base_uri),
+        "expires_in": 600,
+        "interval": 0,
+    }))
+}
+
+async fn user_info(State(state): State<Arc<AppState>>) -> Json<Value> {
+    Json(json!({
+        "sub": state.user.email,
+        "email": state.user.email,
+        "name": state.user.name,
+    }))
+}
@@ -0,0 +1,103 @@
+//! Package details served by the `/data/packages/submit` endpoint.
+//!
+//! Each fixture is a JSON file containing a package object in the format
+//! returned by the Phylum API, like this:
+//!
+//! ```json
+//! {
+//!   "id": "npm:lodash:4.17.21",
+//!   "name": "lodash",
+//!   "version": "4.17.21",
+//!   "registry": "npm",
+//!   "complete": true
+//! }
+//! ```
+//!
+//! Packages without a fixture are reported as newly submitted for processing.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use anyhow::{anyhow, Context, Result};
+use phylum_api::types::Package;
+use serde_json::Value;
+
+/// Package details by registry, name and version.
+#[derive(Clone, Default, Debug)]
+pub struct Fixtures {
+    packages: HashMap<(String, String, String), Value>,
+}
+
+impl Fixtures {
+    /// Recursively load all `.json` files in a directory.
+    pub fn from_dir(path: impl AsRef<Path>) -> Result<Self> {
+        let mut fixtures = Self::default();
+        fixtures.load_dir(path.as_ref())?;
+        Ok(fixtures)
+    }
+
+    fn load_dir(&mut self, dir: &Path) -> Result<()> {
+        let entries = fs::read_dir(dir)
+            .with_context(|| format!("Could not read fixture directory {dir:?}"))?;
+
+        for entry in entries {
+            let path = entry?.path();
+            if path.is_dir() {
+                self.load_dir(&path)?;
+            } else if path.extension().map_or(false, |extension| extension == "json") {
+                let package = fs::read_to_string(&path)
+                    .with_context(|| format!("Could not read fixture {path:?}"))?;
+                let package = serde_json::from_str(&package)
+                    .with_context(|| format!("Invalid fixture {path:?}"))?;
+                self.add(package).with_context(|| format!("Invalid fixture {path:?}"))?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Add package details.
+    ///
+    /// Replaces existing details for the same package.
+    pub fn add(&mut self, package: Value) -> Result<()> {
+        let parsed: Package = serde_json::from_value(package.clone())?;
+        if parsed.registry.is_empty() || parsed.name.is_empty() || parsed.version.is_empty() {
+            return Err(anyhow!("Package registry, name and version are required"));
+        }
+
+        let key = (parsed.registry.to_lowercase(), parsed.name, parsed.version);
+        self.packages.insert(key, package);
+
+        Ok(())
+    }
+
+    /// Get package details.
+    pub fn get(&self, registry: &str, name: &str, version: &str) -> Option<&Value> {
+        self.packages.get(&(registry.to_lowercase(), name.into(), version.into()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn lookup_is_case_insensitive_for_registry() {
+        let mut fixtures = Fixtures::default();
+        fixtures
+            .add(json!({ "id": "lodash", "name": "lodash", "version": "4.17.21", "registry": "npm" }))
+            .unwrap();
+
+        assert!(fixtures.get("NPM", "lodash", "4.17.21").is_some());
+        assert!(fixtures.get("npm", "lodash", "4.17.20").is_none());
+    }
+
+    #[test]
+    fn reject_incomplete_package() {
+        let mut fixtures = Fixtures::default();
+        assert!(fixtures.add(json!({ "name": "lodash" })).is_err());
+    }
+}
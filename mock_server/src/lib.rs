@@ -0,0 +1,343 @@
+//! In-memory Phylum API server for offline testing.
+//!
+//! The server implements the endpoints used by the CLI, its extensions and
+//! the [`phylum_api`] client. Projects, groups, organizations and jobs are
+//! kept in memory, analysis results are scripted through a [`Policy`] and
+//! package details are served from [`Fixtures`].
+//!
+//! All logins are approved immediately and every request is authenticated as
+//! the same user. The [`API_KEY`] can be used as the CLI's API token.
+//!
+//! ```no_run
+//! # async fn example() -> std::io::Result<()> {
+//! use phylum_mock_server::MockServer;
+//!
+//! let server = MockServer::builder().org("acme").start().await?;
+//! println!("Phylum API listening at {}", server.uri());
+//! # Ok(())
+//! # }
+//! ```
+
+use std::io;
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use axum::{middleware, Router};
+use phylum_api::types::{GroupRole, OrgMember};
+use tokio::net::TcpListener;
+use tokio::sync::Notify;
+
+pub use crate::fixtures::Fixtures;
+pub use crate::policy::Policy;
+use crate::state::{AppState, Data, Organization, User};
+
+mod api;
+mod auth;
+pub mod fixtures;
+pub mod policy;
+mod state;
+
+/// API key accepted by the server.
+///
+/// This is a locksmith token, so it is used as access token directly.
+pub const API_KEY: &str = "ph0_MOCK_API_KEY";
+
+/// Running mock server.
+///
+/// The server is shut down when this is dropped.
+pub struct MockServer {
+    addr: SocketAddr,
+    shutdown: Arc<Notify>,
+}
+
+impl MockServer {
+    /// Create a new server builder.
+    pub fn builder() -> MockServerBuilder {
+        MockServerBuilder::default()
+    }
+
+    /// Start a server with the default configuration on a random port.
+    pub async fn start() -> io::Result<Self> {
+        Self::builder().start().await
+    }
+
+    /// Base URI of the server.
+    ///
+    /// This is the API URI used in the CLI's configuration.
+    pub fn uri(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+
+    /// Address the server is listening on.
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        self.shutdown.notify_one();
+    }
+}
+
+/// Builder for [`MockServer`].
+pub struct MockServerBuilder {
+    addr: SocketAddr,
+    user: User,
+    orgs: Vec<String>,
+    policy: Policy,
+    fixtures: Fixtures,
+}
+
+impl Default for MockServerBuilder {
+    fn default() -> Self {
+        Self {
+            addr: SocketAddr::from(([127, 0, 0, 1], 0)),
+            user: User { email: "user@example.com".into(), name: "Mock User".into() },
+            orgs: Vec::new(),
+            policy: Policy::default(),
+            fixtures: Fixtures::default(),
+        }
+    }
+}
+
+impl MockServerBuilder {
+    /// Set the address to listen on.
+    ///
+    /// Defaults to a random port on localhost.
+    pub fn addr(mut self, addr: SocketAddr) -> Self {
+        self.addr = addr;
+        self
+    }
+
+    /// Set the authenticated user.
+    pub fn user(mut self, email: impl Into<String>, name: impl Into<String>) -> Self {
+        self.user = User { email: email.into(), name: name.into() };
+        self
+    }
+
+    /// Add an organization, with the user as its admin.
+    pub fn org(mut self, name: impl Into<String>) -> Self {
+        self.orgs.push(name.into());
+        self
+    }
+
+    /// Set the policy deciding analysis outcomes.
+    ///
+    /// By default all packages pass.
+    pub fn policy(mut self, policy: Policy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Set the package details served by the server.
+    pub fn fixtures(mut self, fixtures: Fixtures) -> Self {
+        self.fixtures = fixtures;
+        self
+    }
+
+    /// Start the server in the background.
+    pub async fn start(self) -> io::Result<MockServer> {
+        let listener = TcpListener::bind(self.addr).await?;
+        let addr = listener.local_addr()?;
+
+        let orgs = self
+            .orgs
+            .into_iter()
+            .map(|name| Organization {
+                name,
+                members: vec![OrgMember { email: self.user.email.clone(), role: GroupRole::Admin }],
+                groups: Vec::new(),
+            })
+            .collect();
+        let data = Data { orgs, ..Data::default() };
+
+        let base_uri = format!("http://{addr}");
+        let state = AppState::new(base_uri, self.user, self.policy, self.fixtures, data);
+        let router = router(Arc::new(state));
+
+        let shutdown = Arc::new(Notify::new());
+        let shutdown_signal = shutdown.clone();
+        tokio::spawn(async move {
+            let server = axum::serve(listener, router)
+                .with_graceful_shutdown(async move { shutdown_signal.notified().await });
+            if let Err(err) = server.await {
+                eprintln!("Mock server failed: {err}");
+            }
+        });
+
+        Ok(MockServer { addr, shutdown })
+    }
+}
+
+/// Create the router for all endpoints.
+fn router(state: Arc<AppState>) -> Router {
+    let protected = api::protected_routes()
+        .merge(auth::protected_routes())
+        .route_layer(middleware::from_fn(auth::require_auth));
+
+    Router::new()
+        .merge(api::public_routes())
+        .merge(auth::public_routes())
+        .merge(protected)
+        .with_state(state)
+}
+
+#[cfg(test)]
+mod tests {
+    use phylum_api::types::{AnalysisPackageDescriptor, JobMetadata, PackageSpecifier};
+    use phylum_api::{ApiError, Client, Error};
+    use phylum_types::types::auth::AccessToken;
+    use phylum_types::types::package::{
+        PackageDescriptor, PackageDescriptorAndLockfile, PackageType,
+    };
+    use serde_json::json;
+
+    use super::*;
+
+    async fn client(server: &MockServer) -> Client {
+        Client::builder(server.uri()).access_token(AccessToken::new(API_KEY)).build().unwrap()
+    }
+
+    fn package(name: &str) -> AnalysisPackageDescriptor {
+        AnalysisPackageDescriptor::PackageDescriptor(PackageDescriptorAndLockfile {
+            package_descriptor: PackageDescriptor {
+                name: name.into(),
+                version: "1.0.0".into(),
+                package_type: PackageType::Npm,
+            },
+            lockfile: Some("package-lock.json".into()),
+        })
+    }
+
+    #[tokio::test]
+    async fn unauthenticated_requests_are_rejected() {
+        let server = MockServer::start().await.unwrap();
+        let client = Client::builder(server.uri()).build().unwrap();
+
+        assert_eq!(client.ping().await.unwrap(), "alive");
+
+        let err = client.orgs().await.unwrap_err();
+        assert!(matches!(err, Error::Api(ApiError { status, .. }) if status.as_u16() == 401));
+    }
+
+    #[tokio::test]
+    async fn user_info() {
+        let server =
+            MockServer::builder().user("jane@example.com", "Jane Doe").start().await.unwrap();
+        let client = client(&server).await;
+
+        let user = client.user_info().await.unwrap();
+        assert_eq!(user.email, "jane@example.com");
+        assert_eq!(user.name.as_deref(), Some("Jane Doe"));
+    }
+
+    #[tokio::test]
+    async fn projects() {
+        let server = MockServer::builder().org("acme").start().await.unwrap();
+        let client = client(&server).await;
+
+        client.org_create_group("acme", "devs").await.unwrap();
+        let id = client
+            .create_project("demo", Some("acme".into()), Some("devs".into()), None)
+            .await
+            .unwrap();
+
+        let err = client
+            .create_project("demo", Some("acme".into()), Some("devs".into()), None)
+            .await
+            .unwrap_err();
+        assert_eq!(err.status().map(|status| status.as_u16()), Some(409));
+
+        let err = client.create_project("demo", None, Some("devs".into()), None).await.unwrap_err();
+        assert_eq!(err.status().map(|status| status.as_u16()), Some(404));
+
+        let found = client.get_project_id("demo", Some("acme"), Some("devs")).await.unwrap();
+        assert_eq!(found, id);
+
+        client.delete_project(id).await.unwrap();
+        assert!(client.get_projects(None, None, None).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn project_pagination() {
+        let server = MockServer::start().await.unwrap();
+        let client = client(&server).await;
+
+        for i in 0..105 {
+            client.create_project(format!("project-{i}"), None, None, None).await.unwrap();
+        }
+
+        assert_eq!(client.get_projects(None, None, None).await.unwrap().len(), 105);
+        assert_eq!(client.get_projects(None, None, Some("project-10")).await.unwrap().len(), 6);
+    }
+
+    #[tokio::test]
+    async fn groups_and_orgs() {
+        let server = MockServer::builder().org("acme").start().await.unwrap();
+        let client = client(&server).await;
+
+        client.create_group("legacy").await.unwrap();
+        client.group_add("legacy", "john@example.com").await.unwrap();
+        let groups = client.get_groups_list().await.unwrap().groups;
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].group_name, "legacy");
+
+        client.org_member_add("acme", "john@example.com").await.unwrap();
+        let members = client.org_members("acme").await.unwrap().members;
+        assert_eq!(members.len(), 2);
+        client.org_member_remove("acme", "john@example.com").await.unwrap();
+
+        let orgs = client.orgs().await.unwrap().organizations;
+        assert_eq!(orgs.len(), 1);
+        assert_eq!(orgs[0].name, "acme");
+    }
+
+    #[tokio::test]
+    async fn job_policy_outcome() {
+        let policy: Policy = serde_json::from_value(json!({
+            "rules": [{ "name": "evil", "outcome": "reject", "title": "Malware" }],
+        }))
+        .unwrap();
+        let server = MockServer::builder().policy(policy).start().await.unwrap();
+        let client = client(&server).await;
+
+        let project = client.create_project("demo", None, None, None).await.unwrap();
+        let packages = [package("evil"), package("good")];
+        let job_id = client
+            .submit_request(&packages, project, None, None, JobMetadata::default())
+            .await
+            .unwrap();
+
+        let raw = client.get_job_status_raw(&job_id, []).await.unwrap();
+        assert!(raw.is_failure);
+        assert_eq!(raw.dependencies.len(), 1);
+        assert_eq!(raw.dependencies[0].name, "evil");
+
+        let history = client.get_project_history("demo", None, None).await.unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].id, job_id.to_string());
+    }
+
+    #[tokio::test]
+    async fn package_fixtures() {
+        let mut fixtures = Fixtures::default();
+        fixtures
+            .add(json!({ "id": "lodash", "name": "lodash", "version": "4.17.21", "registry": "npm" }))
+            .unwrap();
+        let server = MockServer::builder().fixtures(fixtures).start().await.unwrap();
+        let client = client(&server).await;
+
+        let specifier = |version: &str| PackageSpecifier {
+            registry: "npm".into(),
+            name: "lodash".into(),
+            version: version.into(),
+        };
+
+        let response = client.submit_package(&specifier("4.17.21")).await.unwrap();
+        assert!(matches!(response, phylum_api::types::PackageSubmitResponse::AlreadyProcessed(_)));
+
+        let response = client.submit_package(&specifier("1.0.0")).await.unwrap();
+        assert!(matches!(response, phylum_api::types::PackageSubmitResponse::New));
+    }
+}
@@ -0,0 +1,84 @@
+use std::net::{IpAddr, SocketAddr};
+use std::path::PathBuf;
+
+use anyhow::Result;
+use clap::{value_parser, Arg, ArgAction, Command, ValueHint};
+use phylum_mock_server::{Fixtures, MockServer, Policy, API_KEY};
+
+fn app() -> Command {
+    Command::new("phylum-mock-server")
+        .version(env!("CARGO_PKG_VERSION"))
+        .about("In-memory Phylum API server for offline testing")
+        .args(&[
+            Arg::new("host")
+                .long("host")
+                .value_name("HOST")
+                .help("Address to listen on")
+                .value_parser(value_parser!(IpAddr))
+                .default_value("127.0.0.1"),
+            Arg::new("port")
+                .short('p')
+                .long("port")
+                .value_name("PORT")
+                .help("Port to listen on, random if not specified")
+                .value_parser(value_parser!(u16))
+                .default_value("0"),
+            Arg::new("fixtures")
+                .short('f')
+                .long("fixtures")
+                .value_name("DIR")
+                .help("Directory with package detail fixtures")
+                .value_parser(value_parser!(PathBuf))
+                .value_hint(ValueHint::DirPath),
+            Arg::new("policy")
+                .long("policy")
+                .value_name("FILE")
+                .help("JSON file with scripted policy outcomes")
+                .value_parser(value_parser!(PathBuf))
+                .value_hint(ValueHint::FilePath),
+            Arg::new("org")
+                .long("org")
+                .value_name("ORG")
+                .help("Organization to create, can be repeated")
+                .action(ArgAction::Append),
+        ])
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let matches = app().get_matches();
+
+    let host = *matches.get_one::<IpAddr>("host").unwrap();
+    let port = *matches.get_one::<u16>("port").unwrap();
+    let mut builder = MockServer::builder().addr(SocketAddr::new(host, port));
+
+    if let Some(dir) = matches.get_one::<PathBuf>("fixtures") {
+        builder = builder.fixtures(Fixtures::from_dir(dir)?);
+    }
+
+    if let Some(path) = matches.get_one::<PathBuf>("policy") {
+        builder = builder.policy(Policy::from_file(path)?);
+    }
+
+    for org in matches.get_many::<String>("org").into_iter().flatten() {
+        builder = builder.org(org);
+    }
+
+    let server = builder.start().await?;
+    println!("Phylum API listening at {}", server.uri());
+    println!("API key: {API_KEY}");
+
+    tokio::signal::ctrl_c().await?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn verify_app() {
+        app().debug_assert();
+    }
+}
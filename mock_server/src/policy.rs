@@ -0,0 +1,292 @@
+//! Scripted policy outcomes.
+//!
+//! Instead of analyzing packages, the server evaluates every dependency
+//! against a list of rules. The first matching rule decides whether the
+//! dependency is rejected or still being processed; dependencies without a
+//! matching rule pass.
+//!
+//! Policies are loaded from JSON files like this:
+//!
+//! ```json
+//! {
+//!   "rules": [
+//!     { "name": "lodash", "outcome": "reject", "title": "Known malware", "domain": "malicious_code" },
+//!     { "registry": "pypi", "outcome": "incomplete" }
+//!   ]
+//! }
+//! ```
+
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use phylum_api::types::{
+    AnalysisPackageDescriptor, EvaluatedDependency, PolicyEvaluationResponse,
+    PolicyEvaluationResponseRaw, PolicyRejection, RejectionSource, RiskDomain, RiskLevel,
+};
+use phylum_types::types::package::PackageDescriptor;
+use serde::{Deserialize, Serialize};
+
+/// Rules deciding the analysis outcome of dependencies.
+#[derive(Serialize, Deserialize, Clone, Default, Debug)]
+pub struct Policy {
+    pub rules: Vec<PolicyRule>,
+}
+
+impl Policy {
+    /// Load a policy from a JSON file.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let policy = fs::read_to_string(path)
+            .with_context(|| format!("Could not read policy file {path:?}"))?;
+        serde_json::from_str(&policy).with_context(|| format!("Invalid policy file {path:?}"))
+    }
+
+    /// Evaluate dependencies, suppressing rejections of ignored packages.
+    pub(crate) fn evaluate(
+        &self,
+        dependencies: &[Dependency],
+        ignored: &[PackageDescriptor],
+        job_link: Option<String>,
+    ) -> PolicyEvaluationResponseRaw {
+        let mut incomplete_packages_count = 0;
+        let mut evaluated = Vec::new();
+
+        for dependency in dependencies {
+            let rule = self.rules.iter().find(|rule| rule.matches(dependency));
+            let outcome = match rule {
+                Some(rule) => &rule.outcome,
+                None => continue,
+            };
+
+            let (title, domain, severity) = match outcome {
+                Outcome::Reject { title, domain, severity } => (title, domain, severity),
+                Outcome::Incomplete => {
+                    incomplete_packages_count += 1;
+                    continue;
+                },
+            };
+
+            let suppressed = ignored.iter().any(|package| dependency.is(package));
+            let rejection = PolicyRejection {
+                title: title.clone(),
+                source: RejectionSource {
+                    source_type: "mock".into(),
+                    tag: None,
+                    domain: *domain,
+                    severity: *severity,
+                    description: None,
+                    reason: None,
+                },
+                suppressed,
+            };
+
+            evaluated.push(EvaluatedDependency {
+                purl: dependency.purl.clone(),
+                registry: dependency.registry.clone(),
+                name: dependency.name.clone(),
+                version: dependency.version.clone(),
+                rejections: vec![rejection],
+            });
+        }
+
+        let is_failure = evaluated
+            .iter()
+            .flat_map(|dependency| &dependency.rejections)
+            .any(|rejection| !rejection.suppressed);
+
+        PolicyEvaluationResponseRaw {
+            is_failure,
+            incomplete_packages_count,
+            help: "Policy outcomes are scripted by phylum-mock-server".into(),
+            dependencies: evaluated,
+            job_link,
+        }
+    }
+}
+
+/// Outcome for all dependencies matching the rule's filters.
+///
+/// Filters which are not set match every dependency.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct PolicyRule {
+    /// Ecosystem of the dependency, like `npm`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub registry: Option<String>,
+    /// Name of the dependency.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Version of the dependency.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    #[serde(flatten)]
+    pub outcome: Outcome,
+}
+
+impl PolicyRule {
+    fn matches(&self, dependency: &Dependency) -> bool {
+        let matches = |filter: &Option<String>, value: &str| {
+            filter.as_ref().map_or(true, |filter| filter.eq_ignore_ascii_case(value))
+        };
+
+        matches(&self.registry, &dependency.registry)
+            && matches(&self.name, &dependency.name)
+            && matches(&self.version, &dependency.version)
+    }
+}
+
+/// Analysis outcome of a dependency.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(tag = "outcome", rename_all = "snake_case")]
+pub enum Outcome {
+    /// Reject the dependency.
+    Reject {
+        title: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        domain: Option<RiskDomain>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        severity: Option<RiskLevel>,
+    },
+    /// Keep the dependency's analysis pending.
+    Incomplete,
+}
+
+/// Dependency submitted for analysis.
+#[derive(Clone, Debug)]
+pub(crate) struct Dependency {
+    pub purl: String,
+    pub registry: String,
+    pub name: String,
+    pub version: String,
+}
+
+impl Dependency {
+    /// Check if the dependency matches a package descriptor.
+    fn is(&self, package: &PackageDescriptor) -> bool {
+        self.registry == package.package_type.to_string()
+            && self.name == package.name
+            && self.version == package.version
+    }
+}
+
+impl From<&PackageDescriptor> for Dependency {
+    fn from(package: &PackageDescriptor) -> Self {
+        let registry = package.package_type.to_string();
+        Self {
+            purl: format!("pkg:{registry}/{}@{}", package.name, package.version),
+            registry,
+            name: package.name.clone(),
+            version: package.version.clone(),
+        }
+    }
+}
+
+impl From<&AnalysisPackageDescriptor> for Dependency {
+    fn from(package: &AnalysisPackageDescriptor) -> Self {
+        match package {
+            AnalysisPackageDescriptor::PackageDescriptor(package) => {
+                Self::from(&package.package_descriptor)
+            },
+            AnalysisPackageDescriptor::Purl(package) => Self::from_purl(package.purl()),
+        }
+    }
+}
+
+impl Dependency {
+    /// Split a package URL like `pkg:npm/%40scope/name@1.0.0` into its parts.
+    fn from_purl(purl: &str) -> Self {
+        let path = purl.strip_prefix("pkg:").unwrap_or(purl);
+        let path = path.split(['?', '#']).next().unwrap_or_default();
+        let (registry, package) = path.split_once('/').unwrap_or(("", path));
+        let (name, version) = package.rsplit_once('@').unwrap_or((package, ""));
+
+        Self {
+            purl: purl.into(),
+            registry: registry.to_lowercase(),
+            name: name.replace("%40", "@"),
+            version: version.into(),
+        }
+    }
+}
+
+/// Summarize raw evaluation results.
+pub(crate) fn summarize(raw: &PolicyEvaluationResponseRaw) -> PolicyEvaluationResponse {
+    let mut output = String::new();
+    let mut report = String::from("# Phylum analysis\n");
+
+    for dependency in &raw.dependencies {
+        for rejection in dependency.rejections.iter().filter(|rejection| !rejection.suppressed) {
+            output.push_str(&format!("[{}] {}\n", dependency.purl, rejection.title));
+            report.push_str(&format!("\n- `{}`: {}", dependency.purl, rejection.title));
+        }
+    }
+
+    if raw.incomplete_packages_count > 0 {
+        output.push_str(&format!(
+            "{} packages are still processing\n",
+            raw.incomplete_packages_count
+        ));
+    }
+
+    if output.is_empty() {
+        output.push_str("All packages passed the policy\n");
+    }
+
+    PolicyEvaluationResponse {
+        is_failure: raw.is_failure,
+        incomplete_count: raw.incomplete_packages_count,
+        output,
+        report,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dependency(name: &str) -> Dependency {
+        Dependency::from_purl(&format!("pkg:npm/{name}@1.0.0"))
+    }
+
+    #[test]
+    fn parse_purl() {
+        let dependency = Dependency::from_purl("pkg:npm/%40scope/name@1.2.3?arch=x86");
+        assert_eq!(dependency.registry, "npm");
+        assert_eq!(dependency.name, "@scope/name");
+        assert_eq!(dependency.version, "1.2.3");
+    }
+
+    #[test]
+    fn scripted_outcomes() {
+        let policy: Policy = serde_json::from_value(serde_json::json!({
+            "rules": [
+                { "name": "evil", "outcome": "reject", "title": "Malware", "domain": "malicious_code" },
+                { "name": "pending", "outcome": "incomplete" },
+            ],
+        }))
+        .unwrap();
+
+        let dependencies = [dependency("evil"), dependency("pending"), dependency("good")];
+        let raw = policy.evaluate(&dependencies, &[], None);
+
+        assert!(raw.is_failure);
+        assert_eq!(raw.incomplete_packages_count, 1);
+        assert_eq!(raw.dependencies.len(), 1);
+        assert_eq!(raw.dependencies[0].name, "evil");
+        assert_eq!(raw.dependencies[0].rejections[0].source.domain, Some(RiskDomain::Malicious));
+
+        let summary = summarize(&raw);
+        assert!(summary.is_failure);
+        assert_eq!(
+            summary.output,
+            "[pkg:npm/evil@1.0.0] Malware\n1 packages are still processing\n"
+        );
+    }
+
+    #[test]
+    fn empty_policy_passes() {
+        let raw = Policy::default().evaluate(&[dependency("good")], &[], None);
+        assert!(!raw.is_failure);
+        assert!(raw.dependencies.is_empty());
+    }
+}
@@ -0,0 +1,159 @@
+//! Shared server state.
+
+use std::sync::{Mutex, MutexGuard};
+
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use chrono::{DateTime, Utc};
+use phylum_api::types::{AnalysisPackageDescriptor, OrgMember, UserToken};
+use phylum_types::types::common::{JobId, ProjectId};
+use phylum_types::types::package::PackageType;
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::fixtures::Fixtures;
+use crate::policy::Policy;
+
+/// State shared by all request handlers.
+pub(crate) struct AppState {
+    /// Base URI the server is reachable at.
+    pub base_uri: String,
+    /// The authenticated user.
+    pub user: User,
+    pub policy: Policy,
+    pub fixtures: Fixtures,
+    data: Mutex<Data>,
+}
+
+impl AppState {
+    pub fn new(
+        base_uri: String,
+        user: User,
+        policy: Policy,
+        fixtures: Fixtures,
+        data: Data,
+    ) -> Self {
+        Self { base_uri, user, policy, fixtures, data: Mutex::new(data) }
+    }
+
+    /// Lock the mutable server data.
+    pub fn data(&self) -> MutexGuard<'_, Data> {
+        // Handlers never panic while holding the lock, so poisoning is a bug.
+        self.data.lock().expect("poisoned server state")
+    }
+}
+
+/// User all requests are authenticated as.
+#[derive(Clone, Debug)]
+pub(crate) struct User {
+    pub email: String,
+    pub name: String,
+}
+
+/// Mutable server data.
+#[derive(Default)]
+pub(crate) struct Data {
+    pub projects: Vec<Project>,
+    pub groups: Vec<Group>,
+    pub orgs: Vec<Organization>,
+    pub jobs: Vec<Job>,
+    pub tokens: Vec<UserToken>,
+}
+
+impl Data {
+    pub fn project_mut(&mut self, id: ProjectId) -> Result<&mut Project, ApiError> {
+        self.projects
+            .iter_mut()
+            .find(|project| project.id == id)
+            .ok_or_else(|| ApiError::not_found(format!("Project {id} does not exist")))
+    }
+
+    pub fn group_mut(&mut self, name: &str) -> Result<&mut Group, ApiError> {
+        self.groups
+            .iter_mut()
+            .find(|group| group.name == name)
+            .ok_or_else(|| ApiError::not_found(format!("Group {name:?} does not exist")))
+    }
+
+    pub fn org_mut(&mut self, name: &str) -> Result<&mut Organization, ApiError> {
+        self.orgs
+            .iter_mut()
+            .find(|org| org.name == name)
+            .ok_or_else(|| ApiError::not_found(format!("Organization {name:?} does not exist")))
+    }
+
+    pub fn job(&self, id: JobId) -> Result<&Job, ApiError> {
+        self.jobs
+            .iter()
+            .find(|job| job.id == id)
+            .ok_or_else(|| ApiError::not_found(format!("Job {id} does not exist")))
+    }
+}
+
+pub(crate) struct Project {
+    pub id: ProjectId,
+    pub name: String,
+    pub org: Option<String>,
+    pub group: Option<String>,
+    pub repository_url: Option<String>,
+    pub default_label: Option<String>,
+    pub ecosystems: Vec<PackageType>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Legacy group, which is not part of an organization.
+pub(crate) struct Group {
+    pub name: String,
+    pub owner: String,
+    pub members: Vec<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+pub(crate) struct Organization {
+    pub name: String,
+    pub members: Vec<OrgMember>,
+    pub groups: Vec<String>,
+}
+
+pub(crate) struct Job {
+    pub id: JobId,
+    pub project: ProjectId,
+    pub label: String,
+    pub packages: Vec<AnalysisPackageDescriptor>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Error response in the format of the Phylum API.
+#[derive(Debug)]
+pub(crate) struct ApiError {
+    status: StatusCode,
+    description: String,
+}
+
+impl ApiError {
+    pub fn new(status: StatusCode, description: impl Into<String>) -> Self {
+        Self { status, description: description.into() }
+    }
+
+    pub fn not_found(description: impl Into<String>) -> Self {
+        Self::new(StatusCode::NOT_FOUND, description)
+    }
+
+    pub fn conflict(description: impl Into<String>) -> Self {
+        Self::new(StatusCode::CONFLICT, description)
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let body = json!({
+            "error": {
+                "error_id": Uuid::new_v4().to_string(),
+                "description": self.description,
+            },
+        });
+        (self.status, Json(body)).into_response()
+    }
+}
@@ -1,9 +1,9 @@
-/// API endpoint paths
+//! API endpoint paths.
+
+use phylum_types::types::common::JobId;
 use thiserror::Error as ThisError;
 use url::{ParseError, Url};
 
-use super::JobId;
-
 /// Phylum API base path.
 const API_PATH: &str = "api/v0/";
 
@@ -115,17 +115,17 @@ pub fn project(api_uri: &str, project_id: &str) -> Result<Url, BaseUriError> {
 }
 
 /// GET /groups
-pub(crate) fn group_list(api_uri: &str) -> Result<Url, BaseUriError> {
+pub fn group_list(api_uri: &str) -> Result<Url, BaseUriError> {
     Ok(get_api_path(api_uri)?.join("groups")?)
 }
 
 /// POST /groups
-pub(crate) fn group_create(api_uri: &str) -> Result<Url, BaseUriError> {
+pub fn group_create(api_uri: &str) -> Result<Url, BaseUriError> {
     Ok(get_api_path(api_uri)?.join("groups")?)
 }
 
 /// DELETE /groups/<groupName>
-pub(crate) fn group_delete(api_uri: &str, group: &str) -> Result<Url, BaseUriError> {
+pub fn group_delete(api_uri: &str, group: &str) -> Result<Url, BaseUriError> {
     let mut url = get_api_path(api_uri)?;
     url.path_segments_mut().unwrap().pop_if_empty().extend(["groups", group]);
     Ok(url)
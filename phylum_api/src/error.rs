@@ -0,0 +1,85 @@
+//! Error types of the API client.
+
+use std::error::Error as StdError;
+use std::io;
+use std::path::PathBuf;
+
+use reqwest::StatusCode;
+use thiserror::Error as ThisError;
+
+use crate::endpoints::BaseUriError;
+
+/// Boxed error returned by a [`TokenSource`](crate::TokenSource).
+pub type BoxError = Box<dyn StdError + Send + Sync>;
+
+/// Result type of the API client.
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// API client error.
+#[derive(ThisError, Debug)]
+pub enum Error {
+    /// The request could not be sent or its response could not be received.
+    #[error("Error invoking REST endpoint")]
+    Http(#[from] reqwest::Error),
+    /// The configured base URL is invalid.
+    #[error(transparent)]
+    BaseUri(#[from] BaseUriError),
+    /// The API responded with a non-successful status code.
+    #[error(transparent)]
+    Api(#[from] ApiError),
+    /// The response body did not match the expected type.
+    #[error("Invalid response from the Phylum API")]
+    Decode(#[source] serde_json::Error),
+    /// The token source failed to provide an access token.
+    #[error("Could not get access token")]
+    Auth(#[source] BoxError),
+    /// The client's connection settings are invalid.
+    #[error(transparent)]
+    Config(#[from] ConfigError),
+    /// No project matches the requested name.
+    #[error("No project found with name {0:?}")]
+    ProjectNotFound(String),
+}
+
+impl Error {
+    /// HTTP status code returned by the API, if any.
+    pub fn status(&self) -> Option<StatusCode> {
+        match self {
+            Error::Http(error) => error.status(),
+            Error::Api(error) => Some(error.status),
+            _ => None,
+        }
+    }
+}
+
+/// Non-successful API response.
+#[derive(ThisError, Debug)]
+#[error("HTTP request error ({status}):\n\n{message}")]
+pub struct ApiError {
+    pub status: StatusCode,
+    /// Server-side identifier for the error, useful for support requests.
+    pub error_id: Option<String>,
+    /// Error description, or the raw response body if it was not JSON.
+    pub message: String,
+}
+
+/// Invalid connection settings.
+#[derive(ThisError, Debug)]
+pub enum ConfigError {
+    #[error("Could not read CA bundle {path:?}")]
+    ReadCaBundle { path: PathBuf, source: io::Error },
+    #[error("Invalid certificate in CA bundle {path:?}")]
+    InvalidCaBundle { path: PathBuf, source: BoxError },
+    #[error("No certificates in CA bundle {path:?}")]
+    EmptyCaBundle { path: PathBuf },
+    #[error("Invalid proxy URL {url:?}")]
+    InvalidProxy { url: String, source: reqwest::Error },
+    #[error("Could not read client certificate {path:?}")]
+    ReadClientCert { path: PathBuf, source: io::Error },
+    #[error("Could not read client key {path:?}")]
+    ReadClientKey { path: PathBuf, source: io::Error },
+    #[error("Invalid client certificate {path:?}")]
+    InvalidClientCert { path: PathBuf, source: reqwest::Error },
+    #[error("Could not build HTTP client")]
+    Client(#[source] reqwest::Error),
+}
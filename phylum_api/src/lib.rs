@@ -0,0 +1,27 @@
+//! Async client for the Phylum API.
+//!
+//! ```no_run
+//! # async fn example() -> phylum_api::Result<()> {
+//! use std::time::Duration;
+//!
+//! use phylum_types::types::auth::AccessToken;
+//!
+//! let client = phylum_api::Client::builder("https://api.phylum.io")
+//!     .access_token(AccessToken::new("ACCESS_TOKEN"))
+//!     .timeout(Duration::from_secs(30))
+//!     .build()?;
+//!
+//! let projects = client.get_projects(None, None, None).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+mod client;
+pub mod endpoints;
+pub mod error;
+pub mod http;
+pub mod retry;
+pub mod types;
+
+pub use client::{Client, ClientBuilder, Token, TokenSource};
+pub use error::{ApiError, Error, Result};
@@ -0,0 +1,208 @@
+//! Retry policy for transient API failures.
+
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use rand::Rng;
+use reqwest::header::{HeaderMap, HeaderValue, RETRY_AFTER};
+use reqwest::{Method, StatusCode};
+use serde::{Deserialize, Serialize};
+
+/// Header used by the server to deduplicate repeated requests.
+pub const IDEMPOTENCY_KEY: &str = "Idempotency-Key";
+
+/// Retry policy for failed API requests.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct RetryConfig {
+    /// Maximum number of retries after the first attempt.
+    pub max_retries: u32,
+    /// Delay before the first retry in milliseconds.
+    pub initial_delay_ms: u64,
+    /// Maximum delay between retries in milliseconds.
+    pub max_delay_ms: u64,
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        Self { max_retries: 3, initial_delay_ms: 500, max_delay_ms: 30_000 }
+    }
+}
+
+impl RetryConfig {
+    /// Check if all settings have their default value.
+    pub fn is_default(&self) -> bool {
+        self == &Self::default()
+    }
+}
+
+/// Retry policy with exponential backoff.
+#[derive(Clone, Debug)]
+pub struct RetryPolicy {
+    config: RetryConfig,
+}
+
+impl RetryPolicy {
+    pub fn new(config: RetryConfig) -> Self {
+        Self { config }
+    }
+
+    /// Maximum number of retries after the first attempt.
+    pub fn max_retries(&self) -> u32 {
+        self.config.max_retries
+    }
+
+    /// Delay before retrying a request which received a response.
+    ///
+    /// Returns `None` if the request should not be retried.
+    pub fn response_delay(
+        &self,
+        attempt: u32,
+        retry_safe: bool,
+        status: StatusCode,
+        headers: &HeaderMap,
+    ) -> Option<Duration> {
+        // Rate limited requests were never processed, so they're always safe to retry.
+        let transient = matches!(status.as_u16(), 429 | 502 | 503 | 504);
+        if !transient || !(retry_safe || status == StatusCode::TOO_MANY_REQUESTS) {
+            return None;
+        }
+
+        self.delay(attempt, headers.get(RETRY_AFTER))
+    }
+
+    /// Delay before retrying a request which failed without a response.
+    ///
+    /// Returns `None` if the request should not be retried.
+    pub fn error_delay(
+        &self,
+        attempt: u32,
+        retry_safe: bool,
+        error: &reqwest::Error,
+    ) -> Option<Duration> {
+        // Requests which failed to connect never reached the server.
+        let transient = error.is_connect() || error.is_timeout() || error.is_request();
+        if !transient || !(retry_safe || error.is_connect()) {
+            return None;
+        }
+
+        self.delay(attempt, None)
+    }
+
+    /// Delay before the next attempt, `None` once all retries are exhausted.
+    fn delay(&self, attempt: u32, retry_after: Option<&HeaderValue>) -> Option<Duration> {
+        if attempt >= self.config.max_retries {
+            return None;
+        }
+
+        let max_delay = Duration::from_millis(self.config.max_delay_ms);
+
+        // Prefer the server's requested delay over our own backoff.
+        if let Some(retry_after) = retry_after.and_then(parse_retry_after) {
+            return Some(retry_after.min(max_delay));
+        }
+
+        // Exponential backoff with jitter over the upper half of the delay.
+        let backoff = self.config.initial_delay_ms.saturating_mul(1 << attempt.min(32));
+        let backoff = backoff.min(self.config.max_delay_ms);
+        let jitter = rand::thread_rng().gen_range(0..=backoff / 2);
+
+        Some(Duration::from_millis(backoff - jitter))
+    }
+}
+
+/// Check if a request method can be sent multiple times without side effects.
+///
+/// Other requests are only retried if they never reached the server or were
+/// rate limited, since the server might have processed them otherwise.
+pub fn is_retry_safe(method: &Method) -> bool {
+    matches!(*method, Method::GET | Method::HEAD | Method::OPTIONS | Method::PUT | Method::DELETE)
+}
+
+/// Check if a failed request might have been processed by the server.
+///
+/// Gateways can fail after forwarding the request, so its side effects might
+/// have happened despite the error.
+pub fn is_gateway_error(status: StatusCode) -> bool {
+    matches!(
+        status,
+        StatusCode::BAD_GATEWAY | StatusCode::SERVICE_UNAVAILABLE | StatusCode::GATEWAY_TIMEOUT
+    )
+}
+
+/// Parse a `Retry-After` header in seconds or as HTTP date.
+fn parse_retry_after(value: &HeaderValue) -> Option<Duration> {
+    let value = value.to_str().ok()?.trim();
+
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let date = DateTime::parse_from_rfc2822(value).ok()?;
+    let delay = date.with_timezone(&Utc) - Utc::now();
+    Some(delay.to_std().unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy() -> RetryPolicy {
+        RetryPolicy::new(RetryConfig { max_retries: 3, initial_delay_ms: 100, max_delay_ms: 300 })
+    }
+
+    #[test]
+    fn exponential_backoff() {
+        let policy = policy();
+        let headers = HeaderMap::new();
+        let status = StatusCode::BAD_GATEWAY;
+
+        for (attempt, max) in [(0, 100), (1, 200), (2, 300)] {
+            let delay = policy.response_delay(attempt, true, status, &headers).unwrap();
+            assert!(delay >= Duration::from_millis(max / 2) && delay <= Duration::from_millis(max));
+        }
+        assert_eq!(policy.response_delay(3, true, status, &headers), None);
+    }
+
+    #[test]
+    fn retry_conditions() {
+        let policy = policy();
+        let headers = HeaderMap::new();
+
+        assert!(policy
+            .response_delay(0, true, StatusCode::SERVICE_UNAVAILABLE, &headers)
+            .is_some());
+        assert!(policy
+            .response_delay(0, true, StatusCode::INTERNAL_SERVER_ERROR, &headers)
+            .is_none());
+        assert!(policy.response_delay(0, false, StatusCode::BAD_GATEWAY, &headers).is_none());
+        assert!(policy.response_delay(0, false, StatusCode::TOO_MANY_REQUESTS, &headers).is_some());
+
+        assert!(!is_retry_safe(&Method::POST));
+        assert!(is_retry_safe(&Method::GET));
+        assert!(is_retry_safe(&Method::PUT));
+
+        assert!(is_gateway_error(StatusCode::GATEWAY_TIMEOUT));
+        assert!(!is_gateway_error(StatusCode::TOO_MANY_REQUESTS));
+    }
+
+    #[test]
+    fn retry_after() {
+        let policy = policy();
+        let status = StatusCode::TOO_MANY_REQUESTS;
+
+        let mut headers = HeaderMap::new();
+        headers.insert(RETRY_AFTER, HeaderValue::from_static("0"));
+        assert_eq!(policy.response_delay(0, false, status, &headers), Some(Duration::ZERO));
+
+        // Delays are limited to the maximum delay.
+        headers.insert(RETRY_AFTER, HeaderValue::from_static("60"));
+        assert_eq!(
+            policy.response_delay(0, false, status, &headers),
+            Some(Duration::from_millis(300))
+        );
+
+        let date = HeaderValue::from_static("Wed, 21 Oct 2015 07:28:00 GMT");
+        assert_eq!(parse_retry_after(&date), Some(Duration::ZERO));
+    }
+}
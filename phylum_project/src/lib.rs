@@ -23,6 +23,8 @@ pub struct ProjectConfig {
     lockfile_path: Option<String>,
     #[serde(default, alias = "lockfiles")]
     depfiles: Vec<DepfileConfig>,
+    #[serde(default, skip_serializing_if = "InternalPackages::is_empty")]
+    internal_packages: InternalPackages,
     #[serde(skip)]
     root: PathBuf,
 }
@@ -38,6 +40,7 @@ impl ProjectConfig {
             lockfile_type: None,
             lockfile_path: None,
             depfiles: Default::default(),
+            internal_packages: Default::default(),
             root: Default::default(),
         }
     }
@@ -83,6 +86,31 @@ impl ProjectConfig {
     pub fn root(&self) -> &PathBuf {
         &self.root
     }
+
+    /// Get the project's internal package configuration.
+    pub fn internal_packages(&self) -> &InternalPackages {
+        &self.internal_packages
+    }
+}
+
+/// Packages published through internal registries.
+///
+/// Used to detect dependency confusion with public registries.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct InternalPackages {
+    /// Package name prefixes or scopes reserved for internal packages.
+    #[serde(default)]
+    pub namespaces: Vec<String>,
+    /// URLs of internal package registries.
+    #[serde(default)]
+    pub registries: Vec<String>,
+}
+
+impl InternalPackages {
+    /// Check if no internal packages are configured.
+    pub fn is_empty(&self) -> bool {
+        self.namespaces.is_empty() && self.registries.is_empty()
+    }
 }
 
 /// Dependency file metadata.
@@ -217,6 +245,22 @@ mod tests {
         assert_eq!(&PathBuf::from(r"C:\home\user\project\Cargo.lock"), &depfile.path);
     }
 
+    #[test]
+    fn deserialize_internal_packages() {
+        let config = "id: a814bc7b-c17c-4e91-9515-edd7899680fb\n\
+                      name: my project\n\
+                      created_at: 2023-01-01T00:00:00+00:00\n\
+                      group_name: null\n\
+                      internal_packages:\n  \
+                        namespaces: ['@acme']\n  \
+                        registries: ['https://npm.acme.com']\n";
+        let config: ProjectConfig = serde_yaml::from_str(config).unwrap();
+
+        let internal = config.internal_packages();
+        assert_eq!(internal.namespaces, vec!["@acme".to_owned()]);
+        assert_eq!(internal.registries, vec!["https://npm.acme.com".to_owned()]);
+    }
+
     #[cfg(any(unix, windows))]
     #[test]
     fn find_project_conf_can_recurse_up() {